@@ -0,0 +1,86 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use polars::prelude::*;
+use trading_sessions::{identify_many, IdentifyTradingSession, SessionColumn, TradingSession};
+
+const ROWS: i64 = 1_000_000;
+
+fn timestamps() -> Vec<i64> {
+    // One timestamp per minute starting on Thursday 22 February 2024, 00:00 UTC
+    (0..ROWS).map(|i| 1708560000 + i * 60).collect()
+}
+
+fn frame() -> DataFrame {
+    df! { "time" => timestamps() }.unwrap()
+}
+
+fn bench_apply_session_column(c: &mut Criterion) {
+    let df = frame();
+    let mut group = c.benchmark_group("apply_session_column");
+
+    group.bench_function("serial", |b| {
+        b.iter(|| {
+            SessionColumn::new(df.clone().lazy())
+                .apply_session_column()
+                .into_lazyframe()
+                .collect()
+                .unwrap()
+        })
+    });
+
+    group.bench_function("parallel", |b| {
+        b.iter(|| {
+            let mut session_column = SessionColumn::new(df.clone().lazy());
+            session_column.apply_session_column_parallel();
+            session_column.into_lazyframe().collect().unwrap()
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_identify_many(c: &mut Criterion) {
+    let timestamps = timestamps();
+    let mut group = c.benchmark_group("identify_many");
+
+    group.bench_function("scalar_loop", |b| {
+        b.iter(|| {
+            timestamps
+                .iter()
+                .map(|&time| IdentifyTradingSession::new(time).trading_session())
+                .collect::<Vec<_>>()
+        })
+    });
+
+    group.bench_function("batch", |b| b.iter(|| identify_many(&timestamps)));
+
+    group.finish();
+}
+
+fn bench_identify_trading_session(c: &mut Criterion) {
+    let timestamps = timestamps();
+    let mut group = c.benchmark_group("identify_trading_session");
+
+    // Both paths look the session up in a table and never allocate
+    group.bench_function("enum", |b| {
+        b.iter(|| {
+            timestamps
+                .iter()
+                .filter(|&&time| IdentifyTradingSession::new(time).identify_trading_session() == TradingSession::London)
+                .count()
+        })
+    });
+
+    group.bench_function("label", |b| {
+        b.iter(|| {
+            timestamps
+                .iter()
+                .filter(|&&time| IdentifyTradingSession::new(time).identify_trading_session_label() == "London")
+                .count()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_apply_session_column, bench_identify_many, bench_identify_trading_session);
+criterion_main!(benches);
@@ -0,0 +1,111 @@
+//! A small command-line interface to the trading session identification, for shell pipelines and cron jobs.
+//!
+//! Requires the `cli` feature, e.g. `cargo install trading_sessions --features cli`.
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, Write};
+use std::process::ExitCode;
+use std::time::SystemTime;
+
+use chrono::{Months, NaiveDate};
+use polars::prelude::*;
+use trading_sessions::{sessions_between, IdentifyTradingSession, SessionColumn};
+
+const USAGE: &str = "\
+Usage:
+    sessions now
+    sessions at <unix timestamp>
+    sessions annotate <input.csv> [--time-col <column>] --out <output.csv>
+    sessions calendar <year>-<month>";
+
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let result = match args.as_slice() {
+        ["now"] => now(),
+        ["at", unix_timestamp] => at(unix_timestamp),
+        ["annotate", input, options @ ..] => annotate(input, options),
+        ["calendar", month] => calendar(month),
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        // The reader of a pipeline, e.g. `head`, stopped listening
+        Err(error) if error.downcast_ref::<io::Error>().is_some_and(|error| error.kind() == io::ErrorKind::BrokenPipe) => {
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+
+/// Prints the trading session of the system clock.
+fn now() -> Result<(), Box<dyn Error>> {
+    println!("{}", IdentifyTradingSession::from_system_time(SystemTime::now())?.trading_session());
+    Ok(())
+}
+
+
+/// Prints the trading session of a Unix timestamp in seconds.
+fn at(unix_timestamp: &str) -> Result<(), Box<dyn Error>> {
+    let unix_timestamp: i64 = unix_timestamp.parse().map_err(|_| format!("'{unix_timestamp}' is not a Unix timestamp"))?;
+    println!("{}", IdentifyTradingSession::new(unix_timestamp).trading_session());
+    Ok(())
+}
+
+
+/// Adds a "Session" column to a CSV file of Unix timestamps in seconds.
+fn annotate(input: &str, options: &[&str]) -> Result<(), Box<dyn Error>> {
+    let (mut time_col, mut output) = ("time", None);
+    for option in options.chunks(2) {
+        match option {
+            ["--time-col", column] => time_col = column,
+            ["--out", path] => output = Some(*path),
+            _ => return Err(format!("unexpected arguments {option:?}\n{USAGE}").into()),
+        }
+    }
+    let output = output.ok_or(format!("annotate requires an --out file\n{USAGE}"))?;
+
+    // Only CSV is read and written, as the Polars build of this crate leaves out the Parquet reader
+    for path in [input, output] {
+        if !path.ends_with(".csv") {
+            return Err(format!("'{path}' is not a CSV file").into());
+        }
+    }
+
+    let df = CsvReader::from_path(input)?.has_header(true).finish()?;
+    let mut df = SessionColumn::new(df.lazy())
+        .with_columns(time_col, "Session")
+        .try_apply_session_column()?
+        .into_lazyframe()
+        .collect()?;
+    CsvWriter::new(File::create(output)?).finish(&mut df)?;
+    Ok(())
+}
+
+
+/// Prints the `(start, end, session)` intervals of a calendar month in UTC.
+fn calendar(month: &str) -> Result<(), Box<dyn Error>> {
+    let first_day = NaiveDate::parse_from_str(&format!("{month}-01"), "%Y-%m-%d")
+        .map_err(|_| format!("'{month}' is not a month, e.g. 2024-03"))?;
+    let next_month = first_day.checked_add_months(Months::new(1)).ok_or(format!("'{month}' is out of range"))?;
+
+    let start = first_day.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc().timestamp();
+    let end = next_month.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc().timestamp();
+    let mut stdout = io::stdout().lock();
+    for (session, start, end) in sessions_between(start, end) {
+        let to_datetime = |unix_timestamp| IdentifyTradingSession::new(unix_timestamp).to_datetime().ok_or("the session bounds are out of range");
+        let (start, end) = (to_datetime(start)?, to_datetime(end)?);
+        writeln!(stdout, "{}  {}  {}", start.format("%Y-%m-%d %H:%M"), end.format("%Y-%m-%d %H:%M"), session)?;
+    }
+    Ok(())
+}
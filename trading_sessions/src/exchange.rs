@@ -0,0 +1,160 @@
+use crate::trading_sessions::{day_of_week, seconds_of_day, SECONDS_PER_DAY, SECONDS_PER_HOUR};
+
+const DAY: u32 = SECONDS_PER_DAY;
+const HOUR: u32 = SECONDS_PER_HOUR;
+
+
+/// An exchange, bundling its trading hours and timezone, to identify trading sessions against.
+///
+/// The trading hours are expressed in UTC, following the standard (winter) time of the exchange's
+/// timezone:
+/// - FXCM: Sunday 10:00 PM - Friday 10:00 PM, continuously
+/// - CME: Sunday 11:00 PM - Friday 10:00 PM, with a daily maintenance break from 10:00 PM - 11:00 PM
+/// - LSE: Monday - Friday, 8:00 AM - 4:30 PM
+/// - TSE: Monday - Friday, 12:00 AM - 2:30 AM and 3:30 AM - 6:30 AM, with a lunch break in between
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::Exchange;
+///
+/// assert_eq!(Exchange::TSE.timezone(), "Asia/Tokyo");
+///
+/// // Thursday 22 February 2024, 22:30 UTC, during the CME Globex maintenance break
+/// assert!(!Exchange::CME.is_open(1708641000));
+/// assert!(Exchange::FXCM.is_open(1708641000));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Exchange {
+    FXCM,
+    CME,
+    LSE,
+    TSE,
+}
+
+
+/// The weekly trading hours of FXCM, in seconds since Sunday 00:00 UTC.
+const FXCM_HOURS: &[(u32, u32)] = &[(22 * HOUR, 5 * DAY + 22 * HOUR)];
+
+/// The weekly trading hours of CME Globex, in seconds since Sunday 00:00 UTC.
+const CME_HOURS: &[(u32, u32)] = &[
+    (23 * HOUR, DAY + 22 * HOUR),
+    (DAY + 23 * HOUR, 2 * DAY + 22 * HOUR),
+    (2 * DAY + 23 * HOUR, 3 * DAY + 22 * HOUR),
+    (3 * DAY + 23 * HOUR, 4 * DAY + 22 * HOUR),
+    (4 * DAY + 23 * HOUR, 5 * DAY + 22 * HOUR),
+];
+
+/// The weekly trading hours of the London Stock Exchange, in seconds since Sunday 00:00 UTC.
+const LSE_HOURS: &[(u32, u32)] = &[
+    (DAY + 8 * HOUR, DAY + 16 * HOUR + HOUR / 2),
+    (2 * DAY + 8 * HOUR, 2 * DAY + 16 * HOUR + HOUR / 2),
+    (3 * DAY + 8 * HOUR, 3 * DAY + 16 * HOUR + HOUR / 2),
+    (4 * DAY + 8 * HOUR, 4 * DAY + 16 * HOUR + HOUR / 2),
+    (5 * DAY + 8 * HOUR, 5 * DAY + 16 * HOUR + HOUR / 2),
+];
+
+/// The weekly trading hours of the Tokyo Stock Exchange, in seconds since Sunday 00:00 UTC.
+const TSE_HOURS: &[(u32, u32)] = &[
+    (DAY, DAY + 2 * HOUR + HOUR / 2),
+    (DAY + 3 * HOUR + HOUR / 2, DAY + 6 * HOUR + HOUR / 2),
+    (2 * DAY, 2 * DAY + 2 * HOUR + HOUR / 2),
+    (2 * DAY + 3 * HOUR + HOUR / 2, 2 * DAY + 6 * HOUR + HOUR / 2),
+    (3 * DAY, 3 * DAY + 2 * HOUR + HOUR / 2),
+    (3 * DAY + 3 * HOUR + HOUR / 2, 3 * DAY + 6 * HOUR + HOUR / 2),
+    (4 * DAY, 4 * DAY + 2 * HOUR + HOUR / 2),
+    (4 * DAY + 3 * HOUR + HOUR / 2, 4 * DAY + 6 * HOUR + HOUR / 2),
+    (5 * DAY, 5 * DAY + 2 * HOUR + HOUR / 2),
+    (5 * DAY + 3 * HOUR + HOUR / 2, 5 * DAY + 6 * HOUR + HOUR / 2),
+];
+
+/// The fixed-date holidays of FXCM, as `(month, day)` pairs.
+const FXCM_HOLIDAYS: &[(u32, u32)] = &[(1, 1), (12, 25)];
+
+/// The fixed-date holidays of CME Globex, as `(month, day)` pairs.
+const CME_HOLIDAYS: &[(u32, u32)] = &[(1, 1), (7, 4), (12, 25)];
+
+/// The fixed-date holidays of the London Stock Exchange, as `(month, day)` pairs.
+const LSE_HOLIDAYS: &[(u32, u32)] = &[(1, 1), (12, 25), (12, 26)];
+
+/// The fixed-date holidays of the Tokyo Stock Exchange, as `(month, day)` pairs.
+const TSE_HOLIDAYS: &[(u32, u32)] = &[(1, 1), (1, 2), (1, 3), (12, 31)];
+
+
+impl Exchange {
+    /// Returns the IANA timezone the exchange operates in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::Exchange;
+    ///
+    /// assert_eq!(Exchange::CME.timezone(), "America/Chicago");
+    /// ```
+    pub fn timezone(&self) -> &'static str {
+        match self {
+            Exchange::FXCM => "America/New_York",
+            Exchange::CME => "America/Chicago",
+            Exchange::LSE => "Europe/London",
+            Exchange::TSE => "Asia/Tokyo",
+        }
+    }
+
+    /// Returns whether the exchange is open for trading at the given Unix timestamp.
+    ///
+    /// # Arguments
+    ///
+    /// * `unix_timestamp` - A Unix timestamp in seconds.
+    ///
+    /// # Returns
+    ///
+    /// Returns true if the timestamp falls within the trading hours of the exchange; otherwise, returns false.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::Exchange;
+    ///
+    /// // Thursday 22 February 2024, 10:00 UTC
+    /// assert!(Exchange::LSE.is_open(1708596000));
+    /// assert!(!Exchange::TSE.is_open(1708596000));
+    /// ```
+    pub fn is_open(&self, unix_timestamp: i64) -> bool {
+        let seconds_of_week = day_of_week(unix_timestamp) * SECONDS_PER_DAY + seconds_of_day(unix_timestamp);
+        self.trading_hours()
+            .iter()
+            .any(|&(start, end)| (start..end).contains(&seconds_of_week))
+    }
+
+    /// Returns the holidays the exchange closes for on the same date every year, as `(month, day)` pairs.
+    ///
+    /// Holidays on varying dates, such as Easter, and substitute days for holidays falling on the
+    /// weekend are not included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::Exchange;
+    ///
+    /// assert!(Exchange::LSE.fixed_holidays().contains(&(12, 26)));
+    /// assert!(!Exchange::CME.fixed_holidays().contains(&(12, 26)));
+    /// ```
+    pub fn fixed_holidays(&self) -> &'static [(u32, u32)] {
+        match self {
+            Exchange::FXCM => FXCM_HOLIDAYS,
+            Exchange::CME => CME_HOLIDAYS,
+            Exchange::LSE => LSE_HOLIDAYS,
+            Exchange::TSE => TSE_HOLIDAYS,
+        }
+    }
+
+    /// Returns the weekly trading hours of the exchange, in seconds since Sunday 00:00 UTC.
+    pub(crate) fn trading_hours(&self) -> &'static [(u32, u32)] {
+        match self {
+            Exchange::FXCM => FXCM_HOURS,
+            Exchange::CME => CME_HOURS,
+            Exchange::LSE => LSE_HOURS,
+            Exchange::TSE => TSE_HOURS,
+        }
+    }
+}
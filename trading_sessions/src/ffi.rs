@@ -0,0 +1,162 @@
+//! A C-compatible interface to the session identification, for embedding in C++, C# or MQL hosts.
+//!
+//! The crate builds as a `cdylib` with e.g. `cargo rustc --release --no-default-features --features ffi --crate-type cdylib`.
+//! Session labels are returned as static, NUL-terminated strings, which the caller must not free.
+//! Schedules are passed around as opaque handles, created with [`sessions_schedule_forex`] or
+//! [`sessions_schedule_new`] and released with [`sessions_schedule_free`].
+//!
+//! None of the functions panic, which would abort the host across the `extern "C"` boundary: every
+//! `i64` timestamp is identified, down to `INT64_MIN` and up to `INT64_MAX`, and invalid arguments
+//! are reported through the return value instead.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::ffi::CStr;
+//! use trading_sessions::ffi::*;
+//!
+//! // Thursday 22 February 2024, 04:00 UTC
+//! let label = unsafe { CStr::from_ptr(sessions_identify(1708574400)) };
+//! assert_eq!(label.to_str(), Ok("Tokyo"));
+//!
+//! // The extremes of i64 both fall on a Sunday
+//! let label = unsafe { CStr::from_ptr(sessions_identify(i64::MAX)) };
+//! assert_eq!(label.to_str(), Ok("Weekend"));
+//!
+//! unsafe {
+//!     let schedule = sessions_schedule_new();
+//!     assert!(sessions_schedule_add(schedule, c"Tokyo".as_ptr(), 0, 27_000));
+//!     assert!(!sessions_schedule_add(schedule, c"Tokio".as_ptr(), 0, 27_000));
+//!
+//!     // Thursday 22 February 2024, 10:00 UTC
+//!     let label = CStr::from_ptr(sessions_schedule_identify(schedule, 1708596000));
+//!     assert_eq!(label.to_str(), Ok("Undefined"));
+//!     let label = CStr::from_ptr(sessions_schedule_identify(schedule, i64::MIN));
+//!     assert_eq!(label.to_str(), Ok("Weekend"));
+//!     sessions_schedule_free(schedule);
+//! }
+//! ```
+use alloc::boxed::Box;
+use core::ffi::{c_char, CStr};
+
+use crate::{IdentifyTradingSession, SessionSchedule, TradingSession};
+
+
+/// Identifies the trading session of a Unix timestamp in seconds, with the default Forex schedule.
+///
+/// # Returns
+///
+/// The static, NUL-terminated label of the trading session, e.g. "Tokyo_London".
+#[no_mangle]
+pub extern "C" fn sessions_identify(unix_timestamp: i64) -> *const c_char {
+    c_label(IdentifyTradingSession::new(unix_timestamp).trading_session())
+}
+
+
+/// Creates a schedule handle with the default Forex session windows.
+///
+/// # Returns
+///
+/// A handle to be released with [`sessions_schedule_free`].
+#[no_mangle]
+pub extern "C" fn sessions_schedule_forex() -> *mut SessionSchedule {
+    Box::into_raw(Box::new(SessionSchedule::forex()))
+}
+
+
+/// Creates a schedule handle without any session windows.
+///
+/// # Returns
+///
+/// A handle to be released with [`sessions_schedule_free`].
+#[no_mangle]
+pub extern "C" fn sessions_schedule_new() -> *mut SessionSchedule {
+    Box::into_raw(Box::new(SessionSchedule::new()))
+}
+
+
+/// Adds a session window to a schedule handle.
+///
+/// # Arguments
+///
+/// * `schedule` - The schedule handle.
+/// * `label` - The NUL-terminated label of the trading session, e.g. "London".
+/// * `start` - The start of the window in seconds since midnight UTC, inclusive.
+/// * `end` - The end of the window in seconds since midnight UTC, exclusive.
+///
+/// # Returns
+///
+/// `true` if the window was added, or `false` if either pointer is null or the label matches no trading session.
+///
+/// # Safety
+///
+/// `schedule` must be null or a live handle, and `label` must be null or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn sessions_schedule_add(schedule: *mut SessionSchedule, label: *const c_char, start: u32, end: u32) -> bool {
+    if schedule.is_null() || label.is_null() {
+        return false;
+    }
+
+    match CStr::from_ptr(label).to_str().ok().and_then(|label| label.parse::<TradingSession>().ok()) {
+        Some(session) => {
+            let schedule = &mut *schedule;
+            *schedule = core::mem::take(schedule).add(session, start, end);
+            true
+        }
+        None => false,
+    }
+}
+
+
+/// Identifies the trading session of a Unix timestamp in seconds against a schedule handle.
+///
+/// # Returns
+///
+/// The static, NUL-terminated label of the trading session, or "Undefined" if `schedule` is null.
+///
+/// # Safety
+///
+/// `schedule` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn sessions_schedule_identify(schedule: *const SessionSchedule, unix_timestamp: i64) -> *const c_char {
+    match schedule.as_ref() {
+        Some(schedule) => c_label(IdentifyTradingSession::new(unix_timestamp).with_schedule(schedule.clone()).trading_session()),
+        None => c_label(TradingSession::Undefined),
+    }
+}
+
+
+/// Releases a schedule handle. Passing null is a no-op.
+///
+/// # Safety
+///
+/// `schedule` must be null or a live handle, which must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn sessions_schedule_free(schedule: *mut SessionSchedule) {
+    if !schedule.is_null() {
+        drop(Box::from_raw(schedule));
+    }
+}
+
+
+/// Returns the static, NUL-terminated label of a trading session, matching `TradingSession::as_str`.
+fn c_label(session: TradingSession) -> *const c_char {
+    let label: &'static CStr = match session {
+        TradingSession::Tokyo => c"Tokyo",
+        TradingSession::TokyoLondon => c"Tokyo_London",
+        TradingSession::London => c"London",
+        TradingSession::LondonNewYork => c"London_NewYork",
+        TradingSession::NewYork => c"NewYork",
+        TradingSession::Sydney => c"Sydney",
+        TradingSession::SydneyTokyo => c"Sydney_Tokyo",
+        TradingSession::PreMarket => c"PreMarket",
+        TradingSession::Regular => c"Regular",
+        TradingSession::AfterHours => c"AfterHours",
+        TradingSession::Globex => c"Globex",
+        TradingSession::Weekend => c"Weekend",
+        TradingSession::Closed => c"Closed",
+        TradingSession::Holiday => c"Holiday",
+        TradingSession::Undefined => c"Undefined",
+    };
+    label.as_ptr()
+}
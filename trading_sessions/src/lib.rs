@@ -14,8 +14,43 @@
 //! ## Features
 //!
 //! - [`IdentifyTradingSession`](./struct.IdentifyTradingSession.html): Determine the trading session from a Unix timestamp.
+//! - [`IntoUnixSeconds`](./trait.IntoUnixSeconds.html): Pass `u32`, `u64`, `i64` or float epoch seconds to [`IdentifyTradingSession::from_unix_seconds`](./struct.IdentifyTradingSession.html#method.from_unix_seconds) without casting.
 //! - [`SessionVerification`](./struct.SessionVerification.html): Verify if a given session string matches the identified trading session.
-//! - [`SessionColumn`](./struct.SessionColumn.html): Add a "Session" column to a `LazyFrame` based on Unix timestamps.
+//! - [`SessionAliases`](./struct.SessionAliases.html): Resolve alternative session names, e.g. "Asia" or "NY", case-insensitively, parse labels against them, or rename the session column to them.
+//! - [`SessionColumn`](./struct.SessionColumn.html): Add a "Session" column to a `LazyFrame` based on Unix timestamps, Datetime columns or ISO-8601 strings.
+//! - [`SessionStats`](./struct.SessionStats.html): Summarize the rows, first and last timestamps and coverage of each session in a `LazyFrame`, or report the missing bars and gaps of each session occurrence.
+//! - [`SessionColumnBuilder`](./struct.SessionColumnBuilder.html): Configure the session column once and apply it to any number of `LazyFrame`s.
+//! - [`SessionExprExt`](./trait.SessionExprExt.html): Classify timestamps within your own lazy queries, e.g. `col("time").session().label()`.
+//! - [`SessionResampler`](./struct.SessionResampler.html): Resample tick or minute data into one OHLCV bar per session occurrence, with the session bounds.
+//! - [`SessionSplit`](./struct.SessionSplit.html): Split a labeled `LazyFrame` into train and test sets stratified by session, optionally keeping session occurrences whole.
+//! - [`TradingSession`](./enum.TradingSession.html): The typed set of trading sessions, with stable numeric IDs for the UInt8 session column of [`SessionColumn::with_session_ids`](./struct.SessionColumn.html#method.with_session_ids).
+//! - [`Market`](./enum.Market.html): Select the trading hours of Forex (default) or around-the-clock Crypto markets.
+//! - [`Killzone`](./enum.Killzone.html): Refine the trading sessions into ICT-style killzones, e.g. the London open.
+//! - [`SessionSet`](./enum.SessionSet.html): Choose between the three-center model (default) and the four-center model including Sydney.
+//! - [`SessionSchedule`](./struct.SessionSchedule.html): Define custom session windows at minute granularity, e.g. with [`SessionBoundary`](./struct.SessionBoundary.html), select a named preset with [`SessionSchedule::preset`](./struct.SessionSchedule.html#method.preset), or export them as a SQL `CASE` expression with [`SessionSchedule::to_sql_case`](./struct.SessionSchedule.html#method.to_sql_case).
+//! - [`Exchange`](./enum.Exchange.html): Identify sessions against the trading hours of a specific exchange.
+//! - [`HolidayCalendar`](./struct.HolidayCalendar.html): Mark market holidays as closed, even on weekdays, e.g. the fixed-date holidays of an exchange.
+//! - [`EarlyClose`](./struct.EarlyClose.html) and [`LateOpen`](./struct.LateOpen.html): Override the session boundaries on specific dates, e.g. half days.
+//! - [`SessionRangeIter`](./struct.SessionRangeIter.html): Iterate over the session boundaries within a range of Unix timestamps.
+//! - [`SessionIntervals`](./struct.SessionIntervals.html): Iterate over the `(session, start, end)` intervals within a range of Unix timestamps, or export them as an iCalendar (`.ics`) feed.
+//! - [`SessionClock`](./struct.SessionClock.html): Call back when sessions open and close, ticked from your own loop or run on a background thread.
+//! - [`TimestampUnit`](./enum.TimestampUnit.html): Classify timestamps in milliseconds, microseconds or nanoseconds as well as seconds.
+//! - [`TradingSessionsError`](./enum.TradingSessionsError.html): The error type of the fallible `SessionColumn` entry points and validations.
+//!
+//! ## Optional Features
+//!
+//! - `polars` (default): The Polars integration, i.e. [`SessionColumn`](./struct.SessionColumn.html) and the functions on `Series` and `LazyFrame`. Implies `std`.
+//! - `std` (default): [`IdentifyTradingSession::from_system_time`](./struct.IdentifyTradingSession.html#method.from_system_time) and the live helpers [`current_session`](./fn.current_session.html), [`is_session_open_now`](./fn.is_session_open_now.html) and [`next_open_in`](./fn.next_open_in.html), with an injectable [`Clock`](./trait.Clock.html). Implies `alloc`.
+//! - `alloc`: Custom [`SessionSchedule`](./struct.SessionSchedule.html)s, [`HolidayCalendar`](./struct.HolidayCalendar.html)s and the functions returning vectors.
+//!   Without `std`, the crate is `#![no_std]`, and without `alloc` as well, the core classification still works without an allocator.
+//!   Without Polars, the crate also builds for `wasm32-unknown-unknown`, e.g. with `default-features = false, features = ["alloc"]`.
+//! - `serde`: Serialize and deserialize [`TradingSession`](./enum.TradingSession.html), [`SessionResult`](./struct.SessionResult.html), [`SessionSchedule`](./struct.SessionSchedule.html) and [`HolidayCalendar`](./struct.HolidayCalendar.html).
+//! - `chrono`: Convert an [`IdentifyTradingSession`](./struct.IdentifyTradingSession.html) from and to a `chrono::DateTime<Utc>` or `NaiveDateTime`, build a [`SessionVerification`](./struct.SessionVerification.html) from a `DateTime<Utc>`, and express session bounds in any `chrono::TimeZone` with [`session_bounds_in`](./fn.session_bounds_in.html).
+//! - `json`: [`SessionSchedule::to_json`](./struct.SessionSchedule.html#method.to_json) and `from_json`, likewise on [`HolidayCalendar`](./struct.HolidayCalendar.html), with a versioned schema. Implies `std` and `serde`.
+//! - `rayon`: Classify large slices with [`identify_many`](./fn.identify_many.html) and [`classify_into`](./fn.classify_into.html) in chunks across all cores. Implies `std`.
+//! - `streaming`: [`SessionColumn::collect_streaming`](./struct.SessionColumn.html#method.collect_streaming), collecting the session columns with the Polars streaming engine, e.g. for scans larger than memory. Implies `polars`.
+//! - `ffi`: A C-compatible interface in the [`ffi`](./ffi/index.html) module, e.g. `sessions_identify`, to embed the crate as a `cdylib`. Implies `std`.
+//! - `cli`: The `sessions` binary, answering `sessions now`, `sessions at <timestamp>`, `sessions annotate <file.csv> --time-col <column> --out <file.csv>` and `sessions calendar <year>-<month>`. Implies `polars` and `chrono`.
 //!
 //! ## Examples
 //!
@@ -31,9 +66,9 @@
 //! ### SessionVerification
 //!
 //! ```
-//! use trading_sessions::SessionVerification;
+//! use trading_sessions::{SessionVerification, TradingSession};
 //!
-//! let verifier = SessionVerification::new(1708574400, "Tokyo".to_string());
+//! let verifier = SessionVerification::new(1708574400, TradingSession::Tokyo);
 //! assert!(verifier.verify());
 //! ```
 //!
@@ -47,32 +82,55 @@
 //!     "time" => [1708574400, 1708596000, 1708696800]
 //! }.unwrap();
 //! let lazy_frame = df.lazy();
-//! let mut session_column = SessionColumn::new(lazy_frame);
-//! session_column.apply_session_column();
-//! let result_df = session_column.lazyframe.collect().unwrap();
+//! let session_column = SessionColumn::new(lazy_frame).apply_session_column();
+//! let result_df = session_column.into_lazyframe().collect().unwrap();
 //! assert_eq!(result_df.column("Session").unwrap().str_value(0).unwrap(), "Tokyo");
 //! ```
 //!
 //! ## Return Types
 //!
-//! Successful operations return a string representing the trading session or a boolean indicating the verification result.
-//! Errors are typically handled by the calling code and are dependent on the context in which these functions are used.
+//! Successful operations return a [`TradingSession`](./enum.TradingSession.html), which compares equal to its label, or a boolean indicating the verification result.
+//! Fallible operations return a [`TradingSessionsError`](./enum.TradingSessionsError.html), or a dedicated error type where nothing else can go wrong, e.g. [`ParseSessionError`](./struct.ParseSessionError.html).
 //!
 //! ## Notes
 //!
-//! - The crate assumes all timestamps are in UTC.
-//! - Daylight Saving Time is not considered in the current version.
-//!
-//! use polars::prelude::*;
-//!
-//! mod trading_sessions;
-//!
+//! - Timestamps are Unix timestamps and session hours are in UTC, unless a UTC offset is given with [`IdentifyTradingSession::with_utc_offset`](./struct.IdentifyTradingSession.html#method.with_utc_offset), in which case the sessions and dates are those of the local time.
+//! - The Forex market closes for the weekend from Friday 22:00 to Sunday 22:00 (UTC), which is identified as the Weekend session.
+//! - Daylight Saving Time is not considered by default, see [`IdentifyTradingSession::with_dst`](./struct.IdentifyTradingSession.html#method.with_dst) and [`SessionColumn::with_dst`](./struct.SessionColumn.html#method.with_dst) to opt in.
+
+#![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use alloc::{collections::BTreeSet, string::String, vec::Vec};
+use core::ops::RangeInclusive;
+#[cfg(feature = "polars")]
 use polars::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+mod exchange;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod trading_sessions;
+
+pub use exchange::Exchange;
+pub use trading_sessions::{classify_into, dominant_session, identify_killzone, is_market_open, open_duty_cycle, session_coverage, session_duration_secs, session_for_hour, session_progress, sessions_between, trading_day};
+#[cfg(feature = "alloc")]
+pub use trading_sessions::{active_sessions, classify, compare_boundaries, identify_many, sessions_in_range, sessions_touched, to_sql_case};
+#[cfg(feature = "std")]
+pub use trading_sessions::{current_session, current_session_with, is_session_open_now, is_session_open_with, next_open_in, next_open_in_with};
+#[cfg(feature = "chrono")]
+pub use trading_sessions::session_bounds_in;
+#[cfg(feature = "polars")]
+pub use trading_sessions::{identify_sessions_chunked, identify_sessions_series, session_metadata_frame, session_expr, session_series_named, with_session};
 
 /// Identifies the trading session based on the stored Unix timestamp in seconds.
 ///
-/// Calculations are based on UK and USA normal time, i.e., NOT daylight saving time.
+/// Calculations are based on UK and USA normal time, i.e., NOT daylight saving time, unless enabled
+/// with [`IdentifyTradingSession::with_dst`].
 ///
 /// The trading session is determined by the hour of the day in UTC:
 /// - Tokyo: 12:00 AM - 7:00 AM
@@ -82,6 +140,8 @@ use polars::prelude::*;
 /// - NewYork: 4:00 PM - 10:00 PM
 /// - Undefined: Any other time
 ///
+/// Timestamps falling between Friday 22:00 and Sunday 22:00 (UTC), while the Forex market is closed, are identified as Weekend.
+///
 /// # Examples
 ///
 /// ```
@@ -95,41 +155,833 @@ use polars::prelude::*;
 ///
 /// let session_identifier = IdentifyTradingSession::new(1708696800); // This timestamp corresponds to a time within the London_NewYork session
 /// assert_eq!(session_identifier.identify_trading_session(), "London_NewYork");
+///
+/// // Identifiers can be cloned and debug-printed
+/// let cloned = session_identifier.clone();
+/// assert_eq!(cloned, session_identifier);
+/// assert!(format!("{:?}", cloned).contains("1708696800"));
 /// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct IdentifyTradingSession {
-    pub unix_timestamp: u32,
+    pub unix_timestamp: i64,
+    pub utc_offset: i32,
+    pub market: Market,
+    #[cfg(feature = "alloc")]
+    pub schedule: Option<SessionSchedule>,
+    pub exchange: Option<Exchange>,
+    #[cfg(feature = "alloc")]
+    pub holidays: Option<HolidayCalendar>,
+    pub dst: bool,
+}
+
+
+/// Epoch timestamps in seconds of any common numeric type, converted to the `i64` seconds the crate works with.
+///
+/// Implemented for `i32`, `u32`, `i64`, `u64` and `f64`. Timestamps beyond the range of `i64`
+/// saturate and NaN maps to 0, while fractional seconds are floored like in
+/// [`IdentifyTradingSession::from_f64`], so a float timestamp is identified like the whole second it falls into. Used by [`IdentifyTradingSession::from_unix_seconds`].
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{IdentifyTradingSession, IntoUnixSeconds, TradingSession};
+///
+/// assert_eq!(1708596000u32.into_unix_seconds(), 1708596000);
+/// assert_eq!(1708596000.75f64.into_unix_seconds(), 1708596000);
+/// assert_eq!((-0.5f64).into_unix_seconds(), -1);
+/// assert_eq!(u64::MAX.into_unix_seconds(), i64::MAX);
+///
+/// // A saturated timestamp is still identified, here on a Sunday at the end of time
+/// assert_eq!(IdentifyTradingSession::from_unix_seconds(u64::MAX).trading_session(), TradingSession::Weekend);
+/// assert_eq!(IdentifyTradingSession::from_unix_seconds(f64::NEG_INFINITY).trading_session(), TradingSession::Weekend);
+/// ```
+pub trait IntoUnixSeconds {
+    /// Converts the timestamp to whole Unix seconds.
+    fn into_unix_seconds(self) -> i64;
+}
+
+
+/// A source of the current time for the live session helpers, e.g. [`current_session_with`].
+///
+/// Implemented for [`SystemClock`] and for a fixed `SystemTime`, which stands in for the system
+/// clock in tests.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, SystemTime, UNIX_EPOCH};
+/// use trading_sessions::{current_session_with, Clock, TradingSession};
+///
+/// // A clock that is always 30 minutes behind
+/// struct LaggingClock;
+///
+/// impl Clock for LaggingClock {
+///     fn now(&self) -> SystemTime {
+///         SystemTime::now() - Duration::from_secs(30 * 60)
+///     }
+/// }
+///
+/// assert!(TradingSession::ALL.contains(&current_session_with(&LaggingClock)));
+///
+/// // Thursday 22 February 2024, 10:00 UTC
+/// let frozen = UNIX_EPOCH + Duration::from_secs(1708596000);
+/// assert_eq!(current_session_with(&frozen), TradingSession::London);
+/// ```
+#[cfg(feature = "std")]
+pub trait Clock {
+    /// Returns the current time.
+    fn now(&self) -> std::time::SystemTime;
+}
+
+
+/// The system clock, i.e. `SystemTime::now()`, used by [`current_session`], [`is_session_open_now`] and [`next_open_in`].
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct SystemClock;
+
+
+/// A synchronous session clock, calling back when trading sessions open and close.
+///
+/// The clock is driven by calling [`SessionClock::tick`] periodically, e.g. from an existing event
+/// loop, or by [`SessionClock::run`] on a background thread. A session counts as open while it or
+/// an overlap containing it is active, so the London callbacks fire at the start of Tokyo_London
+/// and the end of London_NewYork. The first tick only records the active sessions, so sessions
+/// already open at startup don't fire. Sessions opening and closing between two ticks fire on the
+/// later tick, with the timestamp of that tick.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::{Arc, Mutex};
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use trading_sessions::{SessionClock, TradingSession};
+///
+/// let events = Arc::new(Mutex::new(Vec::new()));
+/// let (opened, closed) = (events.clone(), events.clone());
+/// let mut clock = SessionClock::new()
+///     .on_session_open(TradingSession::London, move |session, _| opened.lock().unwrap().push(format!("{session} opened")))
+///     .on_session_close(TradingSession::Tokyo, move |session, _| closed.lock().unwrap().push(format!("{session} closed")));
+///
+/// // Thursday 22 February 2024, 06:59, 07:00 and 09:00 UTC, Tokyo_London runs from 07:00 to 09:00 UTC
+/// for unix_timestamp in [1708585140, 1708585200, 1708592400] {
+///     clock.tick_at(UNIX_EPOCH + Duration::from_secs(unix_timestamp));
+/// }
+/// assert_eq!(*events.lock().unwrap(), ["London opened", "Tokyo closed"]);
+/// ```
+#[cfg(feature = "std")]
+pub struct SessionClock {
+    clock: Box<dyn Clock + Send>,
+    identifier: IdentifyTradingSession,
+    active: Option<Vec<TradingSession>>,
+    on_open: Vec<(TradingSession, SessionCallback)>,
+    on_close: Vec<(TradingSession, SessionCallback)>,
+}
+
+
+/// A callback of a [`SessionClock`], receiving the session and the Unix timestamp of the tick.
+#[cfg(feature = "std")]
+type SessionCallback = Box<dyn FnMut(TradingSession, i64) + Send>;
+
+
+/// The market whose trading hours are used to identify the trading session.
+///
+/// - Forex: The sessions as listed on [`IdentifyTradingSession`], closed on the weekend.
+/// - Crypto: Trades around the clock, so there is no weekend, and the hours after the New York
+///   session (10:00 PM - 12:00 AM) are folded into the Tokyo session. No timestamp is Undefined.
+///   See [`SessionSchedule::crypto`] for non-overlapping Asia, Europe and US liquidity buckets.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{IdentifyTradingSession, Market, TradingSession};
+///
+/// // Saturday 24 February 2024, 02:00 UTC
+/// let session_identifier = IdentifyTradingSession::new(1708740000).with_market(Market::Crypto);
+/// assert_eq!(session_identifier.trading_session(), TradingSession::Tokyo);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Market {
+    #[default]
+    Forex,
+    Crypto,
+}
+
+
+/// The unit of integer Unix timestamps, for market data stored at a finer resolution than seconds.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{IdentifyTradingSession, TimestampUnit, TradingSession};
+///
+/// // Thursday 22 February 2024, 10:00 UTC in epoch milliseconds
+/// let session_identifier = IdentifyTradingSession::from_timestamp_unit(1708596000000, TimestampUnit::Milliseconds);
+/// assert_eq!(session_identifier.trading_session(), TradingSession::London);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TimestampUnit {
+    #[default]
+    Seconds,
+    Milliseconds,
+    Microseconds,
+    Nanoseconds,
+}
+
+
+/// The trading sessions that can be identified from a Unix timestamp.
+///
+/// The string form of each variant, as returned by [`TradingSession::as_str`], matches the
+/// labels used in the "Session" column:
+/// - Tokyo: "Tokyo"
+/// - TokyoLondon: "Tokyo_London"
+/// - London: "London"
+/// - LondonNewYork: "London_NewYork"
+/// - NewYork: "NewYork"
+/// - Sydney: "Sydney"
+/// - SydneyTokyo: "Sydney_Tokyo"
+/// - PreMarket: "PreMarket"
+/// - Regular: "Regular"
+/// - AfterHours: "AfterHours"
+/// - Globex: "Globex"
+/// - Weekend: "Weekend"
+/// - Closed: "Closed"
+/// - Holiday: "Holiday"
+/// - Undefined: "Undefined"
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::TradingSession;
+///
+/// assert_eq!(TradingSession::LondonNewYork.as_str(), "London_NewYork");
+/// ```
+///
+/// Sessions are ordered chronologically by their start hour, followed by the Sydney sessions of
+/// the four-center model, the NYSE sessions of [`SessionSchedule::nyse`] and the Globex session of
+/// [`SessionSchedule::cme_globex`], with the sessions outside of the daily schedule (Weekend, Closed,
+/// Holiday and Undefined) sorting last:
+///
+/// ```
+/// use std::collections::BTreeMap;
+/// use trading_sessions::TradingSession;
+///
+/// let mut sessions = vec![TradingSession::Undefined, TradingSession::NewYork, TradingSession::Tokyo, TradingSession::London];
+/// sessions.sort();
+/// assert_eq!(sessions, [TradingSession::Tokyo, TradingSession::London, TradingSession::NewYork, TradingSession::Undefined]);
+/// assert!(TradingSession::NewYork < TradingSession::Weekend);
+///
+/// let counts = BTreeMap::from([(TradingSession::London, 2), (TradingSession::TokyoLondon, 1)]);
+/// assert_eq!(counts.keys().next(), Some(&TradingSession::TokyoLondon));
+/// ```
+///
+/// With the `serde` feature enabled, sessions serialize to and from the same labels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TradingSession {
+    // Declared in order of the start hour, which the derived ordering relies on
+    Tokyo,
+    #[cfg_attr(feature = "serde", serde(rename = "Tokyo_London"))]
+    TokyoLondon,
+    London,
+    #[cfg_attr(feature = "serde", serde(rename = "London_NewYork"))]
+    LondonNewYork,
+    NewYork,
+    // Only part of the ForexFour session set, see `SessionSet`
+    Sydney,
+    #[cfg_attr(feature = "serde", serde(rename = "Sydney_Tokyo"))]
+    SydneyTokyo,
+    // Only part of the NYSE schedule, see `SessionSchedule::nyse`
+    PreMarket,
+    Regular,
+    AfterHours,
+    // Only part of the CME Globex schedule, see `SessionSchedule::cme_globex`
+    Globex,
+    Weekend,
+    Closed,
+    Holiday,
+    Undefined,
+}
+
+
+/// The hours of the day in UTC covered by each trading session, inclusive.
+///
+/// Together the ranges cover every hour of the day exactly once. The Weekend is not listed, since it
+/// is determined by the day of the week rather than the hour.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{TradingSession, SESSION_BOUNDARIES};
+///
+/// let (session, hours) = &SESSION_BOUNDARIES[2];
+/// assert_eq!(*session, TradingSession::London);
+/// assert_eq!(*hours, 9..=12);
+/// ```
+pub const SESSION_BOUNDARIES: &[(TradingSession, RangeInclusive<u8>)] = &[
+    (TradingSession::Tokyo, 0..=6),             // 12:00 AM - 7:00 AM
+    (TradingSession::TokyoLondon, 7..=8),       // 7:00 AM - 9:00 AM
+    (TradingSession::London, 9..=12),           // 9:00 AM - 1:00 PM
+    (TradingSession::LondonNewYork, 13..=15),   // 1:00 PM - 4:00 PM
+    (TradingSession::NewYork, 16..=21),         // 4:00 PM - 10:00 PM
+    (TradingSession::Undefined, 22..=23),       // 10:00 PM - 12:00 AM
+];
+
+
+/// The hours of the day in UTC covered by each trading session of the four-center Forex model, inclusive.
+///
+/// Sydney opens as New York closes, which fills the hours left undefined by [`SESSION_BOUNDARIES`]
+/// and represents the Sydney/Tokyo overlap in the morning.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{TradingSession, FOREX_FOUR_BOUNDARIES};
+///
+/// let (session, hours) = FOREX_FOUR_BOUNDARIES.last().unwrap();
+/// assert_eq!(*session, TradingSession::Sydney);
+/// assert_eq!(*hours, 22..=23);
+/// ```
+pub const FOREX_FOUR_BOUNDARIES: &[(TradingSession, RangeInclusive<u8>)] = &[
+    (TradingSession::SydneyTokyo, 0..=5),       // 12:00 AM - 6:00 AM
+    (TradingSession::Tokyo, 6..=6),             // 6:00 AM - 7:00 AM
+    (TradingSession::TokyoLondon, 7..=8),       // 7:00 AM - 9:00 AM
+    (TradingSession::London, 9..=12),           // 9:00 AM - 1:00 PM
+    (TradingSession::LondonNewYork, 13..=15),   // 1:00 PM - 4:00 PM
+    (TradingSession::NewYork, 16..=21),         // 4:00 PM - 10:00 PM
+    (TradingSession::Sydney, 22..=23),          // 10:00 PM - 12:00 AM
+];
+
+
+/// An ICT-style killzone, a window of heightened volatility around the open or close of a trading center.
+///
+/// Killzones refine the trading sessions and may span two of them, e.g. the New York open killzone
+/// starts during the London/New York overlap. The hours in UTC are listed in [`KILLZONE_BOUNDARIES`]:
+/// - Asian: "Asian" (1:00 AM - 5:00 AM)
+/// - LondonOpen: "London_Open" (7:00 AM - 10:00 AM)
+/// - NewYorkOpen: "NewYork_Open" (12:00 PM - 3:00 PM)
+/// - LondonClose: "London_Close" (3:00 PM - 5:00 PM)
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{identify_killzone, IdentifyTradingSession, Killzone, TradingSession};
+///
+/// // Thursday 22 February 2024, 08:00 UTC
+/// let session_identifier = IdentifyTradingSession::new(1708588800);
+/// assert_eq!(session_identifier.trading_session(), TradingSession::TokyoLondon);
+/// assert_eq!(session_identifier.killzone(), Some(Killzone::LondonOpen));
+///
+/// // Thursday 22 February 2024, 11:00 UTC
+/// assert_eq!(identify_killzone(1708599600), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Killzone {
+    // Declared in order of the start hour, like `TradingSession`
+    Asian,
+    #[cfg_attr(feature = "serde", serde(rename = "London_Open"))]
+    LondonOpen,
+    #[cfg_attr(feature = "serde", serde(rename = "NewYork_Open"))]
+    NewYorkOpen,
+    #[cfg_attr(feature = "serde", serde(rename = "London_Close"))]
+    LondonClose,
+}
+
+
+/// The hours of the day in UTC covered by each killzone, inclusive.
+///
+/// Unlike [`SESSION_BOUNDARIES`], the ranges leave gaps, during which no killzone is active.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{Killzone, KILLZONE_BOUNDARIES};
+///
+/// let (killzone, hours) = &KILLZONE_BOUNDARIES[2];
+/// assert_eq!(*killzone, Killzone::NewYorkOpen);
+/// assert_eq!(*hours, 12..=14);
+/// ```
+pub const KILLZONE_BOUNDARIES: &[(Killzone, RangeInclusive<u8>)] = &[
+    (Killzone::Asian, 1..=4),                   // 1:00 AM - 5:00 AM
+    (Killzone::LondonOpen, 7..=9),              // 7:00 AM - 10:00 AM
+    (Killzone::NewYorkOpen, 12..=14),           // 12:00 PM - 3:00 PM
+    (Killzone::LondonClose, 15..=16),           // 3:00 PM - 5:00 PM
+];
+
+
+/// The version of the JSON documents written by [`SessionSchedule::to_json`] and [`HolidayCalendar::to_json`].
+///
+/// Every document carries it in its "version" field, and documents of any other version are rejected when read.
+#[cfg(feature = "json")]
+pub const JSON_SCHEMA_VERSION: u32 = 1;
+
+
+/// The set of trading centers the day is divided into.
+///
+/// - ThreeCenter (default): Tokyo, London and New York, following [`SESSION_BOUNDARIES`].
+/// - ForexFour: Sydney, Tokyo, London and New York, following [`FOREX_FOUR_BOUNDARIES`].
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{IdentifyTradingSession, SessionSet, TradingSession};
+///
+/// // Thursday 22 February 2024, 23:00 UTC
+/// let session_identifier = IdentifyTradingSession::new(1708642800);
+/// assert_eq!(session_identifier.trading_session(), TradingSession::Undefined);
+///
+/// let session_identifier = session_identifier.with_session_set(SessionSet::ForexFour);
+/// assert_eq!(session_identifier.trading_session(), TradingSession::Sydney);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SessionSet {
+    #[default]
+    ThreeCenter,
+    ForexFour,
+}
+
+
+/// The trading session identified for a Unix timestamp.
+///
+/// With the `serde` feature enabled, results can be serialized, e.g. as part of an API response.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{SessionResult, TradingSession};
+///
+/// let result = SessionResult::new(1708574400);
+/// assert_eq!(result.session, TradingSession::Tokyo);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SessionResult {
+    pub timestamp: i64,
+    pub session: TradingSession,
+}
+
+
+/// The market context of a trading session, returned by [`TradingSession::metadata`].
+///
+/// The session covers the hours of the day in UTC from `open_hour_utc` up to, but excluding, `close_hour_utc`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SessionMetadata {
+    pub name: &'static str,
+    pub primary_exchange: &'static str,
+    pub typical_pairs: &'static [&'static str],
+    pub open_hour_utc: u8,
+    pub close_hour_utc: u8,
+    pub market_center: &'static str,
+    pub timezone: Option<&'static str>,
+}
+
+
+/// The trading session of a timestamp along with the sessions right before and after it on the daily schedule.
+///
+/// Returned by [`IdentifyTradingSession::context`], e.g. to show the context of a row in a table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SessionContext {
+    pub prev: TradingSession,
+    pub current: TradingSession,
+    pub next: TradingSession,
+}
+
+
+/// A schedule of session windows, used to identify trading sessions at minute (or even second) granularity.
+///
+/// Each window covers a half-open range of seconds since midnight UTC, so a session can start at e.g.
+/// 07:30. The default schedule, [`SessionSchedule::forex`], follows [`SESSION_BOUNDARIES`].
+/// The Forex weekend from Friday 22:00 to Sunday 22:00 UTC applies, unless the schedule has a weekend
+/// of its own, see [`SessionSchedule::with_weekend`].
+///
+/// With the `serde` feature enabled, schedules can be persisted, e.g. as JSON configuration.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{SessionSchedule, TradingSession};
+///
+/// let schedule = SessionSchedule::new()
+///     .add(TradingSession::Tokyo, 0, 27_000)
+///     .add(TradingSession::TokyoLondon, 27_000, 32_400);
+///
+/// assert_eq!(schedule.session_for_seconds(26_100), TradingSession::Tokyo);       // 07:15
+/// assert_eq!(schedule.session_for_seconds(27_900), TradingSession::TokyoLondon); // 07:45
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SessionSchedule {
+    pub windows: Vec<SessionWindow>,
+    /// The weekend as a half-open range of seconds since Sunday 00:00 UTC, or `None` for the Forex weekend.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub weekend: Option<(u32, u32)>,
+}
+
+
+/// A window of a [`SessionSchedule`] during which the given trading session is active.
+///
+/// The window covers the seconds since midnight UTC in `[start, end)`. A window whose start lies
+/// after its end wraps midnight, e.g. a window from 23:00 to 03:00 covers `[start, 86400)` and `[0, end)`.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{IdentifyTradingSession, SessionSchedule, TradingSession};
+///
+/// // An Asian futures session from 23:00 to 03:00 UTC
+/// let schedule = SessionSchedule::new().add(TradingSession::Tokyo, 23 * 3600, 3 * 3600);
+///
+/// // Wednesday 21 February 2024, 23:30 UTC and Thursday 22 February 2024, 01:00 UTC
+/// let late = IdentifyTradingSession::new(1708558200).with_schedule(schedule.clone());
+/// let early = IdentifyTradingSession::new(1708563600).with_schedule(schedule.clone());
+/// assert_eq!(late.trading_session(), TradingSession::Tokyo);
+/// assert_eq!(early.trading_session(), TradingSession::Tokyo);
+///
+/// // Thursday 22 February 2024, 04:00 UTC
+/// let outside = IdentifyTradingSession::new(1708574400).with_schedule(schedule);
+/// assert_eq!(outside.trading_session(), TradingSession::Undefined);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SessionWindow {
+    pub session: TradingSession,
+    pub start: u32,
+    pub end: u32,
+}
+
+
+/// The SQL dialect of [`SessionSchedule::to_sql_case`], which decides how the Unix timestamp is read from a timestamp column.
+///
+/// - ClickHouse: `toUnixTimestamp(time_col)`, e.g. of a `DateTime` column.
+/// - Postgres: `EXTRACT(EPOCH FROM time_col)`, e.g. of a `timestamptz` column.
+/// - DuckDb: `epoch(time_col)`, e.g. of a `TIMESTAMP` column.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SqlDialect {
+    ClickHouse,
+    Postgres,
+    DuckDb,
+}
+
+
+/// A problem found in a [`SessionSchedule`] by [`SessionSchedule::diagnose`].
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{ScheduleIssue, SessionSchedule, TradingSession};
+///
+/// let schedule = SessionSchedule::new()
+///     .add(TradingSession::Tokyo, 0, 8 * 3600)
+///     .add(TradingSession::London, 7 * 3600, 16 * 3600);
+///
+/// let issues = schedule.diagnose(false);
+/// assert!(matches!(issues[..], [ScheduleIssue::Overlap { start: 25_200, end: 28_800, .. }]));
+/// assert_eq!(issues[0].to_string(), "the Tokyo and London windows overlap from 25200 to 28800 seconds");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScheduleIssue {
+    /// The window does not lie within a single day, see [`SessionSchedule::validate`].
+    OutOfRange(SessionWindow),
+    /// The window starts where it ends, so it covers no time at all.
+    ZeroLength(SessionWindow),
+    /// Two windows cover the seconds since midnight UTC in `[start, end)` at the same time.
+    Overlap { first: SessionWindow, second: SessionWindow, start: u32, end: u32 },
+    /// No window covers the seconds since midnight UTC in `[start, end)`.
+    Gap { start: u32, end: u32 },
 }
 
 
-/// Verifies if the given session string matches the trading session identified by the Unix timestamp.
+/// A session boundary at minute precision, as a time of day in UTC.
 ///
-/// This struct takes a Unix timestamp and a session name as input. It uses the `IdentifyTradingSession` struct
-/// to determine the trading session based on the timestamp. If the identified session matches the input session name,
+/// Some venues open or close at non-zero minutes, e.g. a session starting at 07:30. Boundaries are
+/// used to add windows to a custom [`SessionSchedule`] with [`SessionSchedule::add_between`].
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{IdentifyTradingSession, SessionBoundary, SessionSchedule, TradingSession};
+///
+/// let schedule = SessionSchedule::new()
+///     .add_between(TradingSession::Tokyo, SessionBoundary::new(0, 0), SessionBoundary::new(7, 30))
+///     .add_between(TradingSession::TokyoLondon, SessionBoundary::new(7, 30), SessionBoundary::new(9, 0));
+///
+/// // Thursday 22 February 2024, 07:29 and 07:30 UTC
+/// assert_eq!(IdentifyTradingSession::new(1708586940).with_schedule(schedule.clone()).trading_session(), TradingSession::Tokyo);
+/// assert_eq!(IdentifyTradingSession::new(1708587000).with_schedule(schedule).trading_session(), TradingSession::TokyoLondon);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SessionBoundary {
+    pub hour: u8,
+    pub minute: u8,
+}
+
+
+/// A calendar of UTC dates on which the market is closed, e.g. Christmas or New Year's Day.
+///
+/// Timestamps falling on one of these dates are identified as the Holiday session, regardless of the hour.
+/// With the `serde` feature enabled, calendars can be persisted along with their early closes.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{HolidayCalendar, IdentifyTradingSession, TradingSession};
+///
+/// let calendar = HolidayCalendar::new(&[(2024, 12, 25), (2025, 1, 1)]);
+///
+/// // Wednesday 25 December 2024, 10:00 UTC
+/// let session_identifier = IdentifyTradingSession::new(1735120800).with_holidays(calendar.clone());
+/// assert_eq!(session_identifier.trading_session(), TradingSession::Holiday);
+///
+/// // Thursday 22 February 2024, 10:00 UTC
+/// let session_identifier = IdentifyTradingSession::new(1708596000).with_holidays(calendar);
+/// assert_eq!(session_identifier.trading_session(), TradingSession::London);
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HolidayCalendar {
+    days: BTreeSet<i64>,
+    early_closes: Vec<EarlyClose>,
+    // Calendars persisted before late opens were supported have none
+    #[cfg_attr(feature = "serde", serde(default))]
+    late_opens: Vec<LateOpen>,
+}
+
+
+/// An early close of a trading session on a specific UTC date, e.g. New York on the day after Thanksgiving.
+///
+/// Unlike a holiday, the market opens as usual, but from `close_hour` on the session is closed and
+/// timestamps are identified as the Undefined session. Early closes are added to a [`HolidayCalendar`]
+/// with [`HolidayCalendar::with_early_close`].
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{EarlyClose, HolidayCalendar, IdentifyTradingSession, TradingSession};
+///
+/// let calendar = HolidayCalendar::new(&[]).with_early_close(EarlyClose {
+///     date: (2024, 11, 29),
+///     session: TradingSession::NewYork,
+///     close_hour: 18,
+/// });
+///
+/// // Thursday 28 November 2024, 20:00 UTC
+/// let session_identifier = IdentifyTradingSession::new(1732824000).with_holidays(calendar.clone());
+/// assert_eq!(session_identifier.trading_session(), TradingSession::NewYork);
+///
+/// // Friday 29 November 2024, 17:00 and 20:00 UTC
+/// let session_identifier = IdentifyTradingSession::new(1732899600).with_holidays(calendar.clone());
+/// assert_eq!(session_identifier.trading_session(), TradingSession::NewYork);
+/// let session_identifier = IdentifyTradingSession::new(1732910400).with_holidays(calendar);
+/// assert_eq!(session_identifier.trading_session(), TradingSession::Undefined);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EarlyClose {
+    pub date: (i32, u32, u32),
+    pub session: TradingSession,
+    pub close_hour: u8,
+}
+
+
+/// A late open of a trading session on a specific UTC date, e.g. after a delayed start of trading.
+///
+/// The counterpart of an [`EarlyClose`]: until `open_hour` the session is closed and timestamps are
+/// identified as the Undefined session, from then on the session trades as usual. Late opens are
+/// added to a [`HolidayCalendar`] with [`HolidayCalendar::with_late_open`].
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{HolidayCalendar, IdentifyTradingSession, LateOpen, TradingSession};
+///
+/// let calendar = HolidayCalendar::new(&[]).with_late_open(LateOpen {
+///     date: (2024, 2, 22),
+///     session: TradingSession::London,
+///     open_hour: 11,
+/// });
+///
+/// // Thursday 22 February 2024, 10:00 and 11:00 UTC
+/// let session_identifier = IdentifyTradingSession::new(1708596000).with_holidays(calendar.clone());
+/// assert_eq!(session_identifier.trading_session(), TradingSession::Undefined);
+/// let session_identifier = IdentifyTradingSession::new(1708599600).with_holidays(calendar);
+/// assert_eq!(session_identifier.trading_session(), TradingSession::London);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LateOpen {
+    pub date: (i32, u32, u32),
+    pub session: TradingSession,
+    pub open_hour: u8,
+}
+
+
+/// Iterates over the session boundaries within a range of Unix timestamps.
+///
+/// Yields a `(timestamp, session)` pair for every instant within `[start, end)` at which a new
+/// trading session starts, in ascending order. Boundaries are computed lazily, one at a time, so
+/// ranges spanning many days don't allocate.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{SessionRangeIter, TradingSession};
+///
+/// // Thursday 22 February 2024, 00:00 UTC to Friday 23 February 2024, 00:00 UTC
+/// let boundaries: Vec<(i64, TradingSession)> = SessionRangeIter::new(1708560000, 1708646400).collect();
+/// assert_eq!(boundaries, vec![
+///     (1708560000, TradingSession::Tokyo),
+///     (1708585200, TradingSession::TokyoLondon),
+///     (1708592400, TradingSession::London),
+///     (1708606800, TradingSession::LondonNewYork),
+///     (1708617600, TradingSession::NewYork),
+///     (1708639200, TradingSession::Undefined),
+/// ]);
+///
+/// // Friday 23 February 2024, 20:30 UTC to Monday 26 February 2024, 08:00 UTC
+/// let boundaries: Vec<(i64, TradingSession)> = SessionRangeIter::new(1708720200, 1708934400).collect();
+/// assert_eq!(boundaries, vec![
+///     (1708725600, TradingSession::Weekend),
+///     (1708898400, TradingSession::Undefined),
+///     (1708905600, TradingSession::Tokyo),
+///     (1708930800, TradingSession::TokyoLondon),
+/// ]);
+/// ```
+pub struct SessionRangeIter {
+    next: i64,
+    end: i64,
+}
+
+
+/// An iterator over the trading session intervals within a range of Unix timestamps.
+///
+/// Yields a `(session, start, end)` tuple for every interval within `[start, end)` during which the
+/// session doesn't change, in ascending order, with the end of each interval exclusive. The first and
+/// last intervals are clipped to the range. Unlike [`SessionRangeIter`], the intervals honor the
+/// configuration of the [`IdentifyTradingSession`] they are created from, e.g. its schedule or holidays.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{sessions_between, IdentifyTradingSession, SessionSet, TradingSession};
+///
+/// // Friday 23 February 2024, 20:00 UTC to Saturday 24 February 2024, 00:00 UTC
+/// let intervals: Vec<(TradingSession, i64, i64)> = sessions_between(1708718400, 1708732800).collect();
+/// assert_eq!(intervals, [
+///     (TradingSession::NewYork, 1708718400, 1708725600),
+///     (TradingSession::Weekend, 1708725600, 1708732800),
+/// ]);
+///
+/// // Sunday 25 February 2024, 21:00 UTC to Monday 26 February 2024, 01:00 UTC
+/// let intervals: Vec<(TradingSession, i64, i64)> = IdentifyTradingSession::new(1708894800)
+///     .with_session_set(SessionSet::ForexFour)
+///     .sessions_until(1708909200)
+///     .collect();
+/// assert_eq!(intervals, [
+///     (TradingSession::Weekend, 1708894800, 1708898400),
+///     (TradingSession::Sydney, 1708898400, 1708905600),
+///     (TradingSession::SydneyTokyo, 1708905600, 1708909200),
+/// ]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SessionIntervals {
+    identifier: IdentifyTradingSession,
+    next: i64,
+    end: i64,
+}
+
+
+/// Verifies if the given trading session matches the trading session identified by the Unix timestamp.
+///
+/// This struct takes a Unix timestamp and a trading session as input. It uses the `IdentifyTradingSession` struct
+/// to determine the trading session based on the timestamp. If the identified session matches the input session,
 /// it returns true; otherwise, it returns false.
 ///
 /// # Examples
 ///
 /// ```
-/// use trading_sessions::SessionVerification;
+/// use trading_sessions::{SessionVerification, TradingSession};
 ///
 /// // Assuming the timestamp corresponds to a time within the Tokyo session
-/// let verifier = SessionVerification::new(1708574400, "Tokyo".to_string());
+/// let verifier = SessionVerification::new(1708574400, TradingSession::Tokyo);
 /// assert!(verifier.verify());
 ///
-/// // Assuming the timestamp corresponds to a time within the London session but the session name is incorrect
-/// let verifier = SessionVerification::new(1708596000, "Tokyo".to_string());
+/// // Assuming the timestamp corresponds to a time within the London session but the session is incorrect
+/// let verifier = SessionVerification::new(1708596000, TradingSession::Tokyo);
 /// assert!(!verifier.verify());
 ///
-/// // Assuming the timestamp corresponds to a time within the London_NewYork session
-/// let verifier = SessionVerification::new(1708696800, "London_NewYork".to_string());
+/// // Session labels are parsed explicitly, so a typo surfaces as an error
+/// let verifier = SessionVerification::from_str(1708696800, "London_NewYork").unwrap();
 /// assert!(verifier.verify());
+/// assert_eq!(verifier.clone(), verifier);
+/// assert!(SessionVerification::from_str(1708696800, "Lnodon_NewYork").is_err());
 /// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct SessionVerification {
-    pub unix_timestamp: u32,
-    pub session: String,
+    pub unix_timestamp: i64,
+    pub session: TradingSession,
 }
 
 
+/// A table of alternative names of trading sessions, e.g. "Asia" or "NY", for session labels from other sources.
+///
+/// Labels are resolved case-insensitively and regardless of spaces, underscores and hyphens, so
+/// "new york", "NEW_YORK" and "NewYork" are all the same label. The canonical labels, as returned
+/// by `TradingSession::as_str`, always resolve, even in an empty table. Used by
+/// [`SessionVerification::from_alias`] and [`SessionVerification::verify_alias`], and to rename
+/// the session column with [`SessionColumn::with_output_aliases`]. Parse labels against the table
+/// with [`SessionAliases::parse`], as `str::parse::<TradingSession>` only accepts the canonical labels.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{SessionAliases, TradingSession};
+///
+/// let aliases = SessionAliases::common().with_alias("Europe", TradingSession::London);
+/// assert_eq!(aliases.resolve("tokyo"), Some(TradingSession::Tokyo));
+/// assert_eq!(aliases.resolve("Asia"), Some(TradingSession::Tokyo));
+/// assert_eq!(aliases.resolve("New York"), Some(TradingSession::NewYork));
+/// assert_eq!(aliases.resolve("europe"), Some(TradingSession::London));
+/// assert_eq!(aliases.resolve("Frankfurt"), None);
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct SessionAliases {
+    aliases: Vec<(String, TradingSession)>,
+}
+
+
+/// The error returned when parsing a trading session from a label that matches no session.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{ParseSessionError, TradingSession};
+///
+/// assert_eq!("London".parse::<TradingSession>(), Ok(TradingSession::London));
+/// assert_eq!("Lnodon".parse::<TradingSession>(), Err(ParseSessionError));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseSessionError;
+
+
+/// The error returned when a floating-point timestamp is not a valid Unix timestamp in seconds, e.g. NaN.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{IdentifyTradingSession, InvalidTimestamp};
+///
+/// assert_eq!(IdentifyTradingSession::from_f64(f64::INFINITY), Err(InvalidTimestamp));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InvalidTimestamp;
+
+
 /// Adds a "Session" column to a `LazyFrame` based on Unix timestamps in a "time" column.
 ///
 /// This method transforms the input `LazyFrame` by adding a new column named "Session".
@@ -142,6 +994,8 @@ pub struct SessionVerification {
 /// - NewYork: 4:00 PM - 10:00 PM
 /// - Any other time is labeled as "Undefined"
 ///
+/// Timestamps falling between Friday 22:00 and Sunday 22:00 (UTC), while the Forex market is closed, are labeled as "Weekend".
+///
 /// # Examples
 ///
 /// ```
@@ -157,19 +1011,288 @@ pub struct SessionVerification {
 /// let lazy_frame = df.lazy();
 ///
 /// // Create a SessionColumn instance and apply the session column transformation
-/// let mut session_column = SessionColumn::new(lazy_frame);
-/// session_column.apply_session_column();
+/// let session_column = SessionColumn::new(lazy_frame).apply_session_column();
 ///
 /// // Collect the transformed LazyFrame back into a DataFrame for verification
-/// let result_df = session_column.lazyframe.collect().unwrap();
+/// let result_df = session_column.into_lazyframe().collect().unwrap();
 ///
 /// // Verify that the "Session" column exists and contains the correct session names for each timestamp
-///assert_eq!(result_df.column("Session").unwrap().str_value(0).unwrap(), "Tokyo");
-///assert_eq!(result_df.column("Session").unwrap().str_value(1).unwrap(), "London");
-///assert_eq!(result_df.column("Session").unwrap().str_value(2).unwrap(), "London_NewYork");
+/// assert_eq!(result_df.column("Session").unwrap().str_value(0).unwrap(), "Tokyo");
+/// assert_eq!(result_df.column("Session").unwrap().str_value(1).unwrap(), "London");
+/// assert_eq!(result_df.column("Session").unwrap().str_value(2).unwrap(), "London_NewYork");
 /// ```
-///
-/// Note: This example assumes the existence of a `sessions` module where `SessionColumn` is defined.
+#[cfg(feature = "polars")]
+#[derive(Clone)]
 pub struct SessionColumn {
+    /// Deprecated: use [`SessionColumn::into_lazyframe`] instead. Kept public for one more release.
+    #[doc(hidden)]
     pub lazyframe: LazyFrame,
- }
\ No newline at end of file
+    input_column: String,
+    output_column: String,
+    datetime_column: Option<String>,
+    parse_strings: bool,
+    time_format: Option<String>,
+    schedule: Option<SessionSchedule>,
+    categorical: bool,
+    session_ids: bool,
+    output_aliases: Option<SessionAliases>,
+    dst: bool,
+    unit: TimestampUnit,
+    holidays: Option<HolidayCalendar>,
+    fallback_label: String,
+    null_label: Option<String>,
+    market: Market,
+}
+
+
+/// A reusable configuration of the session column, applied to any number of LazyFrames with [`SessionColumnBuilder::apply`].
+///
+/// The builder takes the same options as [`SessionColumn`], e.g. the column names, dtype, schedule
+/// and fallback label, along with the additional columns to append, without holding a LazyFrame.
+///
+/// # Examples
+///
+/// ```
+/// use polars::prelude::*;
+/// use trading_sessions::{SessionColumnBuilder, SessionSchedule};
+///
+/// let builder = SessionColumnBuilder::new()
+///     .with_columns("timestamp", "session")
+///     .with_schedule(SessionSchedule::forex())
+///     .with_fallback_label("Off_Hours")
+///     .with_indicator_columns();
+///
+/// // Thursday 22 February 2024, 10:00 and 23:00 UTC
+/// let df = df! { "timestamp" => [1708596000, 1708642800] }.unwrap();
+/// let result_df = builder.apply(df.lazy()).unwrap().collect().unwrap();
+///
+/// let sessions: Vec<Option<&str>> = result_df.column("session").unwrap().utf8().unwrap().into_iter().collect();
+/// assert_eq!(sessions, [Some("London"), Some("Off_Hours")]);
+/// assert_eq!(result_df.column("is_london").unwrap().bool().unwrap().get(0), Some(true));
+///
+/// // The timestamp column is checked when the builder is applied
+/// let df = df! { "time" => [1708596000] }.unwrap();
+/// assert!(builder.apply(df.lazy()).is_err());
+/// ```
+#[cfg(feature = "polars")]
+#[derive(Clone)]
+pub struct SessionColumnBuilder {
+    column: SessionColumn,
+    column_pairs: Vec<(String, String)>,
+    extra_columns: Vec<fn(&mut SessionColumn)>,
+}
+
+
+/// Summary statistics per trading session, for sanity-checking incoming market data, see [`SessionStats::summarize`].
+#[cfg(feature = "polars")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SessionStats;
+
+
+/// Resamples tick or minute data into one OHLC bar per session occurrence, see [`SessionResampler::resample`].
+///
+/// Each bar covers one occurrence of a trading session, e.g. the London session of 22 February 2024,
+/// as keyed by the "session_id" column of [`SessionColumn::apply_session_id_column`].
+///
+/// # Examples
+///
+/// ```
+/// use polars::prelude::*;
+/// use trading_sessions::SessionResampler;
+///
+/// // Thursday 22 February 2024, 04:00 and 05:00 UTC in Tokyo, 10:00 and 11:00 UTC in London
+/// let df = df! {
+///     "time" => [1708574400i64, 1708578000, 1708596000, 1708599600],
+///     "price" => [1.0, 3.0, 2.0, 4.0],
+///     "volume" => [10, 20, 30, 40]
+/// }.unwrap();
+///
+/// let bars = SessionResampler::new("price").with_volume_column("volume").resample(df.lazy()).unwrap().collect().unwrap();
+///
+/// assert_eq!(bars.get_column_names(), ["session_id", "Session", "session_open", "session_close", "open", "high", "low", "close", "volume"]);
+/// assert_eq!(bars.column("session_id").unwrap().str_value(1).unwrap(), "2024-02-22_London");
+/// assert_eq!(bars.column("session_open").unwrap().i64().unwrap().get(0), Some(1708560000));
+/// assert_eq!(bars.column("session_close").unwrap().i64().unwrap().get(1), Some(1708606800));
+/// assert_eq!(bars.column("close").unwrap().f64().unwrap().get(0), Some(3.0));
+/// assert_eq!(bars.column("volume").unwrap().i32().unwrap().get(1), Some(70));
+/// ```
+#[cfg(feature = "polars")]
+#[derive(Clone)]
+pub struct SessionResampler {
+    session_column: SessionColumnBuilder,
+    price_column: String,
+    volume_column: Option<String>,
+}
+
+
+/// Splits a LazyFrame labeled with a session column into train and test sets stratified by session, see [`SessionSplit::split`].
+///
+/// Within each session, the latest rows by time go to the test set, so every session is represented
+/// in proportion to its rows in both sets and no test row precedes a train row of the same session.
+///
+/// # Examples
+///
+/// ```
+/// use polars::prelude::*;
+/// use trading_sessions::{SessionColumn, SessionSplit};
+///
+/// // Thursday 22 February 2024, 03:00 to 06:00 and 09:00 to 12:00 UTC, i.e. four Tokyo and four London rows
+/// let df = df! {
+///     "time" => [1708570800i64, 1708574400, 1708578000, 1708581600, 1708592400, 1708596000, 1708599600, 1708603200]
+/// }.unwrap();
+/// let labeled = SessionColumn::new(df.lazy()).apply_session_column().into_lazyframe();
+///
+/// let (train, test) = SessionSplit::new("time", 0.25).split(labeled);
+/// let (train, test) = (train.collect().unwrap(), test.collect().unwrap());
+///
+/// assert_eq!((train.height(), test.height()), (6, 2));
+/// let test_times: Vec<Option<i64>> = test.column("time").unwrap().i64().unwrap().into_iter().collect();
+/// assert_eq!(test_times, [Some(1708581600), Some(1708603200)]);
+/// ```
+#[cfg(feature = "polars")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionSplit {
+    time_column: String,
+    session_column: String,
+    instance_column: Option<String>,
+    test_fraction: f64,
+}
+
+
+/// Adds the `.session()` namespace to Polars expressions, to classify timestamps within any lazy query.
+///
+/// The namespace works on expressions of integer Unix timestamps in seconds or of datetimes of any
+/// time unit, under the default configuration. Use [`SessionColumn`] for custom schedules, markets
+/// or holidays.
+///
+/// # Examples
+///
+/// ```
+/// use polars::prelude::*;
+/// use trading_sessions::SessionExprExt;
+///
+/// // Thursday 22 February 2024, 04:00 and 10:00 UTC, Saturday 24 February 2024, 02:00 UTC
+/// let df = df! {
+///     "time" => [1708574400i64, 1708596000, 1708740000]
+/// }.unwrap();
+///
+/// let result_df = df
+///     .lazy()
+///     .select([col("time").session().label().alias("session"), col("time").session().is_open().alias("open")])
+///     .collect()
+///     .unwrap();
+///
+/// let sessions: Vec<Option<&str>> = result_df.column("session").unwrap().utf8().unwrap().into_iter().collect();
+/// assert_eq!(sessions, [Some("Tokyo"), Some("London"), Some("Weekend")]);
+/// let open: Vec<Option<bool>> = result_df.column("open").unwrap().bool().unwrap().into_iter().collect();
+/// assert_eq!(open, [Some(true), Some(true), Some(false)]);
+/// ```
+#[cfg(feature = "polars")]
+pub trait SessionExprExt {
+    /// Accesses the session functions of an expression of timestamps.
+    fn session(self) -> SessionNameSpace;
+}
+
+
+/// The session functions of an expression of timestamps, see [`SessionExprExt::session`].
+#[cfg(feature = "polars")]
+pub struct SessionNameSpace(Expr);
+
+
+/// The aggregation applied to the value columns by [`SessionColumn::group_by_session`] and [`SessionColumn::group_by_session_and_date`].
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::SessionAgg;
+///
+/// let agg = SessionAgg::Mean;
+/// assert_eq!(agg, SessionAgg::Mean);
+/// ```
+#[cfg(feature = "polars")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SessionAgg {
+    Sum,
+    Mean,
+    Min,
+    Max,
+    /// The number of rows in each group, as a `UInt32` column.
+    Count,
+}
+
+
+/// The outcome of checking the session labels of a LazyFrame, returned by [`SessionVerification::verify_frame`].
+///
+/// The offending rows keep all columns of the input and add a "ComputedSession" column with the
+/// session identified from the timestamp, next to the claimed one.
+#[cfg(feature = "polars")]
+#[derive(Clone)]
+pub struct VerificationReport {
+    pub rows: usize,
+    pub mismatches: usize,
+    pub offending_rows: LazyFrame,
+}
+
+
+/// The error returned by [`classify_into`] when the output buffer does not match the timestamps in length.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{classify_into, LengthMismatch, TradingSession};
+///
+/// let mut out = [TradingSession::Undefined; 1];
+/// let error = classify_into(&[1708574400, 1708596000], &mut out).unwrap_err();
+/// assert_eq!(error, LengthMismatch { timestamps: 2, out: 1 });
+/// assert_eq!(error.to_string(), "cannot classify 2 timestamps into an output buffer of length 1");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LengthMismatch {
+    pub timestamps: usize,
+    pub out: usize,
+}
+
+
+/// The error returned by the fallible entry points of [`SessionColumn`] and by schedule and label validation.
+///
+/// # Examples
+///
+/// ```
+/// use polars::prelude::*;
+/// use trading_sessions::{SessionColumn, TradingSessionsError};
+///
+/// let df = df! {
+///     "timestamp" => [1708574400, 1708596000]
+/// }.unwrap();
+///
+/// match SessionColumn::new(df.lazy()).try_apply_session_column() {
+///     Err(TradingSessionsError::MissingColumn(column)) => assert_eq!(column, "time"),
+///     _ => unreachable!(),
+/// }
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub enum TradingSessionsError {
+    /// The LazyFrame has no column of the given name.
+    MissingColumn(String),
+    /// The column is not of a dtype sessions can be identified from.
+    WrongDtype { column: String, expected: &'static str },
+    /// A window of the schedule does not lie within a single day.
+    InvalidSchedule(SessionWindow),
+    /// The label matches no trading session.
+    UnknownSession(String),
+    /// The name matches no schedule preset, see `SessionSchedule::preset`.
+    UnknownPreset(String),
+    /// Daylight saving time was enabled along with a custom schedule, which it does not shift.
+    #[cfg(feature = "polars")]
+    DstWithSchedule,
+    /// An error raised by Polars, e.g. while resolving the schema of the LazyFrame.
+    #[cfg(feature = "polars")]
+    Polars(PolarsError),
+    /// The JSON document is malformed or does not describe the expected type.
+    #[cfg(feature = "json")]
+    Json(serde_json::Error),
+    /// The JSON document has a schema version other than [`JSON_SCHEMA_VERSION`], or none at all.
+    #[cfg(feature = "json")]
+    UnsupportedVersion(Option<u64>),
+}
@@ -1,9 +1,34 @@
+#[cfg(feature = "polars")]
 use polars::prelude::*;
+#[cfg(feature = "polars")]
+use polars::series::IsSorted;
 
-const SECONDS_PER_DAY: u32 = 86_400;
-const SECONDS_PER_HOUR: u32 = 3_600;
+pub(crate) const SECONDS_PER_DAY: u32 = 86_400;
+pub(crate) const SECONDS_PER_HOUR: u32 = 3_600;
+const DAYS_PER_WEEK: u32 = 7;
+const SUNDAY: u32 = 0;
+const SATURDAY: u32 = 6;
+/// The Forex week opens on Sunday 22:00 UTC, so shifting by two hours aligns the weekend with Saturday and Sunday.
+const WEEKEND_SHIFT: i64 = 2 * SECONDS_PER_HOUR as i64;
+const SECONDS_PER_WEEK: i64 = DAYS_PER_WEEK as i64 * SECONDS_PER_DAY as i64;
+/// How far `next_session` and `previous_session` look for a change of session, in seconds.
+const NAVIGATION_HORIZON: i64 = 14 * SECONDS_PER_DAY as i64;
 
-use crate::{IdentifyTradingSession, SessionVerification, SessionColumn};
+#[cfg(feature = "alloc")]
+use alloc::{format, string::{String, ToString}, vec::Vec};
+#[cfg(feature = "alloc")]
+use core::ops::Range;
+use core::ops::RangeInclusive;
+
+use crate::{Exchange, IdentifyTradingSession, IntoUnixSeconds, InvalidTimestamp, Killzone, LengthMismatch, Market, ScheduleIssue, SessionBoundary, SessionContext, SessionIntervals, SessionMetadata, SessionVerification, ParseSessionError, SessionRangeIter, SessionResult, SessionSet, SessionWindow, TimestampUnit, TradingSession, FOREX_FOUR_BOUNDARIES, KILLZONE_BOUNDARIES, SESSION_BOUNDARIES};
+#[cfg(feature = "alloc")]
+use crate::{EarlyClose, HolidayCalendar, LateOpen, SessionAliases, SessionSchedule, SqlDialect, TradingSessionsError};
+#[cfg(feature = "std")]
+use crate::{Clock, SessionClock, SystemClock};
+#[cfg(feature = "polars")]
+use crate::{SessionAgg, SessionColumn, SessionColumnBuilder, SessionExprExt, SessionNameSpace, SessionResampler, SessionSplit, SessionStats, VerificationReport};
+#[cfg(feature = "json")]
+use crate::JSON_SCHEMA_VERSION;
 
 
 impl IdentifyTradingSession {
@@ -11,90 +36,8445 @@ impl IdentifyTradingSession {
     ///
     /// # Arguments
     ///
-    /// * `unix_timestamp` - A 32-bit unsigned integer representing the Unix timestamp in seconds.
+    /// * `unix_timestamp` - A 64-bit signed integer representing the Unix timestamp in seconds, which may be negative.
     ///
     /// # Returns
     ///
     /// A new IdentifyTradingSession instance.
-    pub fn new(unix_timestamp: u32) -> Self {
-        Self { unix_timestamp }
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{IdentifyTradingSession, TradingSession};
+    ///
+    /// // Timestamps before 1970 and after 2038 are identified like any other
+    /// assert_eq!(IdentifyTradingSession::new(-72000).trading_session(), TradingSession::Tokyo);     // Wednesday 31 December 1969, 04:00 UTC
+    /// assert_eq!(IdentifyTradingSession::new(-396000).trading_session(), TradingSession::Weekend);  // Saturday 27 December 1969, 10:00 UTC
+    /// assert_eq!(IdentifyTradingSession::new(2213604000).trading_session(), TradingSession::London); // Thursday 23 February 2040, 10:00 UTC
+    /// ```
+    pub fn new(unix_timestamp: i64) -> Self {
+        Self {
+            unix_timestamp,
+            utc_offset: 0,
+            market: Market::Forex,
+            #[cfg(feature = "alloc")]
+            schedule: None,
+            exchange: None,
+            #[cfg(feature = "alloc")]
+            holidays: None,
+            dst: false,
+        }
     }
 
-    /// Identifies the trading session based on the stored Unix timestamp in seconds.
+    /// Creates a new IdentifyTradingSession instance from epoch seconds of any numeric type, see [`IntoUnixSeconds`].
+    ///
+    /// Unlike [`IdentifyTradingSession::new`], which takes `i64` seconds, this accepts e.g. the `u32`
+    /// timestamps of older data or float timestamps without casting them first.
+    ///
+    /// # Arguments
     ///
-    /// Returns a string representing the trading session based on the hour of the day in UTC.
+    /// * `unix_timestamp` - The Unix timestamp in seconds, as an `i32`, `u32`, `i64`, `u64` or `f64`.
     ///
     /// # Examples
     ///
     /// ```
-    /// use trading_sessions::IdentifyTradingSession;
+    /// use trading_sessions::{IdentifyTradingSession, TradingSession};
     ///
-    /// let session_identifier = IdentifyTradingSession::new(1708574400);
-    /// assert_eq!(session_identifier.identify_trading_session(), "Tokyo");
+    /// let timestamp: u32 = 1708596000;
+    /// assert_eq!(IdentifyTradingSession::from_unix_seconds(timestamp).trading_session(), TradingSession::London);
+    /// assert_eq!(IdentifyTradingSession::from_unix_seconds(1708574400.5).trading_session(), TradingSession::Tokyo);
+    /// ```
+    pub fn from_unix_seconds(unix_timestamp: impl IntoUnixSeconds) -> Self {
+        Self::new(unix_timestamp.into_unix_seconds())
+    }
+
+    /// Creates a new IdentifyTradingSession instance that buckets the timestamp by the local time at a fixed UTC offset.
+    ///
+    /// The offset is added to the timestamp before determining the hour of the day and the weekend,
+    /// wrapping around midnight for both positive and negative offsets. This is a simple fixed
+    /// shift, so Daylight Saving Time is not taken into account.
+    ///
+    /// # Arguments
+    ///
+    /// * `unix_timestamp` - A 64-bit signed integer representing the Unix timestamp in seconds, which may be negative.
+    /// * `offset_seconds` - The offset from UTC in seconds, e.g. `9 * 3600` for Tokyo local time.
+    ///
+    /// # Returns
+    ///
+    /// A new IdentifyTradingSession instance at the given UTC offset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{IdentifyTradingSession, TradingSession};
+    ///
+    /// // Thursday 22 February 2024, 00:00 UTC is 09:00 in Tokyo
+    /// let session_identifier = IdentifyTradingSession::with_utc_offset(1708560000, 9 * 3600);
+    /// assert_eq!(session_identifier.trading_session(), TradingSession::London);
+    ///
+    /// // Thursday 22 February 2024, 02:00 UTC is 21:00 on Wednesday in New York
+    /// let session_identifier = IdentifyTradingSession::with_utc_offset(1708567200, -5 * 3600);
+    /// assert_eq!(session_identifier.trading_session(), TradingSession::NewYork);
+    /// assert_eq!(session_identifier.date_and_session(), (2024, 2, 21, TradingSession::NewYork));
+    ///
+    /// // Thursday 22 February 2024, 03:00 UTC is 22:00 on Wednesday in New York
+    /// let session_identifier = IdentifyTradingSession::with_utc_offset(1708570800, -5 * 3600);
+    /// assert_eq!(session_identifier.trading_session(), TradingSession::Undefined);
+    ///
+    /// // Local times beyond the range of i64 saturate at its extremes rather than overflowing
+    /// let session_identifier = IdentifyTradingSession::with_utc_offset(i64::MAX, 9 * 3600);
+    /// assert_eq!(session_identifier.trading_session(), TradingSession::Weekend);
+    /// assert_eq!(session_identifier.next_session(), None);
+    /// assert_eq!(IdentifyTradingSession::with_utc_offset(i64::MIN, -5 * 3600).session_bounds(), None);
+    /// ```
+    pub fn with_utc_offset(unix_timestamp: i64, offset_seconds: i32) -> Self {
+        Self { utc_offset: offset_seconds, ..Self::new(unix_timestamp) }
+    }
+
+    /// Creates a new IdentifyTradingSession instance from an integer Unix timestamp in the given unit.
+    ///
+    /// The timestamp is floored to whole seconds, so e.g. epoch milliseconds don't need to be
+    /// pre-scaled.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestamp` - The Unix timestamp in the given unit, which may be negative.
+    /// * `unit` - The unit of the timestamp.
+    ///
+    /// # Returns
+    ///
+    /// A new IdentifyTradingSession instance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{IdentifyTradingSession, TimestampUnit};
+    ///
+    /// // Thursday 22 February 2024, 06:59:59.999999 UTC
+    /// let session_identifier = IdentifyTradingSession::from_timestamp_unit(1708585199999999, TimestampUnit::Microseconds);
+    /// assert_eq!(session_identifier.unix_timestamp, 1708585199);
+    ///
+    /// let session_identifier = IdentifyTradingSession::from_timestamp_unit(-1, TimestampUnit::Nanoseconds);
+    /// assert_eq!(session_identifier.unix_timestamp, -1);
+    /// ```
+    pub fn from_timestamp_unit(timestamp: i64, unit: TimestampUnit) -> Self {
+        Self::new(timestamp.div_euclid(unit.ticks_per_second()))
+    }
+
+    /// Creates a new IdentifyTradingSession instance from a floating-point Unix timestamp in seconds,
+    /// e.g. as parsed from a CSV or JSON file.
+    ///
+    /// Fractions of a second are floored to the whole second they fall in.
+    ///
+    /// # Arguments
+    ///
+    /// * `unix_timestamp` - The Unix timestamp in seconds, possibly with a fraction.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidTimestamp` if the timestamp is NaN, infinite or does not fit a 64-bit
+    /// Unix timestamp once floored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{IdentifyTradingSession, InvalidTimestamp, TradingSession};
+    ///
+    /// // Thursday 22 February 2024, 06:59:59.75 UTC, still within Tokyo
+    /// let session_identifier = IdentifyTradingSession::from_f64(1708585199.75).unwrap();
+    /// assert_eq!(session_identifier.unix_timestamp, 1708585199);
+    /// assert_eq!(session_identifier.trading_session(), TradingSession::Tokyo);
+    ///
+    /// // Timestamps past 2038 and before 1970 are floored as well
+    /// assert_eq!(IdentifyTradingSession::from_f64(4294967296.5).unwrap().unix_timestamp, 4294967296);
+    /// assert_eq!(IdentifyTradingSession::from_f64(-0.5).unwrap().unix_timestamp, -1);
+    ///
+    /// assert_eq!(IdentifyTradingSession::from_f64(f64::NAN), Err(InvalidTimestamp));
+    /// assert_eq!(IdentifyTradingSession::from_f64(1e19), Err(InvalidTimestamp));
     /// ```
-    pub fn identify_trading_session(&self) -> String {
-        let utc_hour = (self.unix_timestamp % SECONDS_PER_DAY) / SECONDS_PER_HOUR;
+    pub fn from_f64(unix_timestamp: f64) -> Result<Self, InvalidTimestamp> {
+        // NaN fails both comparisons, and 2^63 is the first value out of range for an i64
+        if !(i64::MIN as f64..-(i64::MIN as f64)).contains(&unix_timestamp) {
+            return Err(InvalidTimestamp);
+        }
 
-        match utc_hour {
-            0..=6 => "Tokyo".to_string(),            // 12:00 AM - 7:00 AM
-            7..=8 => "Tokyo_London".to_string(),     // 7:00 AM - 9:00 AM
-            9..=12 => "London".to_string(),          // 9:00 AM - 1:00 PM
-            13..=15 => "London_NewYork".to_string(), // 1:00 PM - 4:00 PM
-            16..=21 => "NewYork".to_string(),        // 4:00 PM - 10:00 PM
-            _ => "Undefined".to_string(),
+        // The cast truncates towards zero, which is one second too late for negative fractions
+        let truncated = unix_timestamp as i64;
+        match (truncated as f64) > unix_timestamp {
+            true => Ok(Self::new(truncated - 1)),
+            false => Ok(Self::new(truncated)),
         }
     }
-}
 
+    /// Creates a new IdentifyTradingSession instance that follows the trading hours of the given exchange.
+    ///
+    /// Whenever the exchange is closed, e.g. outside of its opening hours, on the weekend or during a
+    /// maintenance break, the trading session is identified as `TradingSession::Closed`. Otherwise
+    /// the session follows the schedule, as for any other timestamp.
+    ///
+    /// # Arguments
+    ///
+    /// * `unix_timestamp` - A 64-bit signed integer representing the Unix timestamp in seconds, which may be negative.
+    /// * `exchange` - The exchange whose trading hours apply.
+    ///
+    /// # Returns
+    ///
+    /// A new IdentifyTradingSession instance for the given exchange.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{Exchange, IdentifyTradingSession, TradingSession};
+    ///
+    /// // Thursday 22 February 2024, 21:00 UTC
+    /// let session_identifier = IdentifyTradingSession::for_exchange(1708635600, Exchange::CME);
+    /// assert_eq!(session_identifier.trading_session(), TradingSession::NewYork);
+    ///
+    /// // Thursday 22 February 2024, 22:30 UTC, during the CME Globex maintenance break
+    /// let session_identifier = IdentifyTradingSession::for_exchange(1708641000, Exchange::CME);
+    /// assert_eq!(session_identifier.identify_trading_session(), "Closed");
+    /// ```
+    pub fn for_exchange(unix_timestamp: i64, exchange: Exchange) -> Self {
+        Self { exchange: Some(exchange), ..Self::new(unix_timestamp) }
+    }
+
+    /// Sets a custom schedule of session windows to identify the session with, instead of the default schedule.
+    ///
+    /// The windows of the schedule are expressed in seconds since midnight UTC, so session
+    /// boundaries need not fall on the hour. Weekends are still identified as such for the Forex market.
+    ///
+    /// # Arguments
+    ///
+    /// * `schedule` - The schedule of session windows.
+    ///
+    /// # Returns
+    ///
+    /// The IdentifyTradingSession instance using the given schedule.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{IdentifyTradingSession, SessionSchedule, TradingSession};
+    ///
+    /// // The Tokyo/London overlap starts at 07:30 instead of 07:00
+    /// let schedule = SessionSchedule::new()
+    ///     .add(TradingSession::Tokyo, 0, 27_000)
+    ///     .add(TradingSession::TokyoLondon, 27_000, 32_400);
+    ///
+    /// // Thursday 22 February 2024, 07:15 UTC
+    /// let session_identifier = IdentifyTradingSession::new(1708586100).with_schedule(schedule.clone());
+    /// assert_eq!(session_identifier.trading_session(), TradingSession::Tokyo);
+    ///
+    /// // Thursday 22 February 2024, 07:45 UTC
+    /// let session_identifier = IdentifyTradingSession::new(1708587900).with_schedule(schedule);
+    /// assert_eq!(session_identifier.trading_session(), TradingSession::TokyoLondon);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn with_schedule(mut self, schedule: SessionSchedule) -> Self {
+        self.schedule = Some(schedule);
+        self
+    }
 
+    /// Identifies the trading sessions of one of the session sets, see [`SessionSet`].
+    ///
+    /// This is a shorthand for `with_schedule(set.schedule())`.
+    ///
+    /// # Arguments
+    ///
+    /// * `set` - The set of trading centers to divide the day into.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{IdentifyTradingSession, SessionSet, TradingSession};
+    ///
+    /// // Thursday 22 February 2024, 03:00 UTC
+    /// let session_identifier = IdentifyTradingSession::new(1708570800).with_session_set(SessionSet::ForexFour);
+    /// assert_eq!(session_identifier.trading_session(), TradingSession::SydneyTokyo);
+    /// assert!(session_identifier.is_overlap());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn with_session_set(self, set: SessionSet) -> Self {
+        self.with_schedule(set.schedule())
+    }
 
-impl SessionVerification {
-    /// Creates a new SessionVerification instance with the given Unix timestamp and session name.
+    /// Sets the market whose trading hours are used to identify the session.
     ///
     /// # Arguments
     ///
-    /// * `unix_timestamp` - A 32-bit unsigned integer representing the Unix timestamp in seconds.
-    /// * `session` - A string representing the trading session name.
+    /// * `market` - The market, `Market::Forex` by default.
     ///
     /// # Returns
     ///
-    /// A new SessionVerification instance.
-    pub fn new(unix_timestamp: u32, session: String) -> Self {
-        Self { unix_timestamp, session }
+    /// The IdentifyTradingSession instance for the given market.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{IdentifyTradingSession, Market, TradingSession};
+    ///
+    /// // Thursday 22 February 2024, 23:00 UTC
+    /// let session_identifier = IdentifyTradingSession::new(1708642800);
+    /// assert_eq!(session_identifier.trading_session(), TradingSession::Undefined);
+    ///
+    /// let session_identifier = session_identifier.with_market(Market::Crypto);
+    /// assert_eq!(session_identifier.trading_session(), TradingSession::Tokyo);
+    ///
+    /// // Crypto markets never leave a timestamp without a session
+    /// for hour in 0..=23 {
+    ///     let session_identifier = IdentifyTradingSession::new(1708560000 + hour * 3600).with_market(Market::Crypto);
+    ///     assert_ne!(session_identifier.trading_session(), TradingSession::Undefined);
+    /// }
+    /// ```
+    pub fn with_market(mut self, market: Market) -> Self {
+        self.market = market;
+        self
     }
-    /// Verifies if the given session string matches the trading session identified by the Unix timestamp.
+
+    /// Shifts the London and New York session boundaries for daylight saving time.
+    ///
+    /// While the UK observes British Summer Time, from the last Sunday of March to the last Sunday
+    /// of October at 01:00 UTC, the boundaries set by London (its open at 07:00 and close at 16:00
+    /// UTC) are an hour earlier. Likewise while the US observes daylight saving time, from the second
+    /// Sunday of March at 07:00 UTC to the first Sunday of November at 06:00 UTC, the New York open
+    /// at 13:00 and close at 22:00 UTC are an hour earlier. The Tokyo boundaries never shift.
+    ///
+    /// A custom schedule takes precedence, so it is not shifted. For the NYSE and CME Globex schedules,
+    /// pick the schedule in effect at the timestamp instead, e.g. with [`SessionSchedule::nyse_at`].
     ///
     /// # Returns
     ///
-    /// Returns true if the identified session matches the input session name; otherwise, returns false.
-    pub fn verify(&self) -> bool {
-        let session_identifier = IdentifyTradingSession::new(self.unix_timestamp);
-        let identified_session = session_identifier.identify_trading_session();
-        self.session == identified_session
+    /// The IdentifyTradingSession instance with daylight saving time enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{IdentifyTradingSession, TradingSession};
+    ///
+    /// // Thursday 13 June 2024, 06:30 UTC is 07:30 in London
+    /// let session_identifier = IdentifyTradingSession::new(1718260200);
+    /// assert_eq!(session_identifier.trading_session(), TradingSession::Tokyo);
+    /// assert_eq!(session_identifier.with_dst().trading_session(), TradingSession::TokyoLondon);
+    ///
+    /// // Thursday 13 June 2024, 21:30 UTC is 17:30 in New York, after the close
+    /// assert_eq!(IdentifyTradingSession::new(1718314200).with_dst().trading_session(), TradingSession::Undefined);
+    ///
+    /// // Wednesday 20 March 2024, New York has moved its clocks, but London has not yet
+    /// assert_eq!(IdentifyTradingSession::new(1710970200).with_dst().trading_session(), TradingSession::Undefined); // 21:30 UTC
+    /// assert_eq!(IdentifyTradingSession::new(1710916200).with_dst().trading_session(), TradingSession::Tokyo);     // 06:30 UTC
+    /// ```
+    pub fn with_dst(mut self) -> Self {
+        self.dst = true;
+        self
     }
-}
 
+    /// Sets the holiday calendar, so that timestamps falling on one of its dates are identified as `TradingSession::Holiday`.
+    ///
+    /// # Arguments
+    ///
+    /// * `calendar` - The calendar of UTC dates on which the market is closed.
+    ///
+    /// # Returns
+    ///
+    /// The IdentifyTradingSession instance with the holiday calendar.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{HolidayCalendar, IdentifyTradingSession};
+    ///
+    /// // Wednesday 1 January 2025, 14:00 UTC
+    /// let session_identifier = IdentifyTradingSession::new(1735740000)
+    ///     .with_holidays(HolidayCalendar::new(&[(2025, 1, 1)]));
+    /// assert_eq!(session_identifier.identify_trading_session(), "Holiday");
+    ///
+    /// // Thursday 2 January 2025, 14:00 UTC
+    /// let session_identifier = IdentifyTradingSession::new(1735826400)
+    ///     .with_holidays(HolidayCalendar::new(&[(2025, 1, 1)]));
+    /// assert_eq!(session_identifier.identify_trading_session(), "London_NewYork");
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn with_holidays(mut self, calendar: HolidayCalendar) -> Self {
+        self.holidays = Some(calendar);
+        self
+    }
 
+    /// Creates a new IdentifyTradingSession instance from a `chrono` UTC datetime.
+    ///
+    /// Requires the `chrono` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `dt` - A UTC datetime, stored as its Unix timestamp in seconds.
+    ///
+    /// # Returns
+    ///
+    /// A new IdentifyTradingSession instance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use trading_sessions::IdentifyTradingSession;
+    ///
+    /// let dt = Utc.with_ymd_and_hms(2024, 2, 22, 10, 0, 0).unwrap();
+    /// let session_identifier = IdentifyTradingSession::from_datetime(dt);
+    /// assert_eq!(session_identifier.unix_timestamp, 1708596000);
+    /// assert_eq!(session_identifier.identify_trading_session(), "London");
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn from_datetime(dt: chrono::DateTime<chrono::Utc>) -> Self {
+        Self::new(dt.timestamp())
+    }
 
-impl SessionColumn {
-    pub fn new(lazyframe: LazyFrame) -> Self {
-        Self { lazyframe }
+    /// Converts the stored Unix timestamp back to a `chrono` UTC datetime.
+    ///
+    /// Requires the `chrono` feature.
+    ///
+    /// # Returns
+    ///
+    /// The UTC datetime of the stored Unix timestamp, or `None` if it lies outside the range of
+    /// dates `chrono` can represent, e.g. for `i64::MAX`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use trading_sessions::IdentifyTradingSession;
+    ///
+    /// let dt = Utc.with_ymd_and_hms(2024, 2, 22, 10, 0, 0).unwrap();
+    /// let session_identifier = IdentifyTradingSession::from(dt);
+    /// assert_eq!(session_identifier.to_datetime(), Some(dt));
+    ///
+    /// assert_eq!(IdentifyTradingSession::new(i64::MAX).to_datetime(), None);
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn to_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        chrono::DateTime::from_timestamp(self.unix_timestamp, 0)
     }
 
-    /// Applies the trading session column transformation to the LazyFrame.
-    pub fn apply_session_column(&mut self) {
-        self.lazyframe = self.lazyframe.clone().with_column(
-            when(((col("time") % lit(SECONDS_PER_DAY)) / lit(SECONDS_PER_HOUR))
-                .lt_eq(lit(6))).then(lit("Tokyo"))
-                .when(((col("time") % lit(SECONDS_PER_DAY)) / lit(SECONDS_PER_HOUR))
-                .lt_eq(lit(8))).then(lit("Tokyo_London"))
-                .when(((col("time") % lit(SECONDS_PER_DAY)) / lit(SECONDS_PER_HOUR))
-                .lt_eq(lit(12))).then(lit("London"))
-                .when(((col("time") % lit(SECONDS_PER_DAY)) / lit(SECONDS_PER_HOUR))
-                .lt_eq(lit(15))).then(lit("London_NewYork"))
-                .when(((col("time") % lit(SECONDS_PER_DAY)) / lit(SECONDS_PER_HOUR))
-                .lt_eq(lit(21))).then(lit("NewYork"))
-                .otherwise(lit("hello"))
-                .alias("Session"));
-    }
-}
\ No newline at end of file
+    /// Creates a new IdentifyTradingSession instance from a `SystemTime`, e.g. the current time in a live trading system.
+    ///
+    /// Requires the `std` feature. Fractions of a second are truncated.
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - The system time, stored as its Unix timestamp in seconds.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `SystemTimeError` if the system time lies before the Unix epoch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, SystemTime, UNIX_EPOCH};
+    /// use trading_sessions::{IdentifyTradingSession, TradingSession};
+    ///
+    /// // The session right now
+    /// let session = IdentifyTradingSession::from_system_time(SystemTime::now()).unwrap().trading_session();
+    /// assert!(TradingSession::ALL.contains(&session));
+    ///
+    /// let time = UNIX_EPOCH + Duration::from_millis(1708596000_500);
+    /// assert_eq!(IdentifyTradingSession::from_system_time(time).unwrap().unix_timestamp, 1708596000);
+    /// assert!(IdentifyTradingSession::from_system_time(UNIX_EPOCH - Duration::from_secs(1)).is_err());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn from_system_time(time: std::time::SystemTime) -> Result<Self, std::time::SystemTimeError> {
+        let since_epoch = time.duration_since(std::time::UNIX_EPOCH)?;
+        Ok(Self::new(since_epoch.as_secs() as i64))
+    }
+
+    /// Identifies the trading session based on the stored Unix timestamp in seconds.
+    ///
+    /// Returns the trading session based on the hour of the day in UTC. The session compares equal
+    /// to its label, so it can be checked against a string directly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::IdentifyTradingSession;
+    ///
+    /// let session_identifier = IdentifyTradingSession::new(1708574400);
+    /// assert_eq!(session_identifier.identify_trading_session(), "Tokyo");
+    /// ```
+    pub fn identify_trading_session(&self) -> TradingSession {
+        self.trading_session()
+    }
+
+    /// Returns the label of the trading session, as returned by `identify_trading_session` before it
+    /// returned a `TradingSession`.
+    ///
+    /// Prefer `identify_trading_session` or `trading_session` and match on the variants, which the
+    /// compiler checks, over comparing labels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::IdentifyTradingSession;
+    ///
+    /// let session_identifier = IdentifyTradingSession::new(1708696800);
+    /// assert_eq!(session_identifier.identify_trading_session_label(), "London_NewYork");
+    /// ```
+    pub fn identify_trading_session_label(&self) -> &'static str {
+        self.trading_session().as_str()
+    }
+
+    /// Identifies the trading session based on the stored Unix timestamp in seconds.
+    ///
+    /// Returns the trading session based on the hour of the day in UTC, or `TradingSession::Weekend`
+    /// if the timestamp falls between Friday 22:00 and Sunday 22:00 UTC, while the Forex market is closed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{IdentifyTradingSession, TradingSession};
+    ///
+    /// let session_identifier = IdentifyTradingSession::new(1708574400);
+    /// assert_eq!(session_identifier.trading_session(), TradingSession::Tokyo);
+    ///
+    /// // Saturday 24 February 2024, 02:00 UTC
+    /// let session_identifier = IdentifyTradingSession::new(1708740000);
+    /// assert_eq!(session_identifier.trading_session(), TradingSession::Weekend);
+    ///
+    /// // Friday 23 February 2024, 22:00 UTC and Sunday 25 February 2024, 22:00 UTC
+    /// assert_eq!(IdentifyTradingSession::new(1708725600).trading_session(), TradingSession::Weekend);
+    /// assert_eq!(IdentifyTradingSession::new(1708898400).trading_session(), TradingSession::Undefined);
+    ///
+    /// // Every timestamp is handled, down to the extremes of i64, both of which fall on a Sunday
+    /// assert_eq!(IdentifyTradingSession::new(i64::MAX).trading_session(), TradingSession::Weekend);
+    /// assert_eq!(IdentifyTradingSession::new(i64::MIN).trading_session(), TradingSession::Weekend);
+    /// ```
+    pub fn trading_session(&self) -> TradingSession {
+        self.trading_session_at(self.unix_timestamp)
+    }
+
+    /// Identifies the trading session of another Unix timestamp in seconds under the same configuration.
+    fn trading_session_at(&self, unix_timestamp: i64) -> TradingSession {
+        let local_timestamp = unix_timestamp.saturating_add(i64::from(self.utc_offset));
+        #[cfg(feature = "alloc")]
+        if self.holidays.as_ref().is_some_and(|calendar| calendar.is_holiday(unix_timestamp)) {
+            return TradingSession::Holiday;
+        }
+
+        match self.exchange {
+            // The trading hours of the exchange take the place of the weekend
+            Some(exchange) if !exchange.is_open(unix_timestamp) => return TradingSession::Closed,
+            Some(_) => {}
+            None if self.market == Market::Forex && self.is_weekend_at(local_timestamp) => return TradingSession::Weekend,
+            None => {}
+        }
+
+        let session = self.daily_session(unix_timestamp, seconds_of_day(local_timestamp));
+        #[cfg(feature = "alloc")]
+        if self.holidays.as_ref().is_some_and(|calendar| calendar.is_overridden(unix_timestamp, session)) {
+            return TradingSession::Undefined;
+        }
+        session
+    }
+
+    /// Returns whether the stored Unix timestamp falls within the given trading session.
+    ///
+    /// This is a shorthand for comparing against `trading_session`, without constructing a SessionVerification.
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - The trading session to compare against.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use trading_sessions::{IdentifyTradingSession, TradingSession};
+    ///
+    /// let session_identifier = IdentifyTradingSession::new(1708596000);
+    /// assert!(session_identifier.matches(TradingSession::London));
+    /// assert!(!session_identifier.matches(TradingSession::Tokyo));
+    ///
+    /// // Identifiers with the same timestamp are equal, so they can be used as cache keys
+    /// let mut cache = HashMap::new();
+    /// cache.insert(session_identifier.clone(), session_identifier.trading_session());
+    /// assert_eq!(cache.get(&IdentifyTradingSession::new(1708596000)), Some(&TradingSession::London));
+    /// ```
+    pub fn matches(&self, session: TradingSession) -> bool {
+        self.trading_session() == session
+    }
+
+    /// Returns whether the stored Unix timestamp falls within an overlap of two major trading centers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::IdentifyTradingSession;
+    ///
+    /// assert!(IdentifyTradingSession::new(1708588800).is_overlap());  // 08:00 UTC, Tokyo_London
+    /// assert!(!IdentifyTradingSession::new(1708596000).is_overlap()); // 10:00 UTC, London
+    /// assert!(!IdentifyTradingSession::new(1708642800).is_overlap()); // 23:00 UTC, Undefined
+    /// ```
+    pub fn is_overlap(&self) -> bool {
+        self.trading_session().is_overlap()
+    }
+
+    /// Identifies all trading sessions active at the stored Unix timestamp, reporting an overlap as
+    /// the two concurrent sessions it consists of, see [`TradingSession::constituents`].
+    ///
+    /// # Returns
+    ///
+    /// The active trading sessions in chronological order, or an empty vector if none is active.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{IdentifyTradingSession, TradingSession};
+    ///
+    /// // Thursday 22 February 2024, 14:00 UTC
+    /// let session_identifier = IdentifyTradingSession::new(1708610400);
+    /// assert_eq!(session_identifier.active_sessions(), [TradingSession::London, TradingSession::NewYork]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn active_sessions(&self) -> Vec<TradingSession> {
+        self.trading_session().constituents().to_vec()
+    }
+
+    /// Identifies the ICT-style killzone of the stored Unix timestamp, see [`Killzone`].
+    ///
+    /// The killzones follow [`KILLZONE_BOUNDARIES`], shifted by the fixed UTC offset, if any. There is
+    /// no killzone during the Forex weekend.
+    ///
+    /// # Returns
+    ///
+    /// The killzone, or `None` outside of every killzone.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{IdentifyTradingSession, Killzone};
+    ///
+    /// assert_eq!(IdentifyTradingSession::new(1708570800).killzone(), Some(Killzone::Asian));       // 03:00 UTC
+    /// assert_eq!(IdentifyTradingSession::new(1708614000).killzone(), Some(Killzone::LondonClose)); // 15:00 UTC
+    /// assert_eq!(IdentifyTradingSession::new(1708740000).killzone(), None);                        // Saturday
+    /// ```
+    pub fn killzone(&self) -> Option<Killzone> {
+        let local_timestamp = self.local_timestamp();
+        if self.market == Market::Forex && is_weekend(local_timestamp) {
+            return None;
+        }
+
+        killzone_for_seconds(seconds_of_day(local_timestamp))
+    }
+
+    /// Returns whether a Unix timestamp in seconds, shifted by the UTC offset, falls on the weekend of
+    /// the configured schedule, or on the Forex weekend without one.
+    fn is_weekend_at(&self, local_timestamp: i64) -> bool {
+        #[cfg(feature = "alloc")]
+        if let Some(schedule) = &self.schedule {
+            return schedule.is_weekend(local_timestamp);
+        }
+        is_weekend(local_timestamp)
+    }
+
+    /// Returns the stored Unix timestamp shifted by the fixed UTC offset, if any.
+    fn local_timestamp(&self) -> i64 {
+        self.unix_timestamp.saturating_add(i64::from(self.utc_offset))
+    }
+
+    /// Identifies the trading session along with the sessions right before and after it.
+    ///
+    /// The previous and next sessions are the neighbouring sessions on the daily schedule, i.e. the
+    /// session just before the current one opened and the session right after it closes.
+    ///
+    /// # Returns
+    ///
+    /// A SessionContext with the previous, current and next trading session.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{IdentifyTradingSession, SessionContext, TradingSession};
+    ///
+    /// // Thursday 22 February 2024, 10:00 UTC
+    /// let context = IdentifyTradingSession::new(1708596000).context();
+    /// assert_eq!(context, SessionContext {
+    ///     prev: TradingSession::TokyoLondon,
+    ///     current: TradingSession::London,
+    ///     next: TradingSession::LondonNewYork,
+    /// });
+    ///
+    /// // Thursday 22 February 2024, 04:00 UTC, the first session of the day
+    /// let context = IdentifyTradingSession::new(1708574400).context();
+    /// assert_eq!((context.prev, context.next), (TradingSession::Undefined, TradingSession::TokyoLondon));
+    /// ```
+    pub fn context(&self) -> SessionContext {
+        let seconds_of_day = seconds_of_day(self.local_timestamp());
+        let (before, after) = self.with_windows(self.unix_timestamp, |windows| neighbouring_seconds(windows, seconds_of_day));
+        SessionContext {
+            prev: self.daily_session(self.unix_timestamp, before),
+            current: self.trading_session(),
+            next: self.daily_session(self.unix_timestamp, after),
+        }
+    }
+
+    /// Returns the session following the one containing the stored Unix timestamp, along with the
+    /// Unix timestamp in seconds at which it starts.
+    ///
+    /// The transition honors the weekend as well as the configured schedule, market, exchange,
+    /// holidays and daylight saving time, so e.g. the session after Friday's New York session is the Weekend.
+    ///
+    /// # Returns
+    ///
+    /// A `(session, timestamp)` tuple, or `None` if the session doesn't change within the next two
+    /// weeks, e.g. for a custom schedule covering the whole day of a Crypto market.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{IdentifyTradingSession, TradingSession};
+    ///
+    /// // Thursday 22 February 2024, 10:00 UTC, London until 13:00 UTC
+    /// let session_identifier = IdentifyTradingSession::new(1708596000);
+    /// assert_eq!(session_identifier.next_session(), Some((TradingSession::LondonNewYork, 1708606800)));
+    ///
+    /// // Friday 23 February 2024, 21:00 UTC, New York until the weekend starts at 22:00 UTC
+    /// let session_identifier = IdentifyTradingSession::new(1708722000);
+    /// assert_eq!(session_identifier.next_session(), Some((TradingSession::Weekend, 1708725600)));
+    ///
+    /// // Sunday 15:30 UTC at the end of time, the week never opens again
+    /// assert_eq!(IdentifyTradingSession::new(i64::MAX).next_session(), None);
+    /// ```
+    pub fn next_session(&self) -> Option<(TradingSession, i64)> {
+        self.next_session_at(self.unix_timestamp)
+    }
+
+    /// Returns the session following the one containing another Unix timestamp under the same configuration.
+    fn next_session_at(&self, unix_timestamp: i64) -> Option<(TradingSession, i64)> {
+        let current = self.trading_session_at(unix_timestamp);
+        let mut boundary = unix_timestamp;
+        while boundary - unix_timestamp < NAVIGATION_HORIZON && boundary < i64::MAX {
+            boundary = self.next_boundary(boundary);
+            let session = self.trading_session_at(boundary);
+            if session != current {
+                return Some((session, boundary));
+            }
+        }
+        None
+    }
+
+    /// Iterates over the trading session intervals from the stored Unix timestamp up to `end_ts`,
+    /// under the configuration of the identifier.
+    ///
+    /// # Arguments
+    ///
+    /// * `end_ts` - The Unix timestamp in seconds at which the range ends, exclusive.
+    ///
+    /// # Returns
+    ///
+    /// A [`SessionIntervals`] iterator, which is empty if `end_ts` is not after the stored timestamp.
+    pub fn sessions_until(self, end_ts: i64) -> SessionIntervals {
+        SessionIntervals { next: self.unix_timestamp, end: end_ts, identifier: self }
+    }
+
+    /// Identifies the trading session covering most of a bar from the stored Unix timestamp up to `end_ts`,
+    /// under the configuration of the identifier.
+    ///
+    /// Unlike the session of the open time alone, this classifies bars spanning a session boundary,
+    /// e.g. 4h candles, by the session they mostly trade in. Ties go to the session reached first.
+    ///
+    /// # Arguments
+    ///
+    /// * `end_ts` - The Unix timestamp in seconds at which the bar closes, exclusive.
+    ///
+    /// # Returns
+    ///
+    /// The dominant trading session, or the session of the stored timestamp if `end_ts` is not after it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{IdentifyTradingSession, TradingSession};
+    ///
+    /// // A 4h bar from 12:00 to 16:00 UTC on Thursday 22 February 2024, one hour in London and three in London_NewYork
+    /// let session_identifier = IdentifyTradingSession::new(1708603200);
+    /// assert_eq!(session_identifier.trading_session(), TradingSession::London);
+    /// assert_eq!(session_identifier.dominant_session_until(1708617600), TradingSession::LondonNewYork);
+    /// ```
+    pub fn dominant_session_until(self, end_ts: i64) -> TradingSession {
+        let open = self.trading_session();
+        // The seconds within each session, and the order in which the sessions are reached, indexed by their ID
+        let (mut seconds, mut reached) = ([0i64; TradingSession::ALL.len()], [usize::MAX; TradingSession::ALL.len()]);
+        for (order, (session, start, end)) in self.sessions_until(end_ts).enumerate() {
+            let id = usize::from(session.id());
+            seconds[id] += end - start;
+            reached[id] = reached[id].min(order);
+        }
+
+        TradingSession::ALL
+            .iter()
+            .filter(|session| reached[usize::from(session.id())] != usize::MAX)
+            .max_by_key(|session| (seconds[usize::from(session.id())], core::cmp::Reverse(reached[usize::from(session.id())])))
+            .copied()
+            .unwrap_or(open)
+    }
+
+    /// Lists the trading sessions a bar from the stored Unix timestamp up to `end_ts` touches, under
+    /// the configuration of the identifier.
+    ///
+    /// # Arguments
+    ///
+    /// * `end_ts` - The Unix timestamp in seconds at which the bar closes, exclusive.
+    ///
+    /// # Returns
+    ///
+    /// The distinct trading sessions, ordered by their first occurrence, or only the session of the
+    /// stored timestamp if `end_ts` is not after it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{IdentifyTradingSession, TradingSession};
+    ///
+    /// // A 4h bar from 12:00 to 16:00 UTC on Thursday 22 February 2024
+    /// let session_identifier = IdentifyTradingSession::new(1708603200);
+    /// assert_eq!(session_identifier.sessions_touched_until(1708617600), [TradingSession::London, TradingSession::LondonNewYork]);
+    ///
+    /// // The close is exclusive, so a bar from 12:00 to 13:00 UTC stays within London
+    /// assert_eq!(IdentifyTradingSession::new(1708603200).sessions_touched_until(1708606800), [TradingSession::London]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn sessions_touched_until(self, end_ts: i64) -> Vec<TradingSession> {
+        let mut sessions = Vec::new();
+        if end_ts <= self.unix_timestamp {
+            sessions.push(self.trading_session());
+        }
+        for (session, _, _) in self.sessions_until(end_ts) {
+            if !sessions.contains(&session) {
+                sessions.push(session);
+            }
+        }
+        sessions
+    }
+
+    /// Returns the time left until the session containing the stored Unix timestamp ends.
+    ///
+    /// # Returns
+    ///
+    /// The time until [`IdentifyTradingSession::next_session`] starts, or `None` if the session
+    /// doesn't change within the next two weeks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use trading_sessions::IdentifyTradingSession;
+    ///
+    /// // Thursday 22 February 2024, 10:30 UTC, London until 13:00 UTC
+    /// let session_identifier = IdentifyTradingSession::new(1708597800);
+    /// assert_eq!(session_identifier.time_remaining_in_session(), Some(Duration::from_secs(9_000)));
+    /// ```
+    pub fn time_remaining_in_session(&self) -> Option<core::time::Duration> {
+        let (_, start) = self.next_session()?;
+        Some(core::time::Duration::from_secs((start - self.unix_timestamp) as u64))
+    }
+
+    /// Returns the time left until the given trading session next opens.
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - The trading session to count down to.
+    ///
+    /// # Returns
+    ///
+    /// A zero duration if the stored Unix timestamp already falls within the session, or `None` if
+    /// the session doesn't open within the next two weeks, e.g. `TradingSession::Holiday` without a calendar.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use trading_sessions::{IdentifyTradingSession, TradingSession};
+    ///
+    /// // Thursday 22 February 2024, 10:00 UTC
+    /// let session_identifier = IdentifyTradingSession::new(1708596000);
+    /// assert_eq!(session_identifier.time_until_session(TradingSession::NewYork), Some(Duration::from_secs(6 * 3600)));
+    /// assert_eq!(session_identifier.time_until_session(TradingSession::London), Some(Duration::ZERO));
+    ///
+    /// // Friday 23 February 2024, 10:00 UTC, Tokyo opens again on Monday 00:00 UTC
+    /// let session_identifier = IdentifyTradingSession::new(1708682400);
+    /// assert_eq!(session_identifier.time_until_session(TradingSession::Tokyo), Some(Duration::from_secs(62 * 3600)));
+    ///
+    /// assert_eq!(session_identifier.time_until_session(TradingSession::Holiday), None);
+    /// ```
+    pub fn time_until_session(&self, session: TradingSession) -> Option<core::time::Duration> {
+        let mut current = (self.trading_session(), self.unix_timestamp);
+        while current.1 - self.unix_timestamp < NAVIGATION_HORIZON {
+            if current.0 == session {
+                return Some(core::time::Duration::from_secs((current.1 - self.unix_timestamp) as u64));
+            }
+            current = self.next_session_at(current.1)?;
+        }
+        None
+    }
+
+    /// Returns the session preceding the one containing the stored Unix timestamp, along with the
+    /// Unix timestamp in seconds at which the current session started.
+    ///
+    /// Like [`IdentifyTradingSession::next_session`], the transition honors the weekend and the configuration.
+    ///
+    /// # Returns
+    ///
+    /// A `(session, timestamp)` tuple, or `None` if the session didn't change within the last two weeks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{IdentifyTradingSession, TradingSession};
+    ///
+    /// // Thursday 22 February 2024, 10:00 UTC, London since 09:00 UTC
+    /// let session_identifier = IdentifyTradingSession::new(1708596000);
+    /// assert_eq!(session_identifier.previous_session(), Some((TradingSession::TokyoLondon, 1708592400)));
+    ///
+    /// // Saturday 24 February 2024, 02:00 UTC, the weekend since Friday 22:00 UTC
+    /// let session_identifier = IdentifyTradingSession::new(1708740000);
+    /// assert_eq!(session_identifier.previous_session(), Some((TradingSession::NewYork, 1708725600)));
+    ///
+    /// // Sunday 08:29 UTC at the start of time, the weekend has always been running
+    /// assert_eq!(IdentifyTradingSession::new(i64::MIN).previous_session(), None);
+    /// ```
+    pub fn previous_session(&self) -> Option<(TradingSession, i64)> {
+        let current = self.trading_session();
+        let mut boundary = self.unix_timestamp.saturating_add(1);
+        while self.unix_timestamp - boundary < NAVIGATION_HORIZON && boundary > i64::MIN {
+            boundary = self.previous_boundary(boundary);
+            let session = self.trading_session_at(boundary.saturating_sub(1));
+            if session != current {
+                return Some((session, boundary));
+            }
+        }
+        None
+    }
+
+    /// Returns the earliest instant after the given Unix timestamp at which the session may change.
+    fn next_boundary(&self, unix_timestamp: i64) -> i64 {
+        let local_timestamp = unix_timestamp.saturating_add(i64::from(self.utc_offset));
+        let seconds_of_day = seconds_of_day(local_timestamp);
+        let mut next = SECONDS_PER_DAY;
+        self.for_each_boundary(unix_timestamp, |boundary| {
+            if boundary > seconds_of_day {
+                next = next.min(boundary);
+            }
+        });
+        unix_timestamp.saturating_add(i64::from(next) - i64::from(seconds_of_day))
+    }
+
+    /// Returns the latest instant before the given Unix timestamp at which the session may have changed.
+    fn previous_boundary(&self, unix_timestamp: i64) -> i64 {
+        let unix_timestamp = unix_timestamp.saturating_sub(1);
+        let local_timestamp = unix_timestamp.saturating_add(i64::from(self.utc_offset));
+        let seconds_of_day = seconds_of_day(local_timestamp);
+        let mut previous = 0;
+        self.for_each_boundary(unix_timestamp, |boundary| {
+            if boundary <= seconds_of_day {
+                previous = previous.max(boundary);
+            }
+        });
+        unix_timestamp.saturating_add(i64::from(previous) - i64::from(seconds_of_day))
+    }
+
+    /// Calls `f` with every number of seconds since local midnight at which the session may change
+    /// on the day of the given Unix timestamp, in no particular order.
+    fn for_each_boundary(&self, unix_timestamp: i64, mut f: impl FnMut(u32)) {
+        // The exchange hours and holidays are defined in UTC rather than in the local time of the offset
+        let local = |utc_seconds: u32| (i64::from(utc_seconds) + i64::from(self.utc_offset)).rem_euclid(i64::from(SECONDS_PER_DAY)) as u32;
+
+        self.with_windows(unix_timestamp, |windows| {
+            windows.iter().for_each(|window| {
+                f(window.start);
+                f(window.end % SECONDS_PER_DAY);
+            })
+        });
+        f(SECONDS_PER_DAY - WEEKEND_SHIFT as u32);
+        #[cfg(feature = "alloc")]
+        if let Some((start, end)) = self.schedule.as_ref().and_then(|schedule| schedule.weekend) {
+            f(start % SECONDS_PER_DAY);
+            f(end % SECONDS_PER_DAY);
+        }
+
+        if let Some(exchange) = self.exchange {
+            exchange.trading_hours().iter().for_each(|&(start, end)| {
+                f(local(start % SECONDS_PER_DAY));
+                f(local(end % SECONDS_PER_DAY));
+            });
+        }
+
+        #[cfg(feature = "alloc")]
+        if let Some(calendar) = &self.holidays {
+            f(local(0));
+            calendar.early_closes.iter().for_each(|early_close| f(local(u32::from(early_close.close_hour) * SECONDS_PER_HOUR)));
+            calendar.late_opens.iter().for_each(|late_open| f(local(u32::from(late_open.open_hour) * SECONDS_PER_HOUR)));
+        }
+    }
+
+    /// Maps a number of seconds since midnight UTC to the session of the configured schedule and market,
+    /// with daylight saving time as observed at the given Unix timestamp in seconds.
+    fn daily_session(&self, unix_timestamp: i64, seconds_of_day: u32) -> TradingSession {
+        #[cfg(feature = "alloc")]
+        if let Some(schedule) = &self.schedule {
+            return schedule.session_for_seconds(seconds_of_day);
+        }
+
+        let session = match self.dst {
+            true => window_for_seconds(&summer_time_windows(unix_timestamp), seconds_of_day)
+                .map_or(TradingSession::Undefined, |window| window.session),
+            false => session_for_seconds(seconds_of_day),
+        };
+
+        match (self.market, session) {
+            // Crypto markets trade around the clock, so the hours after New York are folded into Tokyo
+            (Market::Crypto, TradingSession::Undefined) => TradingSession::Tokyo,
+            _ => session,
+        }
+    }
+
+    /// Calls `f` with the session windows of the configured schedule, or the default windows if none
+    /// is set, shifted for daylight saving time as observed at the given Unix timestamp if enabled.
+    fn with_windows<R>(&self, unix_timestamp: i64, f: impl FnOnce(&[SessionWindow]) -> R) -> R {
+        #[cfg(feature = "alloc")]
+        if let Some(schedule) = &self.schedule {
+            return f(&schedule.windows);
+        }
+
+        match self.dst {
+            true => f(&summer_time_windows(unix_timestamp)),
+            false => f(&FOREX_WINDOWS),
+        }
+    }
+
+    /// Computes how far through its trading session the stored Unix timestamp is.
+    ///
+    /// The progress is the number of seconds elapsed since the session opened divided by the length
+    /// of the session, so it is 0.0 exactly at the open and just under 1.0 on the last second before
+    /// the close. The session bounds follow the schedule, or the default session boundaries if none is set.
+    ///
+    /// # Returns
+    ///
+    /// The progress in `[0.0, 1.0)`, or `None` if the timestamp is not within a scheduled session,
+    /// e.g. when it is identified as `TradingSession::Undefined` or `TradingSession::Weekend`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::IdentifyTradingSession;
+    ///
+    /// // Thursday 22 February 2024, London session from 09:00 to 13:00 UTC
+    /// assert_eq!(IdentifyTradingSession::new(1708592400).session_progress(), Some(0.0)); // 09:00:00
+    /// assert_eq!(IdentifyTradingSession::new(1708599600).session_progress(), Some(0.5)); // 11:00:00
+    ///
+    /// let near_close = IdentifyTradingSession::new(1708606799).session_progress().unwrap(); // 12:59:59
+    /// assert!(near_close > 0.999 && near_close < 1.0);
+    ///
+    /// // Thursday 22 February 2024, 23:00 UTC
+    /// assert_eq!(IdentifyTradingSession::new(1708642800).session_progress(), None);
+    /// ```
+    pub fn session_progress(&self) -> Option<f64> {
+        let window = self.session_window()?;
+        let elapsed = (seconds_of_day(self.local_timestamp()) + SECONDS_PER_DAY - window.start) % SECONDS_PER_DAY;
+        Some(f64::from(elapsed) / f64::from(window.duration_secs()))
+    }
+
+    /// Returns the UTC start and end Unix timestamps of the trading session containing the stored timestamp.
+    ///
+    /// The session bounds follow the schedule, or the default session boundaries if none is set.
+    /// Sessions that wrap around midnight UTC, e.g. 22:00 - 06:00, start on the previous day or end
+    /// on the next day as needed, so the bounds can be used directly to draw session boxes on a chart.
+    ///
+    /// # Returns
+    ///
+    /// A `(start, end)` tuple, where the end is exclusive, or `None` if the timestamp is not within
+    /// a scheduled session, e.g. when it is identified as `TradingSession::Undefined` or `TradingSession::Weekend`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{IdentifyTradingSession, SessionSchedule, TradingSession};
+    ///
+    /// // Thursday 22 February 2024, 10:00 UTC, London session from 09:00 to 13:00 UTC
+    /// assert_eq!(IdentifyTradingSession::new(1708596000).session_bounds(), Some((1708592400, 1708606800)));
+    ///
+    /// // Thursday 22 February 2024, 03:00 UTC, Sydney session from 22:00 the day before to 06:00 UTC
+    /// let schedule = SessionSchedule::new().add(TradingSession::Sydney, 79_200, 21_600);
+    /// let session_identifier = IdentifyTradingSession::new(1708570800).with_schedule(schedule);
+    /// assert_eq!(session_identifier.session_bounds(), Some((1708552800, 1708581600)));
+    ///
+    /// // Saturday 24 February 2024, 02:00 UTC
+    /// assert_eq!(IdentifyTradingSession::new(1708740000).session_bounds(), None);
+    ///
+    /// // Thursday 15:30 UTC near the end of time, London_NewYork session from 13:00 to 16:00 UTC
+    /// let session_identifier = IdentifyTradingSession::new(i64::MAX - 3 * 86_400);
+    /// assert_eq!(session_identifier.session_bounds(), Some((i64::MAX - 3 * 86_400 - 9_007, i64::MAX - 3 * 86_400 + 1_793)));
+    /// ```
+    pub fn session_bounds(&self) -> Option<(i64, i64)> {
+        let window = self.session_window()?;
+        let local_timestamp = self.local_timestamp();
+        let seconds_of_day = seconds_of_day(local_timestamp);
+        let day_start = local_timestamp - i64::from(seconds_of_day);
+        let (start, end) = (i64::from(window.start), i64::from(window.end));
+        let day = i64::from(SECONDS_PER_DAY);
+
+        let (start, end) = match (window.start <= window.end, seconds_of_day >= window.start) {
+            (true, _) => (day_start.saturating_add(start), day_start.saturating_add(end)),
+            // The window wraps around midnight, so either its end is on the next day or its start on the previous one
+            (false, true) => (day_start.saturating_add(start), day_start.saturating_add(day + end)),
+            (false, false) => (day_start.saturating_add(start - day), day_start.saturating_add(end)),
+        };
+
+        let offset = i64::from(self.utc_offset);
+        Some((start.saturating_sub(offset), end.saturating_sub(offset)))
+    }
+
+    /// Returns when a trading session opens and closes on a UTC date, under the configuration of the
+    /// identifier, i.e. its DST, weekend, holidays, early closes and late opens. The stored timestamp is ignored.
+    ///
+    /// The occurrence is the one opening on the date, so a session wrapping around midnight UTC
+    /// closes on the next day. If the session opens more than once on the date, the first
+    /// occurrence is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - The trading session to look up.
+    /// * `date` - The `(year, month, day)` UTC date, where the month and day start at 1.
+    ///
+    /// # Returns
+    ///
+    /// The `(open, close)` Unix timestamps in seconds, close exclusive, or `None` if the session
+    /// does not open on the date, e.g. during the weekend or on a holiday.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{HolidayCalendar, IdentifyTradingSession, TradingSession};
+    ///
+    /// // Monday 11 March 2024, London from 09:00 to 13:00 UTC
+    /// let identifier = IdentifyTradingSession::new(0);
+    /// assert_eq!(identifier.bounds_on(TradingSession::London, (2024, 3, 11)), Some((1710147600, 1710162000)));
+    ///
+    /// // New York observes DST since 10 March, so London_NewYork starts an hour earlier
+    /// let identifier = IdentifyTradingSession::new(0).with_dst();
+    /// assert_eq!(identifier.bounds_on(TradingSession::London, (2024, 3, 11)), Some((1710147600, 1710158400)));
+    ///
+    /// // Saturday 9 March 2024 and Christmas Day
+    /// assert_eq!(identifier.bounds_on(TradingSession::London, (2024, 3, 9)), None);
+    /// let identifier = IdentifyTradingSession::new(0).with_holidays(HolidayCalendar::new(&[(2024, 12, 25)]));
+    /// assert_eq!(identifier.bounds_on(TradingSession::London, (2024, 12, 25)), None);
+    /// ```
+    pub fn bounds_on(&self, session: TradingSession, date: (i32, u32, u32)) -> Option<(i64, i64)> {
+        let (year, month, day) = date;
+        let (midnight, day) = (days_from_civil(year, month, day) * i64::from(SECONDS_PER_DAY), i64::from(SECONDS_PER_DAY));
+        let identifier = Self { unix_timestamp: midnight, ..self.clone() };
+
+        // A session already running at midnight opened on the previous day
+        let running_at_midnight = identifier.trading_session_at(midnight - 1) == session;
+        identifier
+            .sessions_until(midnight + 2 * day)
+            .take_while(|(_, start, _)| *start < midnight + day)
+            .filter(|(_, start, _)| *start > midnight || !running_at_midnight)
+            .find(|(interval_session, _, _)| *interval_session == session)
+            .map(|(_, start, end)| (start, end))
+    }
+
+    /// Returns the start and end of the trading session containing the stored timestamp as wall-clock
+    /// datetimes in a target timezone, e.g. to show "London opens 10:00 your time".
+    ///
+    /// Requires the `chrono` feature. Any `chrono::TimeZone` works, e.g. `Utc`, a `FixedOffset`, or a
+    /// `chrono_tz::Tz` for IANA timezones with daylight saving time.
+    ///
+    /// # Arguments
+    ///
+    /// * `tz` - The timezone to express the session bounds in.
+    ///
+    /// # Returns
+    ///
+    /// The bounds of [`IdentifyTradingSession::session_bounds`] in the timezone, or `None` if the
+    /// timestamp is not within a scheduled session.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::{FixedOffset, Timelike};
+    /// use trading_sessions::IdentifyTradingSession;
+    ///
+    /// // Thursday 22 February 2024, 10:00 UTC, London session from 09:00 to 13:00 UTC
+    /// let central_european_time = FixedOffset::east_opt(3600).unwrap();
+    /// let (open, close) = IdentifyTradingSession::new(1708596000).session_bounds_in(&central_european_time).unwrap();
+    /// assert_eq!((open.hour(), close.hour()), (10, 14));
+    /// assert_eq!(open.timestamp(), 1708592400);
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn session_bounds_in<Tz: chrono::TimeZone>(&self, tz: &Tz) -> Option<(chrono::DateTime<Tz>, chrono::DateTime<Tz>)> {
+        let (start, end) = self.session_bounds()?;
+        let to_datetime = |unix_timestamp| chrono::DateTime::from_timestamp(unix_timestamp, 0).map(|dt| dt.with_timezone(tz));
+        Some((to_datetime(start)?, to_datetime(end)?))
+    }
+
+    /// Returns the window of the scheduled trading session the stored Unix timestamp falls in, if any.
+    fn session_window(&self) -> Option<SessionWindow> {
+        if matches!(self.trading_session(),
+                    TradingSession::Undefined | TradingSession::Weekend | TradingSession::Closed | TradingSession::Holiday) {
+            return None;
+        }
+
+        let seconds_of_day = seconds_of_day(self.local_timestamp());
+        self.with_windows(self.unix_timestamp, |windows| window_for_seconds(windows, seconds_of_day).copied())
+            .filter(|window| window.session != TradingSession::Undefined)
+    }
+
+    /// Identifies the trading session along with the calendar date of the stored Unix timestamp.
+    ///
+    /// The date is the UTC date, or the local date when an offset was given with
+    /// [`IdentifyTradingSession::with_utc_offset`].
+    ///
+    /// # Returns
+    ///
+    /// A `(year, month, day, session)` tuple, where the month and day start at 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{IdentifyTradingSession, TradingSession};
+    ///
+    /// let session_identifier = IdentifyTradingSession::new(1708596000);
+    /// assert_eq!(session_identifier.date_and_session(), (2024, 2, 22, TradingSession::London));
+    ///
+    /// // Thursday 29 February 2024, 00:00 UTC
+    /// let session_identifier = IdentifyTradingSession::new(1709164800);
+    /// assert_eq!(session_identifier.date_and_session(), (2024, 2, 29, TradingSession::Tokyo));
+    ///
+    /// // Thursday 22 February 2024, 23:00 UTC is already Friday at UTC+1
+    /// let session_identifier = IdentifyTradingSession::with_utc_offset(1708642800, 3600);
+    /// assert_eq!(session_identifier.date_and_session(), (2024, 2, 23, TradingSession::Tokyo));
+    /// ```
+    pub fn date_and_session(&self) -> (i32, u32, u32, TradingSession) {
+        let days = self.local_timestamp().div_euclid(i64::from(SECONDS_PER_DAY));
+        let (year, month, day) = civil_from_days(days);
+        (year, month, day, self.trading_session())
+    }
+
+    /// Determines the Forex trading day of the timestamp, which begins at 17:00 New York time.
+    ///
+    /// The trading day rolls over at 22:00 UTC, or at 21:00 UTC while the US observes daylight saving
+    /// time, so the Sydney and Tokyo sessions belong to the same day as the London and New York
+    /// sessions that follow them. This is the day to aggregate daily bars by, rather than the UTC
+    /// calendar day, which splits the Asian session.
+    ///
+    /// # Returns
+    ///
+    /// A `(year, month, day)` tuple of the trading date, where the month and day start at 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::IdentifyTradingSession;
+    ///
+    /// // Thursday 22 February 2024, 21:59 and 22:00 UTC
+    /// assert_eq!(IdentifyTradingSession::new(1708639140).trading_day(), (2024, 2, 22));
+    /// assert_eq!(IdentifyTradingSession::new(1708639200).trading_day(), (2024, 2, 23));
+    ///
+    /// // Wednesday 10 July 2024, 21:00 UTC, i.e. 17:00 EDT
+    /// assert_eq!(IdentifyTradingSession::new(1720645200).trading_day(), (2024, 7, 11));
+    /// ```
+    pub fn trading_day(&self) -> (i32, u32, u32) {
+        civil_from_days(trading_day_number(self.unix_timestamp))
+    }
+
+    /// Lists each trading session with its absolute start and end timestamps within a 24-hour window.
+    ///
+    /// The windows follow the default session boundaries in UTC and tile the day without gaps or
+    /// overlaps, starting at `day_start_ts`. The weekend is not taken into account, so the sessions
+    /// are listed as they would be on a weekday.
+    ///
+    /// # Arguments
+    ///
+    /// * `day_start_ts` - The Unix timestamp in seconds at which the 24-hour window starts, usually midnight UTC.
+    ///
+    /// # Returns
+    ///
+    /// A vector of `(session, start, end)` tuples, where each window covers the timestamps in `[start, end)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{IdentifyTradingSession, TradingSession};
+    ///
+    /// // Thursday 22 February 2024, 00:00 UTC
+    /// let windows = IdentifyTradingSession::sessions_for_day(1708560000);
+    ///
+    /// assert_eq!(windows[0], (TradingSession::Tokyo, 1708560000, 1708585200));
+    /// assert_eq!(windows.last().unwrap().0, TradingSession::Undefined);
+    ///
+    /// let covered: i64 = windows.iter().map(|(_, start, end)| end - start).sum();
+    /// assert_eq!(covered, 86_400);
+    /// assert!(windows.windows(2).all(|pair| pair[0].2 == pair[1].1));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn sessions_for_day(day_start_ts: i64) -> Vec<(TradingSession, i64, i64)> {
+        let day_end = day_start_ts.saturating_add(i64::from(SECONDS_PER_DAY));
+        let mut windows = Vec::new();
+        let mut start = day_start_ts;
+
+        while start < day_end {
+            let seconds_of_day = seconds_of_day(start);
+            let next_boundary = SESSION_BOUNDARIES
+                .iter()
+                .map(|(_, hours)| boundary_seconds(hours).end)
+                .filter(|&end| end > seconds_of_day)
+                .min()
+                .unwrap_or(SECONDS_PER_DAY);
+            let end = start.saturating_add(i64::from(next_boundary - seconds_of_day)).min(day_end);
+
+            windows.push((session_for_seconds(seconds_of_day), start, end));
+            start = end;
+        }
+
+        windows
+    }
+
+    /// Returns the first and last second timestamps of the given session within a 24-hour window.
+    ///
+    /// # Arguments
+    ///
+    /// * `day_start_ts` - The Unix timestamp in seconds at which the 24-hour window starts, usually midnight UTC.
+    /// * `session` - The trading session to look up.
+    ///
+    /// # Returns
+    ///
+    /// The inclusive `(first, last)` timestamps that map to the session, or `None` if the session
+    /// does not occur within the window, e.g. for `TradingSession::Weekend`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{IdentifyTradingSession, TradingSession};
+    ///
+    /// // Thursday 22 February 2024, 00:00 UTC
+    /// let extent = IdentifyTradingSession::session_extent(1708560000, TradingSession::London);
+    /// assert_eq!(extent, Some((1708592400, 1708606799))); // 09:00:00 - 12:59:59
+    ///
+    /// assert_eq!(IdentifyTradingSession::session_extent(1708560000, TradingSession::Weekend), None);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn session_extent(day_start_ts: i64, session: TradingSession) -> Option<(i64, i64)> {
+        let windows = Self::sessions_for_day(day_start_ts);
+        let mut matching = windows.iter().filter(|window| window.0 == session);
+
+        let first = matching.next()?;
+        let last = matching.next_back().unwrap_or(first);
+        Some((first.1, last.2 - 1))
+    }
+}
+
+
+
+/// Requires the `chrono` feature.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use trading_sessions::IdentifyTradingSession;
+///
+/// let session_identifier: IdentifyTradingSession = Utc.with_ymd_and_hms(2024, 2, 22, 4, 0, 0).unwrap().into();
+/// assert_eq!(session_identifier.identify_trading_session(), "Tokyo");
+/// ```
+#[cfg(feature = "chrono")]
+impl From<chrono::DateTime<chrono::Utc>> for IdentifyTradingSession {
+    fn from(dt: chrono::DateTime<chrono::Utc>) -> Self {
+        Self::from_datetime(dt)
+    }
+}
+
+
+impl IntoUnixSeconds for i64 {
+    fn into_unix_seconds(self) -> i64 {
+        self
+    }
+}
+
+impl IntoUnixSeconds for i32 {
+    fn into_unix_seconds(self) -> i64 {
+        i64::from(self)
+    }
+}
+
+impl IntoUnixSeconds for u32 {
+    fn into_unix_seconds(self) -> i64 {
+        i64::from(self)
+    }
+}
+
+impl IntoUnixSeconds for u64 {
+    fn into_unix_seconds(self) -> i64 {
+        i64::try_from(self).unwrap_or(i64::MAX)
+    }
+}
+
+impl IntoUnixSeconds for f64 {
+    fn into_unix_seconds(self) -> i64 {
+        // Out of range timestamps saturate with `as`, which maps NaN to 0
+        IdentifyTradingSession::from_f64(self).map_or(self as i64, |session_identifier| session_identifier.unix_timestamp)
+    }
+}
+
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now(&self) -> std::time::SystemTime {
+        std::time::SystemTime::now()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Clock for std::time::SystemTime {
+    fn now(&self) -> std::time::SystemTime {
+        *self
+    }
+}
+
+
+#[cfg(feature = "std")]
+impl SessionClock {
+    /// Creates a session clock on the system clock, under the default configuration.
+    pub fn new() -> Self {
+        SessionClock {
+            clock: Box::new(SystemClock),
+            identifier: IdentifyTradingSession::new(0),
+            active: None,
+            on_open: Vec::new(),
+            on_close: Vec::new(),
+        }
+    }
+
+    /// Replaces the clock the session clock reads the time from, see [`Clock`].
+    pub fn with_clock(mut self, clock: impl Clock + Send + 'static) -> Self {
+        self.clock = Box::new(clock);
+        self
+    }
+
+    /// Identifies the sessions with the configuration of an identifier, e.g. a custom schedule or
+    /// holidays. The timestamp of the identifier is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{IdentifyTradingSession, Market, SessionClock};
+    ///
+    /// let clock = SessionClock::new().with_identifier(IdentifyTradingSession::new(0).with_market(Market::Crypto));
+    /// ```
+    pub fn with_identifier(mut self, identifier: IdentifyTradingSession) -> Self {
+        self.identifier = identifier;
+        self
+    }
+
+    /// Registers a callback for when a trading session opens.
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - The trading session to watch.
+    /// * `callback` - Called with the session and the Unix timestamp of the tick that saw it open.
+    pub fn on_session_open(mut self, session: TradingSession, callback: impl FnMut(TradingSession, i64) + Send + 'static) -> Self {
+        self.on_open.push((session, Box::new(callback)));
+        self
+    }
+
+    /// Registers a callback for when a trading session closes.
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - The trading session to watch.
+    /// * `callback` - Called with the session and the Unix timestamp of the tick that saw it close.
+    pub fn on_session_close(mut self, session: TradingSession, callback: impl FnMut(TradingSession, i64) + Send + 'static) -> Self {
+        self.on_close.push((session, Box::new(callback)));
+        self
+    }
+
+    /// Checks the sessions at the current time of the clock and calls the callbacks of the sessions
+    /// that closed or opened since the last tick, in that order.
+    pub fn tick(&mut self) {
+        let now = self.clock.now();
+        self.tick_at(now);
+    }
+
+    /// Checks the sessions at a given time instead of the time of the clock, see [`SessionClock::tick`].
+    pub fn tick_at(&mut self, now: std::time::SystemTime) {
+        let identifier = IdentifyTradingSession { unix_timestamp: clock_identifier(&now).unix_timestamp, ..self.identifier.clone() };
+        let session = identifier.trading_session();
+        let mut active = session.constituents().to_vec();
+        if !active.contains(&session) {
+            active.push(session);
+        }
+
+        if let Some(previous) = self.active.replace(active.clone()) {
+            for (watched, callback) in &mut self.on_close {
+                if previous.contains(watched) && !active.contains(watched) {
+                    callback(*watched, identifier.unix_timestamp);
+                }
+            }
+            for (watched, callback) in &mut self.on_open {
+                if !previous.contains(watched) && active.contains(watched) {
+                    callback(*watched, identifier.unix_timestamp);
+                }
+            }
+        }
+    }
+
+    /// Ticks the clock every `interval` until `stop` is set, blocking the calling thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    /// use trading_sessions::{SessionClock, TradingSession};
+    ///
+    /// let stop = Arc::new(AtomicBool::new(false));
+    /// let mut clock = SessionClock::new().on_session_open(TradingSession::NewYork, |_, _| println!("New York opened"));
+    ///
+    /// let thread_stop = stop.clone();
+    /// let handle = std::thread::spawn(move || clock.run(Duration::from_millis(10), &thread_stop));
+    /// stop.store(true, Ordering::Relaxed);
+    /// handle.join().unwrap();
+    /// ```
+    pub fn run(&mut self, interval: std::time::Duration, stop: &std::sync::atomic::AtomicBool) {
+        while !stop.load(std::sync::atomic::Ordering::Relaxed) {
+            self.tick();
+            std::thread::sleep(interval);
+        }
+    }
+}
+
+
+#[cfg(feature = "std")]
+impl Default for SessionClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+/// Requires the `chrono` feature. The naive datetime is interpreted as UTC.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use trading_sessions::IdentifyTradingSession;
+///
+/// let dt = NaiveDate::from_ymd_opt(2024, 2, 22).unwrap().and_hms_opt(10, 0, 0).unwrap();
+/// let session_identifier = IdentifyTradingSession::from(dt);
+/// assert_eq!(session_identifier.unix_timestamp, 1708596000);
+/// assert_eq!(session_identifier.identify_trading_session(), "London");
+/// ```
+#[cfg(feature = "chrono")]
+impl From<chrono::NaiveDateTime> for IdentifyTradingSession {
+    fn from(dt: chrono::NaiveDateTime) -> Self {
+        Self::from_datetime(dt.and_utc())
+    }
+}
+
+
+
+/// Converts a number of days since 1 January 1970 to a `(year, month, day)` date in the proleptic Gregorian calendar.
+///
+/// Based on Howard Hinnant's `civil_from_days` algorithm, which works in 400-year eras starting on 1 March.
+fn civil_from_days(days: i64) -> (i32, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year as i32, month, day)
+}
+
+
+
+/// Converts a `(year, month, day)` date in the proleptic Gregorian calendar to a number of days since 1 January 1970.
+///
+/// The inverse of [`civil_from_days`], based on Howard Hinnant's `days_from_civil` algorithm.
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let year = i64::from(year) - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let shifted_month = i64::from(if month > 2 { month - 3 } else { month + 9 });
+    let day_of_year = (153 * shifted_month + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+
+
+/// Identifies the trading session of a Unix timestamp in seconds, which may be negative.
+fn session_at(unix_timestamp: i64) -> TradingSession {
+    if is_weekend(unix_timestamp) {
+        return TradingSession::Weekend;
+    }
+
+    session_for_seconds(seconds_of_day(unix_timestamp))
+}
+
+
+/// Returns the number of seconds elapsed since midnight UTC for a Unix timestamp in seconds.
+pub(crate) fn seconds_of_day(unix_timestamp: i64) -> u32 {
+    unix_timestamp.rem_euclid(i64::from(SECONDS_PER_DAY)) as u32
+}
+
+
+/// Returns whether a Unix timestamp in seconds falls between Friday 22:00 and Sunday 22:00 UTC.
+fn is_weekend(unix_timestamp: i64) -> bool {
+    // Reducing to the week first keeps the day of the week and leaves room for the shift at the extremes of i64
+    let day_of_week = day_of_week(unix_timestamp.rem_euclid(SECONDS_PER_WEEK) + WEEKEND_SHIFT);
+    day_of_week == SUNDAY || day_of_week == SATURDAY
+}
+
+
+/// Returns the day of the week in UTC for a Unix timestamp in seconds, from Sunday = 0 to Saturday = 6.
+pub(crate) fn day_of_week(unix_timestamp: i64) -> u32 {
+    let days = unix_timestamp.div_euclid(i64::from(SECONDS_PER_DAY));
+
+    // 1 January 1970 was a Thursday, so shifting by 4 days maps Sunday to 0 and Saturday to 6
+    (days + 4).rem_euclid(i64::from(DAYS_PER_WEEK)) as u32
+}
+
+
+/// Maps an hour of the day in UTC to its trading session under the default schedule.
+///
+/// This is the single source of truth for the default sessions: it looks the hour up in
+/// [`SESSION_BOUNDARIES`], from which the `SessionColumn` expressions are built as well. Hours
+/// outside of the table, i.e. 24 and above, map to `TradingSession::Undefined`.
+///
+/// # Examples
+///
+/// ```
+/// use polars::prelude::*;
+/// use trading_sessions::{session_for_hour, IdentifyTradingSession, SessionColumn, TradingSession};
+///
+/// const LONDON_OPEN: TradingSession = session_for_hour(9);
+/// assert_eq!(LONDON_OPEN, TradingSession::London);
+///
+/// // Thursday 22 February 2024, every hour from 00:00 UTC
+/// let times: Vec<i64> = (0..24).map(|hour| 1708560000 + hour * 3600).collect();
+/// let df = df! { "time" => times.clone() }.unwrap();
+/// let result_df = SessionColumn::new(df.lazy()).apply_session_column().into_lazyframe().collect().unwrap();
+/// let labels = result_df.column("Session").unwrap().utf8().unwrap();
+///
+/// for (hour, (time, label)) in times.iter().zip(labels.into_iter()).enumerate() {
+///     let scalar = IdentifyTradingSession::new(*time).identify_trading_session();
+///     assert_eq!(label, Some(scalar.as_str()));
+///     assert_eq!(label, Some(session_for_hour(hour as u32).as_str()));
+/// }
+/// ```
+pub const fn session_for_hour(utc_hour: u32) -> TradingSession {
+    let mut index = 0;
+    while index < SESSION_BOUNDARIES.len() {
+        let (session, hours) = &SESSION_BOUNDARIES[index];
+        if *hours.start() as u32 <= utc_hour && utc_hour <= *hours.end() as u32 {
+            return *session;
+        }
+        index += 1;
+    }
+    TradingSession::Undefined
+}
+
+
+/// The trading session of each hour of the day in UTC under the default schedule, as mapped by [`session_for_hour`].
+///
+/// Kept as a lookup table so the scalar and batch paths don't walk [`SESSION_BOUNDARIES`] per timestamp.
+const HOUR_SESSIONS: [TradingSession; 24] = hour_sessions();
+
+
+/// Converts [`SESSION_BOUNDARIES`] to the trading session of each hour of the day.
+const fn hour_sessions() -> [TradingSession; 24] {
+    let mut sessions = [TradingSession::Undefined; 24];
+    let mut hour = 0;
+    while hour < sessions.len() {
+        sessions[hour] = session_for_hour(hour as u32);
+        hour += 1;
+    }
+    sessions
+}
+
+
+/// Maps a number of seconds since midnight UTC to its trading session under the default schedule.
+fn session_for_seconds(seconds_of_day: u32) -> TradingSession {
+    match HOUR_SESSIONS.get((seconds_of_day / SECONDS_PER_HOUR) as usize) {
+        Some(session) => *session,
+        None => TradingSession::Undefined,
+    }
+}
+
+
+/// Converts an inclusive range of hours to the half-open range of seconds since midnight it covers.
+#[cfg(feature = "alloc")]
+fn boundary_seconds(hours: &RangeInclusive<u8>) -> Range<u32> {
+    u32::from(*hours.start()) * SECONDS_PER_HOUR..(u32::from(*hours.end()) + 1) * SECONDS_PER_HOUR
+}
+
+
+/// Compares the crate's hour boundaries against an external reference table.
+///
+/// Each reference entry maps an hour of the day in UTC to the session label expected for that hour.
+/// Hours on which the crate agrees with the reference are skipped.
+///
+/// # Arguments
+///
+/// * `reference` - A slice of `(hour, label)` pairs, e.g. taken from an official session table.
+///
+/// # Returns
+///
+/// A vector of `(hour, crate_label, reference_label)` tuples, one for each hour on which the crate
+/// disagrees with the reference.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::compare_boundaries;
+///
+/// let reference = [(6, "Tokyo"), (7, "Tokyo"), (9, "London")];
+/// let disagreements = compare_boundaries(&reference);
+/// assert_eq!(disagreements, vec![(7, "Tokyo_London".to_string(), "Tokyo".to_string())]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn compare_boundaries(reference: &[(u32, &str)]) -> Vec<(u32, String, String)> {
+    reference
+        .iter()
+        .filter_map(|&(hour, reference_label)| {
+            let crate_label = session_for_hour(hour).as_str();
+            (crate_label != reference_label)
+                .then(|| (hour, crate_label.to_string(), reference_label.to_string()))
+        })
+        .collect()
+}
+
+
+/// Returns the length in seconds of the given trading session under the default schedule.
+///
+/// This is a shorthand for [`TradingSession::duration_secs`], e.g. to check how many bars a session
+/// of complete data has. Use [`SessionSchedule::duration_secs`] for a custom schedule.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{session_duration_secs, TradingSession};
+///
+/// // A complete Tokyo session has 420 one-minute bars
+/// assert_eq!(session_duration_secs(TradingSession::Tokyo) / 60, 420);
+/// assert_eq!(session_duration_secs(TradingSession::Weekend), 0);
+/// ```
+pub fn session_duration_secs(session: TradingSession) -> u32 {
+    session.duration_secs()
+}
+
+
+/// Computes the fraction of a time interval that falls within the given trading session.
+///
+/// The interval is split at every hour boundary, at which the default sessions change, and the
+/// seconds of the pieces within the session are summed. Weekends are taken into account, so
+/// intervals spanning several days count each occurrence of the session.
+///
+/// # Arguments
+///
+/// * `start` - The Unix timestamp in seconds at which the interval starts, inclusive.
+/// * `end` - The Unix timestamp in seconds at which the interval ends, exclusive.
+/// * `session` - The trading session to measure.
+///
+/// # Returns
+///
+/// The covered fraction in `[0.0, 1.0]`, or 0.0 for an empty interval.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{session_coverage, TradingSession};
+///
+/// // A 60-minute bar from 12:30 to 13:30 UTC on Thursday 22 February 2024
+/// assert_eq!(session_coverage(1708605000, 1708608600, TradingSession::London), 0.5);
+/// assert_eq!(session_coverage(1708605000, 1708608600, TradingSession::LondonNewYork), 0.5);
+/// assert_eq!(session_coverage(1708605000, 1708608600, TradingSession::Tokyo), 0.0);
+///
+/// // From 10:00 to 11:00 UTC, entirely within London
+/// assert_eq!(session_coverage(1708596000, 1708599600, TradingSession::London), 1.0);
+///
+/// // Monday 19 February 2024 through Sunday 25 February 2024, 7 days of which 5 weekdays
+/// let week = session_coverage(1708300800, 1708905600, TradingSession::London);
+/// assert!((week - 5.0 * 4.0 / (7.0 * 24.0)).abs() < 1e-12);
+///
+/// // The last half hour before the end of time falls on a Sunday
+/// assert_eq!(session_coverage(i64::MAX - 1800, i64::MAX, TradingSession::Weekend), 1.0);
+/// ```
+pub fn session_coverage(start: i64, end: i64, session: TradingSession) -> f64 {
+    if end <= start {
+        return 0.0;
+    }
+
+    let hour = i64::from(SECONDS_PER_HOUR);
+    let mut covered = 0;
+    let mut from = start;
+    while from < end {
+        let to = (from.div_euclid(hour) + 1).checked_mul(hour).map_or(end, |next_hour| next_hour.min(end));
+        if session_at(from) == session {
+            covered += to - from;
+        }
+        from = to;
+    }
+
+    covered as f64 / (end - start) as f64
+}
+
+
+/// Lists the distinct trading sessions a time range touches, in the order they are first crossed.
+///
+/// Both ends of the range are included, so `start_ts == end_ts` returns the single session at that
+/// instant, while `start_ts > end_ts` returns an empty vector. Ranges crossing midnight or spanning
+/// several days list each session only once, including the weekend.
+///
+/// # Arguments
+///
+/// * `start_ts` - The Unix timestamp in seconds at which the range starts, inclusive.
+/// * `end_ts` - The Unix timestamp in seconds at which the range ends, inclusive.
+///
+/// # Returns
+///
+/// A vector of the distinct sessions within the range, ordered by their first occurrence.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{sessions_in_range, TradingSession};
+///
+/// // Thursday 22 February 2024, 10:00 - 11:30 UTC, within London
+/// assert_eq!(sessions_in_range(1708596000, 1708601400), [TradingSession::London]);
+///
+/// // 12:30 - 13:30 UTC, crossing from London into London_NewYork
+/// assert_eq!(sessions_in_range(1708605000, 1708608600),
+///            [TradingSession::London, TradingSession::LondonNewYork]);
+///
+/// // Thursday 22 February 2024, 20:00 UTC through Saturday 24 February 2024, 01:00 UTC
+/// assert_eq!(sessions_in_range(1708632000, 1708736400), [
+///     TradingSession::NewYork,
+///     TradingSession::Undefined,
+///     TradingSession::Tokyo,
+///     TradingSession::TokyoLondon,
+///     TradingSession::London,
+///     TradingSession::LondonNewYork,
+///     TradingSession::Weekend,
+/// ]);
+///
+/// assert_eq!(sessions_in_range(1708596000, 1708596000), [TradingSession::London]);
+/// assert!(sessions_in_range(1708601400, 1708596000).is_empty());
+///
+/// // The last hour before the end of time falls on a Sunday
+/// assert_eq!(sessions_in_range(i64::MAX - 1800, i64::MAX), [TradingSession::Weekend]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn sessions_in_range(start_ts: i64, end_ts: i64) -> Vec<TradingSession> {
+    let (end, hour) = (end_ts, i64::from(SECONDS_PER_HOUR));
+    let mut sessions = Vec::new();
+    let mut from = start_ts;
+
+    // The default sessions only change on the hour, so each hour needs checking once
+    while from <= end {
+        let session = session_at(from);
+        if !sessions.contains(&session) {
+            sessions.push(session);
+        }
+        match (from.div_euclid(hour) + 1).checked_mul(hour) {
+            Some(next_hour) => from = next_hour,
+            None => break,
+        }
+    }
+
+    sessions
+}
+
+
+/// Iterates over the trading session intervals within a range of Unix timestamps under the default configuration.
+///
+/// # Arguments
+///
+/// * `start_ts` - The Unix timestamp in seconds at which the range starts, inclusive.
+/// * `end_ts` - The Unix timestamp in seconds at which the range ends, exclusive.
+///
+/// # Returns
+///
+/// A [`SessionIntervals`] iterator, see [`IdentifyTradingSession::sessions_until`] for custom configurations.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{sessions_between, TradingSession};
+///
+/// // Thursday 22 February 2024, 00:00 UTC through Friday 1 March 2024, 00:00 UTC
+/// let weekend: i64 = sessions_between(1708560000, 1709251200)
+///     .filter(|(session, _, _)| *session == TradingSession::Weekend)
+///     .map(|(_, start, end)| end - start)
+///     .sum();
+/// assert_eq!(weekend, 2 * 86_400);
+/// ```
+pub fn sessions_between(start_ts: i64, end_ts: i64) -> SessionIntervals {
+    IdentifyTradingSession::new(start_ts).sessions_until(end_ts)
+}
+
+
+/// Identifies all trading sessions active at a Unix timestamp in seconds, reporting an overlap as
+/// the two concurrent sessions it consists of.
+///
+/// # Arguments
+///
+/// * `unix_timestamp` - The Unix timestamp in seconds.
+///
+/// # Returns
+///
+/// The active trading sessions in chronological order, or an empty vector during the weekend and
+/// the hours outside of every session.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{active_sessions, TradingSession};
+///
+/// assert_eq!(active_sessions(1708588800), [TradingSession::Tokyo, TradingSession::London]); // 08:00 UTC
+/// assert_eq!(active_sessions(1708596000), [TradingSession::London]);                        // 10:00 UTC
+/// assert!(active_sessions(1708642800).is_empty());                                           // 23:00 UTC
+/// ```
+#[cfg(feature = "alloc")]
+pub fn active_sessions(unix_timestamp: i64) -> Vec<TradingSession> {
+    IdentifyTradingSession::new(unix_timestamp).active_sessions()
+}
+
+
+/// Identifies the ICT-style killzone of a Unix timestamp in seconds, see [`Killzone`].
+///
+/// # Arguments
+///
+/// * `unix_timestamp` - The Unix timestamp in seconds.
+///
+/// # Returns
+///
+/// The killzone, or `None` outside of every killzone and during the Forex weekend.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{identify_killzone, Killzone};
+///
+/// assert_eq!(identify_killzone(1708606800), Some(Killzone::NewYorkOpen)); // Thursday 22 February 2024, 13:00 UTC
+/// assert_eq!(identify_killzone(1708621200), None);                        // Thursday 22 February 2024, 17:00 UTC
+/// ```
+pub fn identify_killzone(unix_timestamp: i64) -> Option<Killzone> {
+    IdentifyTradingSession::new(unix_timestamp).killzone()
+}
+
+
+/// Computes how far through its trading session a Unix timestamp in seconds is, with the default Forex schedule.
+///
+/// # Arguments
+///
+/// * `unix_timestamp` - The Unix timestamp in seconds.
+///
+/// # Returns
+///
+/// The progress in `[0.0, 1.0)`, or `None` outside of every scheduled session, see
+/// [`IdentifyTradingSession::session_progress`].
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::session_progress;
+///
+/// assert_eq!(session_progress(1708599600), Some(0.5)); // Thursday 22 February 2024, 11:00 UTC
+/// assert_eq!(session_progress(1708740000), None);      // Saturday 24 February 2024, 02:00 UTC
+/// ```
+pub fn session_progress(unix_timestamp: i64) -> Option<f64> {
+    IdentifyTradingSession::new(unix_timestamp).session_progress()
+}
+
+
+/// Returns the start and end of the trading session containing a Unix timestamp as wall-clock datetimes in a target timezone.
+///
+/// Requires the `chrono` feature.
+///
+/// # Arguments
+///
+/// * `unix_timestamp` - The Unix timestamp in seconds.
+/// * `tz` - The timezone to express the session bounds in, see [`IdentifyTradingSession::session_bounds_in`].
+///
+/// # Returns
+///
+/// The session bounds, or `None` outside of every scheduled session.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{FixedOffset, Timelike};
+/// use trading_sessions::session_bounds_in;
+///
+/// // Thursday 22 February 2024, 04:00 UTC, Tokyo session from 00:00 to 07:00 UTC, i.e. 09:00 to 16:00 JST
+/// let japan_standard_time = FixedOffset::east_opt(9 * 3600).unwrap();
+/// let (open, close) = session_bounds_in(1708574400, &japan_standard_time).unwrap();
+/// assert_eq!((open.hour(), close.hour()), (9, 16));
+/// ```
+#[cfg(feature = "chrono")]
+pub fn session_bounds_in<Tz: chrono::TimeZone>(unix_timestamp: i64, tz: &Tz) -> Option<(chrono::DateTime<Tz>, chrono::DateTime<Tz>)> {
+    IdentifyTradingSession::new(unix_timestamp).session_bounds_in(tz)
+}
+
+
+/// Determines the Forex trading day of a Unix timestamp in seconds, see [`IdentifyTradingSession::trading_day`].
+///
+/// # Returns
+///
+/// A `(year, month, day)` tuple of the trading date, which begins at 17:00 New York time.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::trading_day;
+///
+/// // Sunday 25 February 2024, 22:00 UTC, when the Forex week opens
+/// assert_eq!(trading_day(1708898400), (2024, 2, 26));
+/// ```
+pub fn trading_day(unix_timestamp: i64) -> (i32, u32, u32) {
+    IdentifyTradingSession::new(unix_timestamp).trading_day()
+}
+
+
+/// Returns the number of days since 1 January 1970 of the Forex trading day of a Unix timestamp in seconds.
+fn trading_day_number(unix_timestamp: i64) -> i64 {
+    let rollover_offset = match is_new_york_summer_time(unix_timestamp) {
+        true => 3 * SECONDS_PER_HOUR,
+        false => 2 * SECONDS_PER_HOUR,
+    };
+    (unix_timestamp + i64::from(rollover_offset)).div_euclid(i64::from(SECONDS_PER_DAY))
+}
+
+
+/// Generates a SQL `CASE` expression classifying a timestamp column under the default Forex schedule, see [`SessionSchedule::to_sql_case`].
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{to_sql_case, SqlDialect};
+///
+/// let sql = to_sql_case("\"time\"", SqlDialect::Postgres);
+/// assert!(sql.starts_with("CASE WHEN ((CAST(FLOOR(EXTRACT(EPOCH FROM \"time\")) AS BIGINT) + 7200) % 604800"));
+/// assert!(sql.contains(" THEN 'London_NewYork' "));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn to_sql_case(time_col: &str, dialect: SqlDialect) -> String {
+    SessionSchedule::forex().to_sql_case(time_col, dialect)
+}
+
+
+/// Identifies the trading session covering most of an OHLC bar under the default configuration.
+///
+/// # Arguments
+///
+/// * `open_ts` - The Unix timestamp in seconds at which the bar opens, inclusive.
+/// * `close_ts` - The Unix timestamp in seconds at which the bar closes, exclusive, e.g. `open_ts + 4 * 3600` for 4h bars.
+///
+/// # Returns
+///
+/// The dominant trading session, see [`IdentifyTradingSession::dominant_session_until`].
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{dominant_session, TradingSession};
+///
+/// // A 4h bar from 08:00 to 12:00 UTC on Thursday 22 February 2024, one hour in Tokyo_London and three in London
+/// assert_eq!(dominant_session(1708588800, 1708588800 + 4 * 3600), TradingSession::London);
+/// ```
+pub fn dominant_session(open_ts: i64, close_ts: i64) -> TradingSession {
+    IdentifyTradingSession::new(open_ts).dominant_session_until(close_ts)
+}
+
+
+/// Lists the trading sessions an OHLC bar touches under the default configuration.
+///
+/// # Arguments
+///
+/// * `open_ts` - The Unix timestamp in seconds at which the bar opens, inclusive.
+/// * `close_ts` - The Unix timestamp in seconds at which the bar closes, exclusive.
+///
+/// # Returns
+///
+/// The distinct trading sessions, ordered by their first occurrence, see [`IdentifyTradingSession::sessions_touched_until`].
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{sessions_touched, TradingSession};
+///
+/// // A 4h bar from 08:00 to 12:00 UTC on Thursday 22 February 2024
+/// assert_eq!(sessions_touched(1708588800, 1708603200), [TradingSession::TokyoLondon, TradingSession::London]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn sessions_touched(open_ts: i64, close_ts: i64) -> Vec<TradingSession> {
+    IdentifyTradingSession::new(open_ts).sessions_touched_until(close_ts)
+}
+
+
+/// Maps a number of seconds since midnight UTC to its killzone, as listed in [`KILLZONE_BOUNDARIES`].
+fn killzone_for_seconds(seconds_of_day: u32) -> Option<Killzone> {
+    let hour = seconds_of_day / SECONDS_PER_HOUR;
+    KILLZONE_BOUNDARIES
+        .iter()
+        .find(|(_, hours)| u32::from(*hours.start()) <= hour && hour <= u32::from(*hours.end()))
+        .map(|(killzone, _)| *killzone)
+}
+
+
+/// Returns whether the Forex market is open at a Unix timestamp in seconds.
+///
+/// The market is closed for the weekend from Friday 22:00 to Sunday 22:00 UTC, and open otherwise.
+///
+/// # Arguments
+///
+/// * `unix_timestamp` - The Unix timestamp in seconds.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::is_market_open;
+///
+/// assert!(is_market_open(1708722000));   // Friday 23 February 2024, 21:00 UTC
+/// assert!(!is_market_open(1708725600));  // Friday 23 February 2024, 22:00 UTC
+/// assert!(!is_market_open(1708894800));  // Sunday 25 February 2024, 21:00 UTC
+/// assert!(is_market_open(1708898400));   // Sunday 25 February 2024, 22:00 UTC
+/// ```
+pub fn is_market_open(unix_timestamp: i64) -> bool {
+    !is_weekend(unix_timestamp)
+}
+
+
+/// Identifies the trading session right now, under the default configuration.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{current_session, TradingSession};
+///
+/// assert!(TradingSession::ALL.contains(&current_session()));
+/// ```
+#[cfg(feature = "std")]
+pub fn current_session() -> TradingSession {
+    current_session_with(&SystemClock)
+}
+
+
+/// Identifies the trading session at the current time of a clock, under the default configuration.
+///
+/// # Arguments
+///
+/// * `clock` - The clock, e.g. a fixed `SystemTime` in tests.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use trading_sessions::{current_session_with, TradingSession};
+///
+/// // Saturday 24 February 2024, 02:00 UTC
+/// let now = UNIX_EPOCH + Duration::from_secs(1708740000);
+/// assert_eq!(current_session_with(&now), TradingSession::Weekend);
+/// ```
+#[cfg(feature = "std")]
+pub fn current_session_with(clock: &impl Clock) -> TradingSession {
+    clock_identifier(clock).trading_session()
+}
+
+
+/// Returns whether a trading session is open right now, overlaps included, e.g. London during London_NewYork.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{is_session_open_now, TradingSession};
+///
+/// if is_session_open_now(TradingSession::London) {
+///     println!("London is open");
+/// }
+/// ```
+#[cfg(feature = "std")]
+pub fn is_session_open_now(session: TradingSession) -> bool {
+    is_session_open_with(session, &SystemClock)
+}
+
+
+/// Returns whether a trading session is open at the current time of a clock, overlaps included.
+///
+/// # Arguments
+///
+/// * `session` - The trading session to check.
+/// * `clock` - The clock, e.g. a fixed `SystemTime` in tests.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use trading_sessions::{is_session_open_with, TradingSession};
+///
+/// // Thursday 22 February 2024, 14:00 UTC, London_NewYork
+/// let now = UNIX_EPOCH + Duration::from_secs(1708610400);
+/// assert!(is_session_open_with(TradingSession::London, &now));
+/// assert!(is_session_open_with(TradingSession::LondonNewYork, &now));
+/// assert!(!is_session_open_with(TradingSession::Tokyo, &now));
+/// ```
+#[cfg(feature = "std")]
+pub fn is_session_open_with(session: TradingSession, clock: &impl Clock) -> bool {
+    let current = current_session_with(clock);
+    current == session || current.constituents().contains(&session)
+}
+
+
+/// Computes the time until the next trading session opens, from right now.
+///
+/// # Returns
+///
+/// The time until the next session opens, or `None` if no session opens within the next two weeks.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use trading_sessions::next_open_in;
+///
+/// // The market reopens within a weekend
+/// assert!(next_open_in().unwrap() <= Duration::from_secs(2 * 86_400));
+/// ```
+#[cfg(feature = "std")]
+pub fn next_open_in() -> Option<std::time::Duration> {
+    next_open_in_with(&SystemClock)
+}
+
+
+/// Computes the time until the next trading session opens, from the current time of a clock.
+///
+/// The next session is the next change to a session other than the weekend, a closure, a
+/// holiday or the Undefined gap, e.g. London_NewYork during London, or Tokyo during the weekend.
+///
+/// # Arguments
+///
+/// * `clock` - The clock, e.g. a fixed `SystemTime` in tests.
+///
+/// # Returns
+///
+/// The time until the next session opens, or `None` if no session opens within the next two weeks.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use trading_sessions::next_open_in_with;
+///
+/// // Thursday 22 February 2024, 10:00:00.250 UTC, London_NewYork opens at 13:00 UTC
+/// let now = UNIX_EPOCH + Duration::from_millis(1708596000_250);
+/// assert_eq!(next_open_in_with(&now), Some(Duration::from_millis(3 * 3600 * 1000 - 250)));
+///
+/// // Friday 23 February 2024, 21:00 UTC, the weekend comes first and Tokyo opens on Monday 00:00 UTC
+/// let now = UNIX_EPOCH + Duration::from_secs(1708722000);
+/// assert_eq!(next_open_in_with(&now), Some(Duration::from_secs(51 * 3600)));
+/// ```
+#[cfg(feature = "std")]
+pub fn next_open_in_with(clock: &impl Clock) -> Option<std::time::Duration> {
+    let now = clock.now();
+    let identifier = clock_identifier(&now);
+    let mut next = identifier.next_session()?;
+    while matches!(next.0, TradingSession::Weekend | TradingSession::Closed | TradingSession::Holiday | TradingSession::Undefined) {
+        next = identifier.next_session_at(next.1)?;
+        if next.1 - identifier.unix_timestamp >= NAVIGATION_HORIZON {
+            return None;
+        }
+    }
+
+    let open = match u64::try_from(next.1) {
+        Ok(seconds) => std::time::UNIX_EPOCH + std::time::Duration::from_secs(seconds),
+        Err(_) => std::time::UNIX_EPOCH - std::time::Duration::from_secs(next.1.unsigned_abs()),
+    };
+    Some(open.duration_since(now).unwrap_or_default())
+}
+
+
+/// Builds an identifier for the current time of a clock in whole seconds, flooring instants before 1970.
+#[cfg(feature = "std")]
+fn clock_identifier(clock: &impl Clock) -> IdentifyTradingSession {
+    match clock.now().duration_since(std::time::UNIX_EPOCH) {
+        Ok(since_epoch) => IdentifyTradingSession::new(since_epoch.as_secs() as i64),
+        Err(error) => {
+            let before_epoch = error.duration();
+            IdentifyTradingSession::new(-(before_epoch.as_secs() as i64) - i64::from(before_epoch.subsec_nanos() > 0))
+        }
+    }
+}
+
+
+/// Computes the fraction of a time range during which the market is open.
+///
+/// The market is open on weekdays and closed for the weekend, i.e. from Friday 22:00 to Sunday 22:00 (UTC).
+/// Use [`HolidayCalendar::open_duty_cycle`] to also account for holidays.
+///
+/// # Arguments
+///
+/// * `start` - The Unix timestamp in seconds at which the range starts, inclusive.
+/// * `end` - The Unix timestamp in seconds at which the range ends, exclusive.
+///
+/// # Returns
+///
+/// The open fraction in `[0.0, 1.0]`, or 0.0 for an empty range.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::open_duty_cycle;
+///
+/// // Monday 19 February 2024 through Sunday 25 February 2024
+/// let week = open_duty_cycle(1708300800, 1708905600);
+/// assert!((week - 5.0 / 7.0).abs() < 1e-12);
+///
+/// // Saturday 24 February 2024, 00:00 - 12:00 UTC
+/// assert_eq!(open_duty_cycle(1708732800, 1708776000), 0.0);
+///
+/// // The last day before the end of time is a Sunday, which opens at 22:00 UTC
+/// assert_eq!(open_duty_cycle(i64::MAX - 3600, i64::MAX), 0.0);
+/// ```
+pub fn open_duty_cycle(start: i64, end: i64) -> f64 {
+    duty_cycle(start, end, |_| false)
+}
+
+
+/// Computes the fraction of `[start, end)` that falls on weekdays for which `is_holiday` is false.
+fn duty_cycle(start: i64, end: i64, is_holiday: impl Fn(i64) -> bool) -> f64 {
+    if end <= start {
+        return 0.0;
+    }
+
+    let day = i64::from(SECONDS_PER_DAY);
+    let mut open = 0;
+    let mut from = start;
+    while from < end {
+        // The weekend starts and ends at 22:00 UTC and holidays last whole days, so both boundaries split the range
+        // Near the end of i64 either boundary may not exist, in which case the range ends first
+        let day_start = from - from.rem_euclid(day);
+        let (weekend_boundary, midnight) = (day_start.checked_add(day - WEEKEND_SHIFT), day_start.checked_add(day));
+        let to = match weekend_boundary {
+            Some(weekend_boundary) if from < weekend_boundary => weekend_boundary,
+            _ => midnight.unwrap_or(end),
+        }.min(end);
+        let closed = is_weekend(from) || is_holiday(from);
+        if !closed {
+            open += to - from;
+        }
+        from = to;
+    }
+
+    open as f64 / (end - start) as f64
+}
+
+
+/// Builds a frame of metadata per trading session, to be joined onto a frame of classified sessions.
+///
+/// The frame has one row per session with the columns:
+/// - `session`: The session label, as in the "Session" column.
+/// - `index`: The position of the session in the declaration order of [`TradingSession::ALL`].
+/// - `color`: A hex color to chart the session with.
+/// - `liquidity_weight`: The relative liquidity of the session, see [`TradingSession::liquidity_weight`].
+/// - `macro_session`: The region of the session, or "Overlap" for the overlapping sessions.
+///
+/// # Returns
+///
+/// A DataFrame with the metadata of all trading sessions.
+///
+/// # Examples
+///
+/// ```
+/// use polars::prelude::*;
+/// use trading_sessions::{session_metadata_frame, SessionColumn};
+///
+/// let metadata = session_metadata_frame();
+/// let london = metadata.clone().lazy().filter(col("session").eq(lit("London"))).collect().unwrap();
+/// assert_eq!(london.column("macro_session").unwrap().str_value(0).unwrap(), "Europe");
+/// assert_eq!(london.column("color").unwrap().str_value(0).unwrap(), "#1f77b4");
+///
+/// let df = df! {
+///     "time" => [1708574400, 1708596000]
+/// }.unwrap();
+///
+/// let enriched = SessionColumn::new(df.lazy())
+///     .apply_session_column()
+///     .into_lazyframe()
+///     .left_join(metadata.lazy(), col("Session"), col("session"))
+///     .collect()
+///     .unwrap();
+/// assert_eq!(enriched.column("macro_session").unwrap().str_value(0).unwrap(), "Asia");
+/// ```
+#[cfg(feature = "polars")]
+pub fn session_metadata_frame() -> DataFrame {
+    let metadata = TradingSession::ALL.iter().map(|session| match session {
+        TradingSession::Tokyo => ("#d62728", "Asia"),
+        TradingSession::TokyoLondon => ("#9467bd", "Overlap"),
+        TradingSession::London => ("#1f77b4", "Europe"),
+        TradingSession::LondonNewYork => ("#17becf", "Overlap"),
+        TradingSession::NewYork => ("#2ca02c", "Americas"),
+        TradingSession::Sydney => ("#ff7f0e", "Oceania"),
+        TradingSession::SydneyTokyo => ("#8c564b", "Overlap"),
+        TradingSession::PreMarket => ("#dbdb8d", "Americas"),
+        TradingSession::Regular => ("#98df8a", "Americas"),
+        TradingSession::AfterHours => ("#bcbd22", "Americas"),
+        TradingSession::Globex => ("#aec7e8", "Americas"),
+        TradingSession::Weekend | TradingSession::Closed | TradingSession::Holiday => ("#7f7f7f", "Closed"),
+        TradingSession::Undefined => ("#c7c7c7", "Undefined"),
+    });
+    let (colors, (liquidity_weights, macro_sessions)): (Vec<&str>, (Vec<f64>, Vec<&str>)) = metadata
+        .zip(TradingSession::ALL)
+        .map(|((color, macro_session), session)| (color, (session.liquidity_weight(), macro_session)))
+        .unzip();
+
+    df! {
+        "session" => TradingSession::ALL.iter().map(TradingSession::as_str).collect::<Vec<_>>(),
+        "index" => (0..TradingSession::ALL.len() as u32).collect::<Vec<_>>(),
+        "color" => colors,
+        "liquidity_weight" => liquidity_weights,
+        "macro_session" => macro_sessions,
+    }
+    .expect("the metadata columns have one row per session each")
+}
+
+
+/// Builds a reusable pipeline step that applies the trading session column to a LazyFrame.
+///
+/// The returned closure can be stored and chained like any other `LazyFrame -> LazyFrame` transform.
+///
+/// # Arguments
+///
+/// * `time_col` - The name of the column holding the Unix timestamps in seconds.
+/// * `out_col` - The name of the session column to add.
+///
+/// # Returns
+///
+/// A closure applying the session column to the LazyFrame it is given.
+///
+/// # Examples
+///
+/// ```
+/// use polars::prelude::*;
+/// use trading_sessions::with_session;
+///
+/// let add_session = with_session("ts", "session");
+///
+/// let df = df! {
+///     "ts" => [1708574400, 1708596000]
+/// }.unwrap();
+///
+/// let result_df = add_session(df.lazy()).collect().unwrap();
+/// assert_eq!(result_df.column("session").unwrap().str_value(0).unwrap(), "Tokyo");
+/// assert_eq!(result_df.column("session").unwrap().str_value(1).unwrap(), "London");
+/// ```
+#[cfg(feature = "polars")]
+pub fn with_session(time_col: &str, out_col: &str) -> impl Fn(LazyFrame) -> LazyFrame {
+    let (time_col, out_col) = (time_col.to_string(), out_col.to_string());
+    move |lazyframe| {
+        SessionColumn::new(lazyframe)
+            .with_columns(&time_col, &out_col)
+            .apply_session_column()
+            .into_lazyframe()
+    }
+}
+
+
+/// Builds the expression classifying a column of Unix timestamps into trading sessions.
+///
+/// Unlike `SessionColumn`, no LazyFrame is taken, so the expression can be composed inside an existing
+/// `select`, `with_columns` or `group_by` call.
+///
+/// # Arguments
+///
+/// * `time_col` - The name of the column holding the Unix timestamps in seconds.
+///
+/// # Returns
+///
+/// An expression evaluating to the trading session names, aliased as `Session`.
+///
+/// # Examples
+///
+/// ```
+/// use polars::prelude::*;
+/// use trading_sessions::session_expr;
+///
+/// let df = df! {
+///     "ts" => [1708574400, 1708596000]
+/// }.unwrap();
+///
+/// let result_df = df.lazy()
+///     .with_columns([session_expr("ts").alias("session")])
+///     .collect()
+///     .unwrap();
+/// assert_eq!(result_df.column("session").unwrap().str_value(0).unwrap(), "Tokyo");
+/// assert_eq!(result_df.column("session").unwrap().str_value(1).unwrap(), "London");
+/// ```
+#[cfg(feature = "polars")]
+pub fn session_expr(time_col: &str) -> Expr {
+    let column = SessionColumn::new(LazyFrame::default()).with_columns(time_col, "Session");
+    column.output_expr(column.session_expr())
+}
+
+
+/// Identifies the trading session of each Unix timestamp in a slice.
+///
+/// # Arguments
+///
+/// * `timestamps` - The Unix timestamps in seconds.
+///
+/// # Returns
+///
+/// A vector with the trading session of each timestamp, in the same order.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{classify, TradingSession};
+///
+/// assert_eq!(classify(&[1708574400, 1708596000]), [TradingSession::Tokyo, TradingSession::London]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn classify(timestamps: &[i64]) -> Vec<TradingSession> {
+    timestamps.iter().map(|&unix_timestamp| session_at(unix_timestamp)).collect()
+}
+
+
+/// Identifies the trading session of each Unix timestamp in a slice, for processing millions of ticks outside of Polars.
+///
+/// Each timestamp is classified with a lookup in a precomputed hour-to-session table, without
+/// allocating a label or walking the session boundaries per element. With the `rayon` feature,
+/// large slices are classified in fixed-size chunks across all cores, written straight into the
+/// returned vector, so no memory beyond the output is allocated.
+///
+/// # Arguments
+///
+/// * `timestamps` - The Unix timestamps in seconds.
+///
+/// # Returns
+///
+/// A vector with the trading session of each timestamp, in the same order.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{classify, identify_many, IdentifyTradingSession, TradingSession};
+///
+/// // Thursday 22 February 2024, every ten minutes from 00:00 UTC for a week
+/// let timestamps: Vec<i64> = (0..1008).map(|i| 1708560000 + i * 600).collect();
+/// let sessions = identify_many(&timestamps);
+///
+/// assert_eq!(sessions[0], TradingSession::Tokyo);
+/// assert!(sessions.contains(&TradingSession::Weekend));
+/// for (&unix_timestamp, &session) in timestamps.iter().zip(&sessions) {
+///     assert_eq!(session, IdentifyTradingSession::new(unix_timestamp).trading_session());
+/// }
+///
+/// // Large slices are classified the same way, across all cores with the `rayon` feature
+/// let ticks: Vec<i64> = (0..200_000).map(|i| 1708560000 + i * 7).collect();
+/// assert_eq!(identify_many(&ticks), classify(&ticks));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn identify_many(timestamps: &[i64]) -> Vec<TradingSession> {
+    #[cfg(feature = "rayon")]
+    if timestamps.len() > PARALLEL_CHUNK_LEN {
+        let mut sessions = vec![TradingSession::Undefined; timestamps.len()];
+        classify_chunks_in_parallel(timestamps, &mut sessions);
+        return sessions;
+    }
+
+    let mut sessions = Vec::with_capacity(timestamps.len());
+    sessions.extend(timestamps.iter().map(|&unix_timestamp| session_at(unix_timestamp)));
+    sessions
+}
+
+
+/// Identifies the trading session of each Unix timestamp into a preallocated output buffer.
+///
+/// Unlike [`classify`], this does not allocate, which makes it suitable for real-time loops. With
+/// the `rayon` feature, large slices are classified in chunks across all cores, like [`identify_many`].
+///
+/// # Arguments
+///
+/// * `timestamps` - The Unix timestamps in seconds.
+/// * `out` - The buffer receiving the trading session of each timestamp, of the same length as `timestamps`.
+///
+/// # Errors
+///
+/// Returns a `LengthMismatch` if `out` and `timestamps` differ in length, leaving `out` untouched.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{classify, classify_into, TradingSession};
+///
+/// let timestamps = [1708574400, 1708596000, 1708696800, 1708740000];
+/// let mut out = [TradingSession::Undefined; 4];
+/// classify_into(&timestamps, &mut out).unwrap();
+/// assert_eq!(out.to_vec(), classify(&timestamps));
+///
+/// let mut too_short = [TradingSession::Undefined; 3];
+/// assert!(classify_into(&timestamps, &mut too_short).is_err());
+/// ```
+pub fn classify_into(timestamps: &[i64], out: &mut [TradingSession]) -> Result<(), LengthMismatch> {
+    if timestamps.len() != out.len() {
+        return Err(LengthMismatch { timestamps: timestamps.len(), out: out.len() });
+    }
+
+    #[cfg(feature = "rayon")]
+    if timestamps.len() > PARALLEL_CHUNK_LEN {
+        classify_chunks_in_parallel(timestamps, out);
+        return Ok(());
+    }
+
+    for (session, &unix_timestamp) in out.iter_mut().zip(timestamps) {
+        *session = session_at(unix_timestamp);
+    }
+    Ok(())
+}
+
+
+/// The number of timestamps classified per rayon task, large enough to outweigh the scheduling overhead.
+#[cfg(feature = "rayon")]
+const PARALLEL_CHUNK_LEN: usize = 1 << 16;
+
+
+/// Classifies timestamps into an output buffer of the same length, one chunk of [`PARALLEL_CHUNK_LEN`] per rayon task.
+#[cfg(feature = "rayon")]
+fn classify_chunks_in_parallel(timestamps: &[i64], out: &mut [TradingSession]) {
+    use rayon::prelude::*;
+
+    out.par_chunks_mut(PARALLEL_CHUNK_LEN)
+        .zip(timestamps.par_chunks(PARALLEL_CHUNK_LEN))
+        .for_each(|(out, timestamps)| {
+            for (session, &unix_timestamp) in out.iter_mut().zip(timestamps) {
+                *session = session_at(unix_timestamp);
+            }
+        });
+}
+
+
+/// Identifies the trading session of each timestamp in a standalone Series.
+///
+/// The timestamps are either integer Unix timestamps in seconds or a Datetime Series of any time unit.
+/// The returned Series is already named, so it can be inserted into a DataFrame directly.
+///
+/// # Arguments
+///
+/// * `times` - The Series of timestamps.
+/// * `name` - The name of the returned Series.
+///
+/// # Returns
+///
+/// A Utf8 Series with the session label of each timestamp, or an error if the timestamps are not
+/// integers or datetimes.
+///
+/// # Examples
+///
+/// ```
+/// use polars::prelude::*;
+/// use trading_sessions::session_series_named;
+///
+/// let times = Series::new("ts", [1708574400i64, 1708596000, 1708696800]);
+/// let mut df = df! {
+///     "close" => [1.0, 2.0, 3.0]
+/// }.unwrap();
+///
+/// df.with_column(session_series_named(&times, "Session").unwrap()).unwrap();
+///
+/// let sessions: Vec<Option<&str>> = df.column("Session").unwrap().utf8().unwrap().into_iter().collect();
+/// assert_eq!(sessions, [Some("Tokyo"), Some("London"), Some("London_NewYork")]);
+/// ```
+#[cfg(feature = "polars")]
+pub fn session_series_named(times: &Series, name: &str) -> PolarsResult<Series> {
+    let (ticks_per_second, schedule) = (ticks_per_second_of(times.dtype())?, SessionSchedule::forex());
+    let mut sessions = classify_parallel(&times.cast(&DataType::Int64)?, ticks_per_second, |_| &schedule, None, TradingSession::Undefined.as_str(), None, true)?;
+    sessions.rename(name);
+    Ok(sessions)
+}
+
+
+/// Returns the number of ticks per second of a Series of integer Unix timestamps in seconds or of datetimes.
+#[cfg(feature = "polars")]
+fn ticks_per_second_of(dtype: &DataType) -> PolarsResult<i64> {
+    Ok(match dtype {
+        DataType::Datetime(TimeUnit::Nanoseconds, _) => 1_000_000_000,
+        DataType::Datetime(TimeUnit::Microseconds, _) => 1_000_000,
+        DataType::Datetime(TimeUnit::Milliseconds, _) => 1_000,
+        dtype if dtype.is_integer() => 1,
+        dtype => polars_bail!(InvalidOperation: "cannot identify trading sessions of dtype {}", dtype),
+    })
+}
+
+
+/// Identifies the trading session of each Unix timestamp in a Series, eagerly.
+///
+/// This is the eager counterpart of [`SessionColumn::apply_session_column`] for workflows on small
+/// in-memory buffers, and is equivalent to [`session_series_named`] with the name "Session".
+///
+/// # Arguments
+///
+/// * `timestamps` - The Series of integer Unix timestamps in seconds, or of datetimes.
+///
+/// # Returns
+///
+/// A Utf8 Series named "Session" with the session label of each timestamp, or an error if the
+/// timestamps are not integers or datetimes.
+///
+/// # Examples
+///
+/// ```
+/// use polars::prelude::*;
+/// use trading_sessions::identify_sessions_series;
+///
+/// let timestamps = Series::new("time", [1708574400u32, 1708596000, 1708740000]);
+/// let sessions = identify_sessions_series(&timestamps).unwrap();
+///
+/// assert_eq!(sessions.name(), "Session");
+/// assert_eq!(sessions.str_value(2).unwrap(), "Weekend");
+/// assert!(identify_sessions_series(&Series::new("time", ["a", "b"])).is_err());
+/// ```
+#[cfg(feature = "polars")]
+pub fn identify_sessions_series(timestamps: &Series) -> PolarsResult<Series> {
+    session_series_named(timestamps, "Session")
+}
+
+
+/// Identifies the trading session of each Unix timestamp in an `Int64Chunked`, eagerly.
+///
+/// This is the typed counterpart of [`identify_sessions_series`] for callers already holding a
+/// `ChunkedArray<Int64Type>`, so no dtype check is needed.
+///
+/// # Arguments
+///
+/// * `timestamps` - The ChunkedArray of Unix timestamps in seconds.
+///
+/// # Returns
+///
+/// A `Utf8Chunked` named "Session" with the session label of each timestamp. Null timestamps stay null.
+///
+/// # Examples
+///
+/// ```
+/// use polars::prelude::*;
+/// use trading_sessions::identify_sessions_chunked;
+///
+/// let timestamps = Int64Chunked::from_slice("time", &[1708574400, 1708596000, 1708740000]);
+/// let sessions = identify_sessions_chunked(&timestamps).unwrap();
+///
+/// assert_eq!(sessions.name(), "Session");
+/// assert_eq!(sessions.get(0), Some("Tokyo"));
+/// assert_eq!(sessions.get(2), Some("Weekend"));
+/// ```
+#[cfg(feature = "polars")]
+pub fn identify_sessions_chunked(timestamps: &Int64Chunked) -> PolarsResult<Utf8Chunked> {
+    identify_sessions_series(&timestamps.clone().into_series())?.utf8().cloned()
+}
+
+
+#[cfg(feature = "alloc")]
+impl SessionSchedule {
+    /// Creates a new, empty SessionSchedule.
+    ///
+    /// # Returns
+    ///
+    /// A new SessionSchedule instance without any session windows.
+    pub fn new() -> Self {
+        Self { windows: Vec::new(), weekend: None }
+    }
+
+    /// Creates the default Forex schedule, as listed in [`SESSION_BOUNDARIES`].
+    ///
+    /// # Returns
+    ///
+    /// A new SessionSchedule instance with the default session windows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{SessionSchedule, TradingSession};
+    ///
+    /// let schedule = SessionSchedule::forex();
+    /// assert_eq!(schedule.session_for_seconds(10 * 3600), TradingSession::London);
+    /// ```
+    pub fn forex() -> Self {
+        Self { windows: FOREX_WINDOWS.to_vec(), weekend: None }
+    }
+
+    /// Creates a schedule of Asia, Europe and US liquidity buckets for around-the-clock Crypto markets.
+    ///
+    /// Unlike the default schedule, the buckets don't overlap and together cover the whole day:
+    /// - Tokyo: 10:00 PM - 8:00 AM, the Asian hours
+    /// - London: 8:00 AM - 1:00 PM, the European hours
+    /// - NewYork: 1:00 PM - 10:00 PM, the US hours
+    ///
+    /// Combine it with `Market::Crypto`, so the weekend is classified the same way.
+    ///
+    /// # Returns
+    ///
+    /// A new SessionSchedule instance with the Crypto liquidity buckets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{IdentifyTradingSession, Market, SessionSchedule, TradingSession};
+    ///
+    /// let schedule = SessionSchedule::crypto();
+    /// assert_eq!(schedule.duration_secs(TradingSession::Tokyo), 10 * 3600);
+    /// assert_eq!(schedule.session_for_seconds(23 * 3600), TradingSession::Tokyo);
+    ///
+    /// // Saturday 24 February 2024, 15:00 UTC
+    /// let session_identifier = IdentifyTradingSession::new(1708786800).with_market(Market::Crypto).with_schedule(schedule);
+    /// assert_eq!(session_identifier.trading_session(), TradingSession::NewYork);
+    /// ```
+    pub fn crypto() -> Self {
+        Self::new()
+            .add(TradingSession::Tokyo, 22 * SECONDS_PER_HOUR, 8 * SECONDS_PER_HOUR)
+            .add(TradingSession::London, 8 * SECONDS_PER_HOUR, 13 * SECONDS_PER_HOUR)
+            .add(TradingSession::NewYork, 13 * SECONDS_PER_HOUR, 22 * SECONDS_PER_HOUR)
+    }
+
+    /// Creates the NYSE equity schedule in Eastern Standard Time, classifying timestamps into PreMarket
+    /// (04:00 - 09:30 ET), Regular (09:30 - 16:00 ET), AfterHours (16:00 - 20:00 ET) and Closed.
+    ///
+    /// Instead of the Forex weekend, the schedule is closed for the weekend from the after-hours close
+    /// on Friday to the pre-market open on Monday, i.e. from Saturday 01:00 to Monday 09:00 UTC.
+    ///
+    /// The windows are fixed to standard time all year round, and daylight saving time, e.g.
+    /// [`IdentifyTradingSession::with_dst`], does not shift a custom schedule. From the second Sunday of
+    /// March to the first Sunday of November, every session is therefore classified an hour late; use
+    /// [`SessionSchedule::nyse_at`] for the schedule in effect during Eastern Daylight Time.
+    ///
+    /// # Returns
+    ///
+    /// A new SessionSchedule instance with the NYSE session windows in UTC.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{IdentifyTradingSession, SessionSchedule, TradingSession};
+    ///
+    /// let schedule = SessionSchedule::nyse();
+    /// assert_eq!(schedule.session_for_seconds(14 * 3600), TradingSession::PreMarket);       // 09:00 ET
+    /// assert_eq!(schedule.session_for_seconds(14 * 3600 + 1800), TradingSession::Regular);  // 09:30 ET
+    ///
+    /// // Thursday 22 February 2024, 22:00 UTC
+    /// let session_identifier = IdentifyTradingSession::new(1708639200).with_schedule(schedule.clone());
+    /// assert_eq!(session_identifier.trading_session(), TradingSession::AfterHours);
+    ///
+    /// // Friday 23 February 2024, 23:00 UTC is still after hours, Sunday 25 February 2024, 23:30 UTC the weekend
+    /// let session_at = |time| IdentifyTradingSession::new(time).with_schedule(schedule.clone()).trading_session();
+    /// assert_eq!(session_at(1708729200), TradingSession::AfterHours);
+    /// assert_eq!(session_at(1708907400), TradingSession::Weekend);
+    /// assert_eq!(session_at(1708938000), TradingSession::PreMarket);
+    ///
+    /// // Monday 1 July 2024, 13:30 UTC is the 09:30 EDT open, which only the summer schedule knows of
+    /// assert_eq!(session_at(1719840600), TradingSession::PreMarket);
+    /// let session_identifier = IdentifyTradingSession::new(1719840600).with_schedule(SessionSchedule::nyse_at(1719840600));
+    /// assert_eq!(session_identifier.trading_session(), TradingSession::Regular);
+    /// ```
+    pub fn nyse() -> Self {
+        Self { windows: NYSE_WINDOWS.to_vec(), weekend: Some(NYSE_WEEKEND) }
+    }
+
+    /// Creates the NYSE equity schedule in effect at a Unix timestamp in seconds.
+    ///
+    /// While New York observes daylight saving time, i.e. from the second Sunday of March to the first
+    /// Sunday of November, every window and the weekend open and close an hour earlier in UTC than in [`SessionSchedule::nyse`].
+    ///
+    /// # Arguments
+    ///
+    /// * `unix_timestamp` - The Unix timestamp in seconds to pick the schedule for.
+    ///
+    /// # Returns
+    ///
+    /// A new SessionSchedule instance with the NYSE session windows in UTC.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{IdentifyTradingSession, SessionSchedule, TradingSession};
+    ///
+    /// // Monday 1 July 2024, 13:30 UTC, i.e. 09:30 EDT
+    /// let session_identifier = IdentifyTradingSession::new(1719840600).with_schedule(SessionSchedule::nyse_at(1719840600));
+    /// assert_eq!(session_identifier.trading_session(), TradingSession::Regular);
+    ///
+    /// assert_eq!(SessionSchedule::nyse_at(1708596000), SessionSchedule::nyse());
+    /// ```
+    pub fn nyse_at(unix_timestamp: i64) -> Self {
+        let [weekend] = us_summer_time_weekends([NYSE_WEEKEND], unix_timestamp);
+        Self { windows: us_summer_time_windows(NYSE_WINDOWS, unix_timestamp).to_vec(), weekend: Some(weekend) }
+    }
+
+    /// Creates the CME Globex schedule for equity index futures in Central Standard Time.
+    ///
+    /// The trading day runs from 17:00 to 16:00 CT, with the regular trading hours from 08:30 to 15:15 CT:
+    /// - Globex: 17:00 - 08:30 CT and 15:15 - 16:00 CT, the electronic session outside of the regular hours
+    /// - Regular: 08:30 - 15:15 CT
+    /// - Closed: 16:00 - 17:00 CT, the daily maintenance halt
+    ///
+    /// Like schedules without a weekend of their own, the Forex weekend from Friday 22:00 to Sunday 22:00 UTC still applies.
+    ///
+    /// The windows are fixed to standard time all year round, and daylight saving time, e.g.
+    /// [`IdentifyTradingSession::with_dst`], does not shift a custom schedule. While Chicago observes
+    /// daylight saving time, every session is therefore classified an hour late; use
+    /// [`SessionSchedule::cme_globex_at`] for the schedule in effect during Central Daylight Time.
+    ///
+    /// # Returns
+    ///
+    /// A new SessionSchedule instance with the CME Globex session windows in UTC.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{IdentifyTradingSession, SessionSchedule, TradingSession};
+    ///
+    /// let schedule = SessionSchedule::cme_globex();
+    /// assert!(schedule.validate().is_ok());
+    ///
+    /// // Thursday 22 February 2024, 04:00, 15:00 and 22:30 UTC
+    /// let session_at = |time| IdentifyTradingSession::new(time).with_schedule(schedule.clone()).trading_session();
+    /// assert_eq!(session_at(1708574400), TradingSession::Globex);
+    /// assert_eq!(session_at(1708614000), TradingSession::Regular);
+    /// assert_eq!(session_at(1708641000), TradingSession::Closed);
+    /// ```
+    pub fn cme_globex() -> Self {
+        Self { windows: CME_GLOBEX_WINDOWS.to_vec(), weekend: None }
+    }
+
+    /// Creates the CME Globex schedule in effect at a Unix timestamp in seconds.
+    ///
+    /// While Chicago observes daylight saving time, every window opens and closes an hour earlier in
+    /// UTC than in [`SessionSchedule::cme_globex`].
+    ///
+    /// # Arguments
+    ///
+    /// * `unix_timestamp` - The Unix timestamp in seconds to pick the schedule for.
+    ///
+    /// # Returns
+    ///
+    /// A new SessionSchedule instance with the CME Globex session windows in UTC.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{SessionSchedule, TradingSession};
+    ///
+    /// // Monday 1 July 2024, 21:30 UTC, i.e. 16:30 CDT
+    /// let schedule = SessionSchedule::cme_globex_at(1719869400);
+    /// assert_eq!(schedule.session_for_seconds(21 * 3600 + 1800), TradingSession::Closed);
+    /// ```
+    pub fn cme_globex_at(unix_timestamp: i64) -> Self {
+        Self { windows: us_summer_time_windows(CME_GLOBEX_WINDOWS, unix_timestamp).to_vec(), weekend: None }
+    }
+
+    /// Looks up a schedule by name, e.g. to select the schedule of a pipeline from a config string.
+    ///
+    /// The built-in presets are:
+    /// - "forex_utc": the default Forex schedule, see [`SessionSchedule::forex`]
+    /// - "forex_summer": the Forex schedule while both London and New York observe summer time, i.e. their boundaries an hour earlier.
+    ///   The schedule is a static snapshot, which misclassifies the winter months; to follow the changes of
+    ///   daylight saving time, use the default schedule with [`IdentifyTradingSession::with_dst`] instead
+    /// - "nyse": the NYSE schedule in standard time, see [`SessionSchedule::nyse`]
+    /// - "cme": the CME Globex schedule in standard time, see [`SessionSchedule::cme_globex`]
+    /// - "crypto": the Crypto liquidity buckets, see [`SessionSchedule::crypto`]
+    ///
+    /// Presets registered with [`SessionSchedule::register_preset`] are looked up first, so they may
+    /// also replace a built-in preset.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the preset.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TradingSessionsError::UnknownPreset` if no preset of the given name exists.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{SessionSchedule, TradingSession};
+    ///
+    /// assert_eq!(SessionSchedule::preset("nyse").unwrap(), SessionSchedule::nyse());
+    ///
+    /// // London opens at 07:00 UTC in winter and at 06:00 UTC in summer
+    /// let schedule = SessionSchedule::preset("forex_summer").unwrap();
+    /// assert_eq!(schedule.session_for_seconds(6 * 3600), TradingSession::TokyoLondon);
+    /// assert_eq!(schedule.session_for_seconds(21 * 3600), TradingSession::Undefined);
+    ///
+    /// assert_eq!(SessionSchedule::preset("forex").unwrap_err().to_string(), "'forex' matches no schedule preset");
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn preset(name: &str) -> Result<Self, TradingSessionsError> {
+        let custom = CUSTOM_PRESETS.read().unwrap_or_else(std::sync::PoisonError::into_inner);
+        match custom.iter().find(|(preset, _)| preset == name) {
+            Some((_, schedule)) => Ok(schedule.clone()),
+            None => builtin_preset(name).ok_or_else(|| TradingSessionsError::UnknownPreset(name.to_string())),
+        }
+    }
+
+    /// Registers a named preset at runtime, to be looked up with [`SessionSchedule::preset`].
+    ///
+    /// The presets are shared by the whole process. Registering a name again replaces the earlier preset.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the preset.
+    /// * `schedule` - The schedule to look up by the name.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TradingSessionsError::InvalidSchedule` if the schedule does not pass [`SessionSchedule::validate`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{SessionSchedule, TradingSession};
+    ///
+    /// let schedule = SessionSchedule::new().add(TradingSession::Regular, 8 * 3600, 16 * 3600);
+    /// SessionSchedule::register_preset("xetra", schedule.clone()).unwrap();
+    ///
+    /// assert_eq!(SessionSchedule::preset("xetra").unwrap(), schedule);
+    /// assert!(SessionSchedule::preset_names().contains(&"xetra".to_string()));
+    ///
+    /// let invalid = SessionSchedule::new().add(TradingSession::Regular, 8 * 3600, 100_000);
+    /// assert!(SessionSchedule::register_preset("invalid", invalid).is_err());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn register_preset(name: &str, schedule: SessionSchedule) -> Result<(), TradingSessionsError> {
+        schedule.validate()?;
+        let mut custom = CUSTOM_PRESETS.write().unwrap_or_else(std::sync::PoisonError::into_inner);
+        match custom.iter_mut().find(|(preset, _)| preset == name) {
+            Some((_, registered)) => *registered = schedule,
+            None => custom.push((name.to_string(), schedule)),
+        }
+        Ok(())
+    }
+
+    /// Returns the names of the presets, built-in ones first, followed by the registered ones in order of registration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::SessionSchedule;
+    ///
+    /// assert_eq!(SessionSchedule::preset_names()[..5], ["forex_utc", "forex_summer", "nyse", "cme", "crypto"]);
+    /// assert!(SessionSchedule::preset_names().iter().all(|name| SessionSchedule::preset(name).is_ok()));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn preset_names() -> Vec<String> {
+        let custom = CUSTOM_PRESETS.read().unwrap_or_else(std::sync::PoisonError::into_inner);
+        PRESET_NAMES
+            .iter()
+            .map(|name| name.to_string())
+            .chain(custom.iter().map(|(name, _)| name.clone()).filter(|name| !PRESET_NAMES.contains(&name.as_str())))
+            .collect()
+    }
+
+    /// Adds a session window to the schedule.
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - The trading session of the window.
+    /// * `start` - The start of the window in seconds since midnight UTC, inclusive.
+    /// * `end` - The end of the window in seconds since midnight UTC, exclusive.
+    ///
+    /// # Returns
+    ///
+    /// The SessionSchedule instance including the window.
+    pub fn add(mut self, session: TradingSession, start: u32, end: u32) -> Self {
+        self.windows.push(SessionWindow { session, start, end });
+        self
+    }
+
+    /// Sets the weekend of the schedule, taking the place of the Forex weekend, e.g. for equity markets.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The start of the weekend in seconds since Sunday 00:00 UTC, inclusive.
+    /// * `end` - The end of the weekend in seconds since Sunday 00:00 UTC, exclusive. An end before `start` wraps the week.
+    ///
+    /// # Returns
+    ///
+    /// The SessionSchedule instance with the weekend.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{SessionSchedule, TradingSession};
+    ///
+    /// // From Friday 21:00 to Sunday 23:00 UTC
+    /// let schedule = SessionSchedule::forex().with_weekend(5 * 86_400 + 21 * 3600, 23 * 3600);
+    ///
+    /// // Friday 23 February 2024, 21:30 UTC and Sunday 25 February 2024, 22:30 UTC
+    /// assert!(schedule.is_weekend(1708723800));
+    /// assert!(schedule.is_weekend(1708900200));
+    /// assert!(!SessionSchedule::forex().is_weekend(1708723800));
+    /// ```
+    pub fn with_weekend(self, start: u32, end: u32) -> Self {
+        Self { weekend: Some((start, end)), ..self }
+    }
+
+    /// Returns whether a Unix timestamp in seconds falls on the weekend of the schedule, by default
+    /// the Forex weekend from Friday 22:00 to Sunday 22:00 UTC.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::SessionSchedule;
+    ///
+    /// // Friday 23 February 2024, 23:00 UTC, i.e. 18:00 EST
+    /// assert!(SessionSchedule::forex().is_weekend(1708729200));
+    /// assert!(!SessionSchedule::nyse().is_weekend(1708729200));
+    /// ```
+    pub fn is_weekend(&self, unix_timestamp: i64) -> bool {
+        match self.weekend {
+            Some((start, end)) => {
+                let seconds_of_week = day_of_week(unix_timestamp) * SECONDS_PER_DAY + seconds_of_day(unix_timestamp);
+                match start <= end {
+                    true => (start..end).contains(&seconds_of_week),
+                    false => seconds_of_week >= start || seconds_of_week < end,
+                }
+            }
+            None => is_weekend(unix_timestamp),
+        }
+    }
+
+    /// Adds a session window between two minute-precision boundaries to the schedule.
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - The trading session of the window.
+    /// * `start` - The boundary at which the window starts, inclusive.
+    /// * `end` - The boundary at which the window ends, exclusive. A boundary before `start` wraps midnight.
+    ///
+    /// # Returns
+    ///
+    /// The SessionSchedule instance including the window.
+    pub fn add_between(self, session: TradingSession, start: SessionBoundary, end: SessionBoundary) -> Self {
+        self.add(session, start.seconds_of_day(), end.seconds_of_day())
+    }
+
+    /// Maps a number of seconds since midnight UTC to its trading session.
+    ///
+    /// Windows are matched in the order they were added, and seconds outside of all windows map to
+    /// `TradingSession::Undefined`.
+    ///
+    /// # Arguments
+    ///
+    /// * `seconds_of_day` - The number of seconds elapsed since midnight UTC.
+    ///
+    /// # Returns
+    ///
+    /// The trading session of the first window containing the given time of day.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{SessionSchedule, TradingSession};
+    ///
+    /// let schedule = SessionSchedule::new().add(TradingSession::London, 27_000, 46_800);
+    /// assert_eq!(schedule.session_for_seconds(26_999), TradingSession::Undefined);
+    /// assert_eq!(schedule.session_for_seconds(27_000), TradingSession::London);
+    /// ```
+    pub fn session_for_seconds(&self, seconds_of_day: u32) -> TradingSession {
+        window_for_seconds(&self.windows, seconds_of_day)
+            .map_or(TradingSession::Undefined, |window| window.session)
+    }
+
+    /// Returns the total length in seconds of the windows of the given trading session.
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - The trading session to measure.
+    ///
+    /// # Returns
+    ///
+    /// The summed length of all windows of the session, or 0 if the schedule has none.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{SessionSchedule, TradingSession};
+    ///
+    /// let schedule = SessionSchedule::new()
+    ///     .add(TradingSession::Tokyo, 23 * 3600, 3 * 3600)
+    ///     .add(TradingSession::London, 27_000, 46_800);
+    /// assert_eq!(schedule.duration_secs(TradingSession::Tokyo), 14_400);
+    /// assert_eq!(schedule.duration_secs(TradingSession::NewYork), 0);
+    ///
+    /// assert_eq!(SessionSchedule::forex().duration_secs(TradingSession::London), TradingSession::London.duration_secs());
+    /// ```
+    pub fn duration_secs(&self, session: TradingSession) -> u32 {
+        self.windows
+            .iter()
+            .filter(|window| window.session == session)
+            .map(SessionWindow::duration_secs)
+            .sum()
+    }
+
+    /// Checks that every window of the schedule lies within a single day.
+    ///
+    /// A window may wrap around midnight, but its start must lie before midnight, its end no later
+    /// than midnight, and it must not be empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TradingSessionsError::InvalidSchedule` with the first invalid window.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{SessionSchedule, TradingSession, TradingSessionsError};
+    ///
+    /// assert!(SessionSchedule::forex().validate().is_ok());
+    ///
+    /// let schedule = SessionSchedule::new().add(TradingSession::London, 32_400, 90_000);
+    /// assert!(matches!(schedule.validate(), Err(TradingSessionsError::InvalidSchedule(window)) if window.end == 90_000));
+    /// ```
+    pub fn validate(&self) -> Result<(), TradingSessionsError> {
+        match self.windows.iter().find(|window| {
+            window.start >= SECONDS_PER_DAY || window.end > SECONDS_PER_DAY || window.start == window.end
+        }) {
+            Some(window) => Err(TradingSessionsError::InvalidSchedule(*window)),
+            None => Ok(()),
+        }
+    }
+
+    /// Lists the problems of the schedule, e.g. to report a custom schedule before it produces confusing sessions.
+    ///
+    /// The diagnostics cover the windows failing [`SessionSchedule::validate`], zero-length windows and
+    /// overlapping windows, whose overlap is classified by whichever window comes first. With
+    /// `require_full_coverage`, the hours of the day covered by no window are reported as gaps as well,
+    /// since they are classified with the fallback session.
+    ///
+    /// # Arguments
+    ///
+    /// * `require_full_coverage` - Whether the windows must cover the whole day.
+    ///
+    /// # Returns
+    ///
+    /// The issues, with the problems of single windows in order of the windows first, followed by
+    /// the overlaps and then the gaps, ordered by the time of day at which they start. Empty if the schedule is sound.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{ScheduleIssue, SessionSchedule, TradingSession};
+    ///
+    /// assert!(SessionSchedule::forex().diagnose(true).is_empty());
+    /// assert!(SessionSchedule::crypto().diagnose(true).is_empty());
+    ///
+    /// let schedule = SessionSchedule::new()
+    ///     .add(TradingSession::Tokyo, 23 * 3600, 7 * 3600)
+    ///     .add(TradingSession::London, 7 * 3600, 7 * 3600)
+    ///     .add(TradingSession::NewYork, 13 * 3600, 22 * 3600);
+    /// assert!(schedule.validate().is_err());
+    /// assert_eq!(schedule.diagnose(true), [
+    ///     ScheduleIssue::ZeroLength(schedule.windows[1]),
+    ///     ScheduleIssue::Gap { start: 7 * 3600, end: 13 * 3600 },
+    ///     ScheduleIssue::Gap { start: 22 * 3600, end: 23 * 3600 },
+    /// ]);
+    /// assert_eq!(schedule.diagnose(false).len(), 1);
+    /// ```
+    pub fn diagnose(&self, require_full_coverage: bool) -> Vec<ScheduleIssue> {
+        let mut issues: Vec<ScheduleIssue> = self
+            .windows
+            .iter()
+            .filter_map(|window| match window {
+                _ if window.start >= SECONDS_PER_DAY || window.end > SECONDS_PER_DAY => Some(ScheduleIssue::OutOfRange(*window)),
+                _ if window.start == window.end => Some(ScheduleIssue::ZeroLength(*window)),
+                _ => None,
+            })
+            .collect();
+
+        // The valid windows as ranges within the day, splitting the ones which wrap around midnight
+        let mut ranges: Vec<(SessionWindow, u32, u32)> = Vec::new();
+        for window in self.windows.iter().filter(|window| window.start < SECONDS_PER_DAY && window.end <= SECONDS_PER_DAY && window.start != window.end) {
+            match window.start < window.end {
+                true => ranges.push((*window, window.start, window.end)),
+                false => {
+                    ranges.push((*window, window.start, SECONDS_PER_DAY));
+                    if window.end > 0 {
+                        ranges.push((*window, 0, window.end));
+                    }
+                }
+            }
+        }
+        ranges.sort_by_key(|&(_, start, end)| (start, end));
+
+        let mut overlaps = Vec::new();
+        for (index, &(first, first_start, first_end)) in ranges.iter().enumerate() {
+            for &(second, second_start, second_end) in &ranges[index + 1..] {
+                let (start, end) = (first_start.max(second_start), first_end.min(second_end));
+                if start < end {
+                    overlaps.push((start, end, first, second));
+                }
+            }
+        }
+        overlaps.sort_by_key(|&(start, end, _, _)| (start, end));
+        issues.extend(overlaps.into_iter().map(|(start, end, first, second)| ScheduleIssue::Overlap { first, second, start, end }));
+
+        if require_full_coverage {
+            let mut covered_until = 0;
+            for &(_, start, end) in &ranges {
+                if start > covered_until {
+                    issues.push(ScheduleIssue::Gap { start: covered_until, end: start });
+                }
+                covered_until = covered_until.max(end);
+            }
+            if covered_until < SECONDS_PER_DAY {
+                issues.push(ScheduleIssue::Gap { start: covered_until, end: SECONDS_PER_DAY });
+            }
+        }
+
+        issues
+    }
+
+    /// Generates a SQL `CASE` expression classifying a timestamp column like the schedule, so that
+    /// ETL jobs outside of Rust label sessions by the same rules.
+    ///
+    /// The expression follows the identification of a Forex identifier with this schedule: the weekend
+    /// of the schedule, by default from Friday 22:00 to Sunday 22:00 UTC, comes first, then the windows in the order they were added,
+    /// and "Undefined" for the seconds outside of every window. Fractional seconds are floored. DST,
+    /// holidays and the other markets are not taken into account.
+    ///
+    /// # Arguments
+    ///
+    /// * `time_col` - The SQL expression of the timestamp column, e.g. `"time"` or `trades.ts`, inserted as is.
+    /// * `dialect` - The SQL dialect, see [`SqlDialect`].
+    ///
+    /// # Returns
+    ///
+    /// The `CASE` expression evaluating to the session label, as returned by `TradingSession::as_str`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{SessionSchedule, SqlDialect, TradingSession};
+    ///
+    /// let schedule = SessionSchedule::new().add(TradingSession::Tokyo, 79_200, 25_200);
+    /// let sql = schedule.to_sql_case("ts", SqlDialect::ClickHouse);
+    ///
+    /// assert_eq!(sql, "CASE \
+    ///     WHEN ((toUnixTimestamp(ts) + 7200) % 604800 + 604800) % 604800 >= 172800 \
+    ///     AND ((toUnixTimestamp(ts) + 7200) % 604800 + 604800) % 604800 < 345600 THEN 'Weekend' \
+    ///     WHEN (toUnixTimestamp(ts) % 86400 + 86400) % 86400 >= 79200 \
+    ///     OR (toUnixTimestamp(ts) % 86400 + 86400) % 86400 < 25200 THEN 'Tokyo' \
+    ///     ELSE 'Undefined' END");
+    ///
+    /// // The NYSE weekend runs from Saturday 01:00 to Monday 09:00 UTC
+    /// let sql = SessionSchedule::nyse().to_sql_case("ts", SqlDialect::DuckDb);
+    /// assert!(sql.starts_with("CASE WHEN ((CAST(FLOOR(epoch(ts)) AS BIGINT) + 345600) % 604800 + 604800) % 604800 >= 522000 OR"));
+    /// ```
+    pub fn to_sql_case(&self, time_col: &str, dialect: SqlDialect) -> String {
+        let unix_seconds = match dialect {
+            SqlDialect::ClickHouse => format!("toUnixTimestamp({time_col})"),
+            SqlDialect::Postgres => format!("CAST(FLOOR(EXTRACT(EPOCH FROM {time_col})) AS BIGINT)"),
+            SqlDialect::DuckDb => format!("CAST(FLOOR(epoch({time_col})) AS BIGINT)"),
+        };
+        let seconds_per_week = SECONDS_PER_DAY * DAYS_PER_WEEK;
+        let seconds_of_day = format!("({unix_seconds} % {SECONDS_PER_DAY} + {SECONDS_PER_DAY}) % {SECONDS_PER_DAY}");
+
+        let weekend = match self.weekend {
+            // 1 January 1970 was a Thursday, so shifting by 4 days counts the seconds since Sunday 00:00 UTC
+            Some((start, end)) => {
+                let shift = 4 * SECONDS_PER_DAY;
+                let seconds_of_week = format!("(({unix_seconds} + {shift}) % {seconds_per_week} + {seconds_per_week}) % {seconds_per_week}");
+                match start <= end {
+                    true => format!("{seconds_of_week} >= {start} AND {seconds_of_week} < {end}"),
+                    false => format!("{seconds_of_week} >= {start} OR {seconds_of_week} < {end}"),
+                }
+            }
+            // Shifted by two hours, the weekend covers Saturday and Sunday, i.e. days 2 and 3 of a week starting on Thursday
+            None => {
+                let seconds_of_week = format!("(({unix_seconds} + {WEEKEND_SHIFT}) % {seconds_per_week} + {seconds_per_week}) % {seconds_per_week}");
+                format!("{seconds_of_week} >= {} AND {seconds_of_week} < {}", 2 * SECONDS_PER_DAY, 4 * SECONDS_PER_DAY)
+            }
+        };
+        let mut sql = format!("CASE WHEN {weekend} THEN '{}'", TradingSession::Weekend.as_str());
+        for window in &self.windows {
+            let condition = match window.start <= window.end {
+                true => format!("{seconds_of_day} >= {} AND {seconds_of_day} < {}", window.start, window.end),
+                false => format!("{seconds_of_day} >= {} OR {seconds_of_day} < {}", window.start, window.end),
+            };
+            sql.push_str(&format!(" WHEN {condition} THEN '{}'", window.session.as_str()));
+        }
+        sql.push_str(&format!(" ELSE '{}' END", TradingSession::Undefined.as_str()));
+        sql
+    }
+
+    /// Returns when a trading session opens and closes on a UTC date under the schedule, taking the
+    /// Forex weekend into account, see [`IdentifyTradingSession::bounds_on`].
+    ///
+    /// For DST and holidays, configure an identifier with the schedule instead, e.g.
+    /// `IdentifyTradingSession::new(0).with_schedule(schedule).with_dst()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{SessionSchedule, TradingSession};
+    ///
+    /// // Monday 11 March 2024, London from 09:00 to 13:00 UTC
+    /// let bounds = SessionSchedule::forex().bounds_on(TradingSession::London, (2024, 3, 11));
+    /// assert_eq!(bounds, Some((1710147600, 1710162000)));
+    ///
+    /// // A window wrapping around midnight closes on the next day
+    /// let schedule = SessionSchedule::new().add(TradingSession::Sydney, 79_200, 21_600);
+    /// assert_eq!(schedule.bounds_on(TradingSession::Sydney, (2024, 3, 11)), Some((1710194400, 1710223200)));
+    /// ```
+    pub fn bounds_on(&self, session: TradingSession, date: (i32, u32, u32)) -> Option<(i64, i64)> {
+        IdentifyTradingSession::new(0).with_schedule(self.clone()).bounds_on(session, date)
+    }
+
+    /// Serializes the schedule to a JSON document, tagged with [`JSON_SCHEMA_VERSION`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TradingSessionsError::Json` if the schedule cannot be serialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{SessionSchedule, TradingSession};
+    ///
+    /// let schedule = SessionSchedule::new().add(TradingSession::London, 28_800, 46_800);
+    /// let json = schedule.to_json().unwrap();
+    /// assert_eq!(json, r#"{"version":1,"windows":[{"session":"London","start":28800,"end":46800}]}"#);
+    /// assert_eq!(SessionSchedule::from_json(&json).unwrap(), schedule);
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> Result<String, TradingSessionsError> {
+        to_versioned_json(self)
+    }
+
+    /// Deserializes a schedule from a JSON document written by [`SessionSchedule::to_json`], and validates it.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TradingSessionsError::UnsupportedVersion` if the document is not of [`JSON_SCHEMA_VERSION`],
+    /// a `TradingSessionsError::Json` if it does not describe a schedule, or a
+    /// `TradingSessionsError::InvalidSchedule` if the schedule fails [`SessionSchedule::validate`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{SessionSchedule, TradingSessionsError};
+    ///
+    /// let error = SessionSchedule::from_json(r#"{"version":2,"windows":[]}"#).unwrap_err();
+    /// assert!(matches!(error, TradingSessionsError::UnsupportedVersion(Some(2))));
+    ///
+    /// let error = SessionSchedule::from_json(r#"{"version":1,"windows":[{"session":"London","start":0,"end":0}]}"#).unwrap_err();
+    /// assert!(matches!(error, TradingSessionsError::InvalidSchedule(_)));
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn from_json(json: &str) -> Result<Self, TradingSessionsError> {
+        let schedule: Self = from_versioned_json(json)?;
+        schedule.validate()?;
+        Ok(schedule)
+    }
+}
+
+
+/// Serializes a value to a JSON object with an additional "version" field of [`JSON_SCHEMA_VERSION`].
+#[cfg(feature = "json")]
+fn to_versioned_json<T: serde::Serialize>(value: &T) -> Result<String, TradingSessionsError> {
+    // The fields are spliced in as serialized, so they keep their declaration order after the version
+    let fields = serde_json::to_string(value)?;
+    let fields = fields.strip_prefix('{').unwrap_or(&fields);
+    match fields {
+        "}" => Ok(format!("{{\"version\":{JSON_SCHEMA_VERSION}}}")),
+        fields => Ok(format!("{{\"version\":{JSON_SCHEMA_VERSION},{fields}")),
+    }
+}
+
+
+/// Deserializes a value from a JSON object written by `to_versioned_json`, after checking its "version" field.
+#[cfg(feature = "json")]
+fn from_versioned_json<T: serde::de::DeserializeOwned>(json: &str) -> Result<T, TradingSessionsError> {
+    let mut document: serde_json::Value = serde_json::from_str(json)?;
+    let version = document.as_object_mut().and_then(|fields| fields.remove("version")).and_then(|version| version.as_u64());
+    if version != Some(u64::from(JSON_SCHEMA_VERSION)) {
+        return Err(TradingSessionsError::UnsupportedVersion(version));
+    }
+    Ok(serde_json::from_value(document)?)
+}
+
+
+/// The session windows of the default Forex schedule, as listed in [`SESSION_BOUNDARIES`].
+///
+/// Kept as a constant so identifying sessions against the default schedule never allocates.
+const FOREX_WINDOWS: [SessionWindow; SESSION_BOUNDARIES.len()] = forex_windows();
+
+
+/// Converts [`SESSION_BOUNDARIES`] to session windows in seconds since midnight UTC.
+const fn forex_windows() -> [SessionWindow; SESSION_BOUNDARIES.len()] {
+    let mut windows = [SessionWindow { session: TradingSession::Undefined, start: 0, end: 0 }; SESSION_BOUNDARIES.len()];
+    let mut index = 0;
+    while index < SESSION_BOUNDARIES.len() {
+        let (session, hours) = &SESSION_BOUNDARIES[index];
+        windows[index] = SessionWindow {
+            session: *session,
+            start: *hours.start() as u32 * SECONDS_PER_HOUR,
+            end: (*hours.end() as u32 + 1) * SECONDS_PER_HOUR,
+        };
+        index += 1;
+    }
+    windows
+}
+
+
+/// The session windows of the NYSE in Eastern Standard Time (UTC-5), see [`SessionSchedule::nyse`].
+const NYSE_WINDOWS: [SessionWindow; 4] = [
+    SessionWindow { session: TradingSession::Closed, start: 3_600, end: 32_400 },         // 01:00 - 09:00 UTC
+    SessionWindow { session: TradingSession::PreMarket, start: 32_400, end: 52_200 },     // 09:00 - 14:30 UTC
+    SessionWindow { session: TradingSession::Regular, start: 52_200, end: 75_600 },       // 14:30 - 21:00 UTC
+    SessionWindow { session: TradingSession::AfterHours, start: 75_600, end: 3_600 },     // 21:00 - 01:00 UTC
+];
+
+
+/// The weekend of the NYSE in Eastern Standard Time, from the after-hours close on Friday 20:00 to the
+/// pre-market open on Monday 04:00 ET, in seconds since Sunday 00:00 UTC, see [`SessionSchedule::nyse`].
+#[cfg(feature = "alloc")]
+const NYSE_WEEKEND: (u32, u32) = (6 * SECONDS_PER_DAY + SECONDS_PER_HOUR, SECONDS_PER_DAY + 9 * SECONDS_PER_HOUR);
+
+
+/// The session windows of CME Globex equity index futures in Central Standard Time (UTC-6), see [`SessionSchedule::cme_globex`].
+const CME_GLOBEX_WINDOWS: [SessionWindow; 4] = [
+    SessionWindow { session: TradingSession::Globex, start: 82_800, end: 52_200 },       // 23:00 - 14:30 UTC
+    SessionWindow { session: TradingSession::Regular, start: 52_200, end: 76_500 },      // 14:30 - 21:15 UTC
+    SessionWindow { session: TradingSession::Globex, start: 76_500, end: 79_200 },       // 21:15 - 22:00 UTC
+    SessionWindow { session: TradingSession::Closed, start: 79_200, end: 82_800 },       // 22:00 - 23:00 UTC
+];
+
+
+/// The names of the built-in presets of [`SessionSchedule::preset`].
+#[cfg(feature = "std")]
+const PRESET_NAMES: [&str; 5] = ["forex_utc", "forex_summer", "nyse", "cme", "crypto"];
+
+
+/// The presets registered with [`SessionSchedule::register_preset`], in order of registration.
+#[cfg(feature = "std")]
+static CUSTOM_PRESETS: std::sync::RwLock<Vec<(String, SessionSchedule)>> = std::sync::RwLock::new(Vec::new());
+
+
+/// Returns the built-in preset of the given name, see [`SessionSchedule::preset`].
+#[cfg(feature = "std")]
+fn builtin_preset(name: &str) -> Option<SessionSchedule> {
+    match name {
+        "forex_utc" => Some(SessionSchedule::forex()),
+        "forex_summer" => Some(SessionSchedule { windows: shifted_forex_windows(true, true).to_vec(), weekend: None }),
+        "nyse" => Some(SessionSchedule::nyse()),
+        "cme" => Some(SessionSchedule::cme_globex()),
+        "crypto" => Some(SessionSchedule::crypto()),
+        _ => None,
+    }
+}
+
+
+/// Shifts the boundaries of [`FOREX_WINDOWS`] set by London and New York an hour earlier while
+/// the respective city observes daylight saving time at the given Unix timestamp in seconds.
+fn summer_time_windows(unix_timestamp: i64) -> [SessionWindow; SESSION_BOUNDARIES.len()] {
+    shifted_forex_windows(is_london_summer_time(unix_timestamp), is_new_york_summer_time(unix_timestamp))
+}
+
+
+/// Shifts the boundaries of [`FOREX_WINDOWS`] set by London and New York, if the respective city observes summer time, an hour earlier.
+fn shifted_forex_windows(london: bool, new_york: bool) -> [SessionWindow; SESSION_BOUNDARIES.len()] {
+    let shift = |boundary: u32| match boundary / SECONDS_PER_HOUR {
+        // London opens at 07:00 and closes at 16:00, New York opens at 13:00 and closes at 22:00 UTC
+        7 | 16 if london => boundary - SECONDS_PER_HOUR,
+        13 | 22 if new_york => boundary - SECONDS_PER_HOUR,
+        _ => boundary,
+    };
+
+    FOREX_WINDOWS.map(|window| SessionWindow { start: shift(window.start), end: shift(window.end), ..window })
+}
+
+
+/// Builds the boolean expression evaluating a summer time rule on an expression of Unix timestamps in seconds.
+#[cfg(feature = "polars")]
+fn summer_time_expr(unix_seconds: Expr, is_summer_time: fn(i64) -> bool) -> Expr {
+    unix_seconds.cast(DataType::Int64).map(move |unix_seconds| {
+        let summer_time: BooleanChunked = unix_seconds.i64()?.into_iter().map(|unix_timestamp| unix_timestamp.map(is_summer_time)).collect();
+        Ok(Some(summer_time.with_name(unix_seconds.name()).into_series()))
+    }, GetOutput::from_type(DataType::Boolean))
+}
+
+
+/// Shifts every window an hour earlier while the US observes daylight saving time at the given Unix timestamp in seconds.
+#[cfg(feature = "alloc")]
+fn us_summer_time_windows<const N: usize>(windows: [SessionWindow; N], unix_timestamp: i64) -> [SessionWindow; N] {
+    match is_new_york_summer_time(unix_timestamp) {
+        true => windows.map(|window| SessionWindow {
+            start: (window.start + SECONDS_PER_DAY - SECONDS_PER_HOUR) % SECONDS_PER_DAY,
+            end: (window.end + SECONDS_PER_DAY - SECONDS_PER_HOUR) % SECONDS_PER_DAY,
+            ..window
+        }),
+        false => windows,
+    }
+}
+
+
+/// Shifts every weekend, in seconds since Sunday 00:00 UTC, an hour earlier while the US observes daylight
+/// saving time at the given Unix timestamp in seconds.
+#[cfg(feature = "alloc")]
+fn us_summer_time_weekends<const N: usize>(weekends: [(u32, u32); N], unix_timestamp: i64) -> [(u32, u32); N] {
+    let seconds_per_week = SECONDS_PER_DAY * DAYS_PER_WEEK;
+    match is_new_york_summer_time(unix_timestamp) {
+        true => weekends.map(|(start, end)| {
+            ((start + seconds_per_week - SECONDS_PER_HOUR) % seconds_per_week, (end + seconds_per_week - SECONDS_PER_HOUR) % seconds_per_week)
+        }),
+        false => weekends,
+    }
+}
+
+
+/// Returns whether the UK observes British Summer Time at the given Unix timestamp in seconds,
+/// i.e. from the last Sunday of March to the last Sunday of October at 01:00 UTC.
+fn is_london_summer_time(unix_timestamp: i64) -> bool {
+    let (year, _, _) = civil_from_days(unix_timestamp.div_euclid(i64::from(SECONDS_PER_DAY)));
+    let start = last_sunday(year, 3) * i64::from(SECONDS_PER_DAY) + i64::from(SECONDS_PER_HOUR);
+    let end = last_sunday(year, 10) * i64::from(SECONDS_PER_DAY) + i64::from(SECONDS_PER_HOUR);
+    (start..end).contains(&unix_timestamp)
+}
+
+
+/// Returns whether the US observes daylight saving time at the given Unix timestamp in seconds, i.e.
+/// from the second Sunday of March at 07:00 UTC to the first Sunday of November at 06:00 UTC.
+fn is_new_york_summer_time(unix_timestamp: i64) -> bool {
+    let (year, _, _) = civil_from_days(unix_timestamp.div_euclid(i64::from(SECONDS_PER_DAY)));
+    let start = (first_sunday(year, 3) + 7) * i64::from(SECONDS_PER_DAY) + 7 * i64::from(SECONDS_PER_HOUR);
+    let end = first_sunday(year, 11) * i64::from(SECONDS_PER_DAY) + 6 * i64::from(SECONDS_PER_HOUR);
+    (start..end).contains(&unix_timestamp)
+}
+
+
+/// Returns the first Sunday of the given month as a number of days since 1 January 1970.
+fn first_sunday(year: i32, month: u32) -> i64 {
+    let first = days_from_civil(year, month, 1);
+    first + (i64::from(DAYS_PER_WEEK) - i64::from(day_of_week(first * i64::from(SECONDS_PER_DAY)))) % i64::from(DAYS_PER_WEEK)
+}
+
+
+/// Returns the last Sunday of the given month, up to November, as a number of days since 1 January 1970.
+fn last_sunday(year: i32, month: u32) -> i64 {
+    let last = days_from_civil(year, month + 1, 1) - 1;
+    last - i64::from(day_of_week(last * i64::from(SECONDS_PER_DAY)))
+}
+
+
+/// Returns the first of the windows covering the given number of seconds since midnight UTC, if any.
+fn window_for_seconds(windows: &[SessionWindow], seconds_of_day: u32) -> Option<&SessionWindow> {
+    windows
+        .iter()
+        .find(|window| window.contains(seconds_of_day))
+}
+
+
+/// Returns the seconds since midnight UTC right before and right after the stretch of the day
+/// containing `seconds_of_day` over which none of the windows starts or ends.
+fn neighbouring_seconds(windows: &[SessionWindow], seconds_of_day: u32) -> (u32, u32) {
+    let boundaries = || {
+        windows
+            .iter()
+            .flat_map(|window| [window.start % SECONDS_PER_DAY, window.end % SECONDS_PER_DAY])
+    };
+
+    // Stretches without a boundary before or after them wrap around midnight
+    let opened = boundaries()
+        .filter(|&boundary| boundary <= seconds_of_day)
+        .max()
+        .or_else(|| boundaries().max())
+        .unwrap_or(seconds_of_day);
+    let closes = boundaries()
+        .filter(|&boundary| boundary > seconds_of_day)
+        .min()
+        .or_else(|| boundaries().min())
+        .unwrap_or(seconds_of_day);
+
+    ((opened + SECONDS_PER_DAY - 1) % SECONDS_PER_DAY, closes)
+}
+
+
+impl SessionBoundary {
+    /// Creates a new SessionBoundary at the given time of day in UTC.
+    ///
+    /// # Arguments
+    ///
+    /// * `hour` - The hour of the day, from 0 to 23.
+    /// * `minute` - The minute of the hour, from 0 to 59.
+    ///
+    /// # Returns
+    ///
+    /// A new SessionBoundary instance.
+    pub const fn new(hour: u8, minute: u8) -> Self {
+        Self { hour, minute }
+    }
+
+    /// Returns the boundary at the minute of the day a Unix timestamp in seconds falls in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::SessionBoundary;
+    ///
+    /// // Thursday 22 February 2024, 23:05:42 UTC
+    /// assert_eq!(SessionBoundary::from_timestamp(1708643142), SessionBoundary::new(23, 5));
+    /// ```
+    pub const fn from_timestamp(unix_timestamp: i64) -> Self {
+        let minute_of_day = unix_timestamp.rem_euclid(SECONDS_PER_DAY as i64) / 60;
+        Self::new((minute_of_day / 60) as u8, (minute_of_day % 60) as u8)
+    }
+
+    /// Returns the number of minutes elapsed since midnight UTC at the boundary.
+    pub const fn minute_of_day(&self) -> u32 {
+        self.hour as u32 * 60 + self.minute as u32
+    }
+
+    /// Returns the number of seconds elapsed since midnight UTC at the boundary.
+    pub const fn seconds_of_day(&self) -> u32 {
+        self.minute_of_day() * 60
+    }
+}
+
+
+impl core::fmt::Display for LengthMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "cannot classify {} timestamps into an output buffer of length {}", self.timestamps, self.out)
+    }
+}
+
+
+impl core::error::Error for LengthMismatch {}
+
+
+impl SessionWindow {
+    /// Returns whether the window covers the given number of seconds since midnight UTC.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{SessionWindow, TradingSession};
+    ///
+    /// let window = SessionWindow { session: TradingSession::Tokyo, start: 23 * 3600, end: 3 * 3600 };
+    /// assert!(window.contains(23 * 3600 + 1800));
+    /// assert!(window.contains(3600));
+    /// assert!(!window.contains(3 * 3600));
+    /// ```
+    pub fn contains(&self, seconds_of_day: u32) -> bool {
+        match self.start <= self.end {
+            true => (self.start..self.end).contains(&seconds_of_day),
+            // The window wraps midnight
+            false => seconds_of_day >= self.start || seconds_of_day < self.end,
+        }
+    }
+
+    /// Returns the length of the window in seconds, taking windows that wrap midnight into account.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{SessionWindow, TradingSession};
+    ///
+    /// let window = SessionWindow { session: TradingSession::Tokyo, start: 23 * 3600, end: 3 * 3600 };
+    /// assert_eq!(window.duration_secs(), 4 * 3600);
+    /// ```
+    pub fn duration_secs(&self) -> u32 {
+        (self.end + SECONDS_PER_DAY - self.start) % SECONDS_PER_DAY
+    }
+}
+
+
+#[cfg(feature = "alloc")]
+impl Default for SessionSchedule {
+    fn default() -> Self {
+        Self::forex()
+    }
+}
+
+
+#[cfg(feature = "alloc")]
+impl HolidayCalendar {
+    /// Creates a new HolidayCalendar from a list of `(year, month, day)` UTC dates.
+    ///
+    /// # Arguments
+    ///
+    /// * `dates` - The dates on which the market is closed, where the month and day start at 1.
+    ///
+    /// # Returns
+    ///
+    /// A new HolidayCalendar instance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::HolidayCalendar;
+    ///
+    /// let calendar = HolidayCalendar::new(&[(2024, 12, 25), (2024, 12, 26)]);
+    /// assert!(calendar.is_holiday(1735120800)); // 25 December 2024, 10:00 UTC
+    /// assert!(!calendar.is_holiday(1735293600)); // 27 December 2024, 10:00 UTC
+    /// ```
+    pub fn new(dates: &[(i32, u32, u32)]) -> Self {
+        let days = dates
+            .iter()
+            .map(|&(year, month, day)| days_from_civil(year, month, day))
+            .collect();
+        Self { days, early_closes: Vec::new(), late_opens: Vec::new() }
+    }
+
+    /// Creates a new HolidayCalendar with the fixed-date holidays of an exchange over a range of years.
+    ///
+    /// # Arguments
+    ///
+    /// * `exchange` - The exchange whose [`Exchange::fixed_holidays`] are loaded.
+    /// * `years` - The years to load the holidays for.
+    ///
+    /// # Returns
+    ///
+    /// A new HolidayCalendar instance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{Exchange, HolidayCalendar, IdentifyTradingSession, TradingSession};
+    ///
+    /// let calendar = HolidayCalendar::for_exchange(Exchange::LSE, 2024..=2025);
+    /// assert!(calendar.is_holiday(1735207200));  // Thursday 26 December 2024, 10:00 UTC
+    /// assert!(calendar.is_holiday(1735725600));  // Wednesday 1 January 2025, 10:00 UTC
+    /// assert!(!calendar.is_holiday(1735293600)); // Friday 27 December 2024, 10:00 UTC
+    ///
+    /// let session_identifier = IdentifyTradingSession::for_exchange(1735207200, Exchange::LSE).with_holidays(calendar);
+    /// assert_eq!(session_identifier.trading_session(), TradingSession::Holiday);
+    /// ```
+    pub fn for_exchange(exchange: Exchange, years: RangeInclusive<i32>) -> Self {
+        let days = years
+            .flat_map(|year| exchange.fixed_holidays().iter().map(move |&(month, day)| days_from_civil(year, month, day)))
+            .collect();
+        Self { days, early_closes: Vec::new(), late_opens: Vec::new() }
+    }
+
+    /// Adds an early close of a trading session to the calendar.
+    ///
+    /// # Arguments
+    ///
+    /// * `early_close` - The date, session and hour in UTC from which the session is closed.
+    ///
+    /// # Returns
+    ///
+    /// The HolidayCalendar instance including the early close.
+    pub fn with_early_close(mut self, early_close: EarlyClose) -> Self {
+        self.early_closes.push(early_close);
+        self
+    }
+
+    /// Returns whether the given session has closed early by the given Unix timestamp in seconds.
+    pub fn is_closed_early(&self, unix_timestamp: i64, session: TradingSession) -> bool {
+        let day = unix_timestamp.div_euclid(i64::from(SECONDS_PER_DAY));
+        self.early_closes.iter().any(|early_close| {
+            let (year, month, date) = early_close.date;
+            early_close.session == session
+                && days_from_civil(year, month, date) == day
+                && seconds_of_day(unix_timestamp) >= u32::from(early_close.close_hour) * SECONDS_PER_HOUR
+        })
+    }
+
+    /// Adds a late open of a trading session to the calendar.
+    ///
+    /// # Arguments
+    ///
+    /// * `late_open` - The date, session and hour in UTC until which the session is closed.
+    ///
+    /// # Returns
+    ///
+    /// The HolidayCalendar instance including the late open.
+    pub fn with_late_open(mut self, late_open: LateOpen) -> Self {
+        self.late_opens.push(late_open);
+        self
+    }
+
+    /// Returns whether the given session has yet to open late at the given Unix timestamp in seconds.
+    pub fn is_opened_late(&self, unix_timestamp: i64, session: TradingSession) -> bool {
+        let day = unix_timestamp.div_euclid(i64::from(SECONDS_PER_DAY));
+        self.late_opens.iter().any(|late_open| {
+            let (year, month, date) = late_open.date;
+            late_open.session == session
+                && days_from_civil(year, month, date) == day
+                && seconds_of_day(unix_timestamp) < u32::from(late_open.open_hour) * SECONDS_PER_HOUR
+        })
+    }
+
+    /// Returns whether an early close or late open keeps the given session closed at the given Unix timestamp in seconds.
+    fn is_overridden(&self, unix_timestamp: i64, session: TradingSession) -> bool {
+        self.is_closed_early(unix_timestamp, session) || self.is_opened_late(unix_timestamp, session)
+    }
+
+    /// Returns whether the given Unix timestamp in seconds falls on one of the holidays.
+    pub fn is_holiday(&self, unix_timestamp: i64) -> bool {
+        self.days.contains(&unix_timestamp.div_euclid(i64::from(SECONDS_PER_DAY)))
+    }
+
+    /// Computes the fraction of a time range during which the market is open, accounting for the
+    /// weekend as well as the holidays of the calendar.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The Unix timestamp in seconds at which the range starts, inclusive.
+    /// * `end` - The Unix timestamp in seconds at which the range ends, exclusive.
+    ///
+    /// # Returns
+    ///
+    /// The open fraction in `[0.0, 1.0]`, or 0.0 for an empty range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::HolidayCalendar;
+    ///
+    /// // Monday 23 December 2024 through Sunday 29 December 2024, with Christmas and Boxing Day
+    /// let calendar = HolidayCalendar::new(&[(2024, 12, 25), (2024, 12, 26)]);
+    /// let week = calendar.open_duty_cycle(1734912000, 1735516800);
+    /// assert!((week - 3.0 / 7.0).abs() < 1e-12);
+    /// ```
+    pub fn open_duty_cycle(&self, start: i64, end: i64) -> f64 {
+        duty_cycle(start, end, |unix_timestamp| self.is_holiday(unix_timestamp))
+    }
+
+    /// Serializes the calendar, including its early closes and late opens, to a JSON document tagged
+    /// with [`JSON_SCHEMA_VERSION`].
+    ///
+    /// The holidays are stored as days since 1 January 1970.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TradingSessionsError::Json` if the calendar cannot be serialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{EarlyClose, HolidayCalendar, TradingSession};
+    ///
+    /// let calendar = HolidayCalendar::new(&[(2024, 12, 25)]).with_early_close(EarlyClose {
+    ///     date: (2024, 12, 24),
+    ///     session: TradingSession::NewYork,
+    ///     close_hour: 18,
+    /// });
+    /// let json = calendar.to_json().unwrap();
+    /// assert!(json.starts_with(r#"{"version":1,"days":[20082],"early_closes":[{"date":[2024,12,24]"#));
+    /// assert_eq!(HolidayCalendar::from_json(&json).unwrap(), calendar);
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> Result<String, TradingSessionsError> {
+        to_versioned_json(self)
+    }
+
+    /// Deserializes a calendar from a JSON document written by [`HolidayCalendar::to_json`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TradingSessionsError::UnsupportedVersion` if the document is not of [`JSON_SCHEMA_VERSION`],
+    /// or a `TradingSessionsError::Json` if it does not describe a calendar.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{HolidayCalendar, TradingSessionsError};
+    ///
+    /// let calendar = HolidayCalendar::from_json(r#"{"version":1,"days":[20082],"early_closes":[]}"#).unwrap();
+    /// assert_eq!(calendar, HolidayCalendar::new(&[(2024, 12, 25)]));
+    ///
+    /// let error = HolidayCalendar::from_json(r#"{"days":[20082],"early_closes":[]}"#).unwrap_err();
+    /// assert_eq!(error.to_string(), "the JSON document has no schema version");
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn from_json(json: &str) -> Result<Self, TradingSessionsError> {
+        from_versioned_json(json)
+    }
+}
+
+
+#[cfg(feature = "alloc")]
+impl SessionAliases {
+    /// Creates an empty alias table, which only resolves the canonical session labels.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an alias table with the common names of the trading centers and their regions.
+    ///
+    /// Resolves "Asia" and "TYO" to Tokyo, "LDN" to London, "NY", "NYC" and "US" to New York,
+    /// and "SYD" to Sydney, on top of the canonical labels.
+    pub fn common() -> Self {
+        [
+            ("Asia", TradingSession::Tokyo),
+            ("TYO", TradingSession::Tokyo),
+            ("LDN", TradingSession::London),
+            ("NY", TradingSession::NewYork),
+            ("NYC", TradingSession::NewYork),
+            ("US", TradingSession::NewYork),
+            ("SYD", TradingSession::Sydney),
+        ]
+        .into_iter()
+        .fold(Self::new(), |aliases, (alias, session)| aliases.with_alias(alias, session))
+    }
+
+    /// Adds an alias of a trading session to the table.
+    ///
+    /// # Arguments
+    ///
+    /// * `alias` - The alternative name, matched case-insensitively and regardless of spaces, underscores and hyphens.
+    /// * `session` - The canonical trading session the alias resolves to.
+    ///
+    /// # Returns
+    ///
+    /// The SessionAliases instance including the alias.
+    pub fn with_alias(mut self, alias: &str, session: TradingSession) -> Self {
+        self.aliases.push((alias.to_string(), session));
+        self
+    }
+
+    /// Resolves a session label to its canonical trading session.
+    ///
+    /// # Returns
+    ///
+    /// The trading session of the canonical label or alias, or `None` if the label matches neither.
+    pub fn resolve(&self, label: &str) -> Option<TradingSession> {
+        TradingSession::ALL
+            .iter()
+            .map(|session| (session.as_str(), *session))
+            .chain(self.aliases.iter().map(|(alias, session)| (alias.as_str(), *session)))
+            .find(|(alias, _)| normalized_label(alias).eq(normalized_label(label)))
+            .map(|(_, session)| session)
+    }
+
+    /// Returns the first alias of a trading session in the table, e.g. to write the session in another naming convention.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{SessionAliases, TradingSession};
+    ///
+    /// let aliases = SessionAliases::common();
+    /// assert_eq!(aliases.alias_of(TradingSession::NewYork), Some("NY"));
+    /// assert_eq!(aliases.alias_of(TradingSession::TokyoLondon), None);
+    /// ```
+    pub fn alias_of(&self, session: TradingSession) -> Option<&str> {
+        self.aliases.iter().find(|(_, aliased)| *aliased == session).map(|(alias, _)| alias.as_str())
+    }
+
+    /// Registers an alias in the table in place, e.g. the session names of a broker while reading its configuration.
+    ///
+    /// This is the mutating form of [`SessionAliases::with_alias`]. The table is a plain value, so
+    /// the aliases only apply where it is passed, e.g. to [`SessionAliases::parse`].
+    ///
+    /// # Arguments
+    ///
+    /// * `alias` - The alternative name, matched case-insensitively and regardless of spaces, underscores and hyphens.
+    /// * `session` - The canonical trading session the alias resolves to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{SessionAliases, TradingSession};
+    ///
+    /// let mut aliases = SessionAliases::new();
+    /// aliases.register("EU", TradingSession::London);
+    /// assert_eq!(aliases.resolve("eu"), Some(TradingSession::London));
+    /// ```
+    pub fn register(&mut self, alias: &str, session: TradingSession) {
+        self.aliases.push((alias.to_string(), session));
+    }
+
+    /// Parses a trading session from a canonical label or an alias of the table, like `str::parse`
+    /// does for the canonical labels only.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParseSessionError` if the label matches neither a canonical label nor an alias.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{ParseSessionError, SessionAliases, SessionVerification, TradingSession};
+    ///
+    /// let aliases = SessionAliases::new().with_alias("EU", TradingSession::London);
+    /// assert_eq!(aliases.parse("eu"), Ok(TradingSession::London));
+    /// assert_eq!(aliases.parse("Frankfurt"), Err(ParseSessionError));
+    ///
+    /// // Parsing a TradingSession from a string, and with it SessionVerification::from_str, ignores any table
+    /// assert_eq!("EU".parse::<TradingSession>(), Err(ParseSessionError));
+    /// assert!(SessionVerification::from_str(1708596000, "EU").is_err());
+    /// assert!(SessionVerification::from_alias(1708596000, "EU", &aliases).unwrap().verify());
+    /// ```
+    pub fn parse(&self, label: &str) -> Result<TradingSession, ParseSessionError> {
+        self.resolve(label).ok_or(ParseSessionError)
+    }
+}
+
+
+/// Lowercases a session label and drops its spaces, underscores and hyphens, for comparing labels.
+#[cfg(feature = "alloc")]
+fn normalized_label(label: &str) -> impl Iterator<Item = char> + '_ {
+    label
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '_' | '-'))
+        .map(|c| c.to_ascii_lowercase())
+}
+
+
+impl Killzone {
+    /// Every killzone, in order of the start hour.
+    pub const ALL: &'static [Killzone] = &[Killzone::Asian, Killzone::LondonOpen, Killzone::NewYorkOpen, Killzone::LondonClose];
+
+    /// Returns the label of the killzone as used in the "Killzone" column.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::Killzone;
+    ///
+    /// assert_eq!(Killzone::LondonOpen.as_str(), "London_Open");
+    /// ```
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Killzone::Asian => "Asian",
+            Killzone::LondonOpen => "London_Open",
+            Killzone::NewYorkOpen => "NewYork_Open",
+            Killzone::LondonClose => "London_Close",
+        }
+    }
+}
+
+
+/// Killzones display as their label, as returned by `Killzone::as_str`.
+impl core::fmt::Display for Killzone {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+
+impl SessionSet {
+    /// Returns the hours of the day in UTC covered by each trading session of the set, inclusive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{SessionSet, SESSION_BOUNDARIES};
+    ///
+    /// assert_eq!(SessionSet::ThreeCenter.boundaries(), SESSION_BOUNDARIES);
+    /// assert_eq!(SessionSet::ForexFour.boundaries().len(), 7);
+    /// ```
+    pub const fn boundaries(&self) -> &'static [(TradingSession, RangeInclusive<u8>)] {
+        match self {
+            SessionSet::ThreeCenter => SESSION_BOUNDARIES,
+            SessionSet::ForexFour => FOREX_FOUR_BOUNDARIES,
+        }
+    }
+
+    /// Returns the schedule of session windows of the set, e.g. for [`IdentifyTradingSession::with_schedule`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{SessionSchedule, SessionSet, TradingSession};
+    ///
+    /// assert_eq!(SessionSet::ThreeCenter.schedule(), SessionSchedule::forex());
+    ///
+    /// // 03:00 UTC
+    /// assert_eq!(SessionSet::ForexFour.schedule().session_for_seconds(10_800), TradingSession::SydneyTokyo);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn schedule(&self) -> SessionSchedule {
+        let windows = self
+            .boundaries()
+            .iter()
+            .map(|(session, hours)| {
+                let seconds = boundary_seconds(hours);
+                SessionWindow { session: *session, start: seconds.start, end: seconds.end }
+            })
+            .collect();
+        SessionSchedule { windows, weekend: None }
+    }
+}
+
+
+impl TimestampUnit {
+    /// Returns the number of timestamp ticks per second in the unit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::TimestampUnit;
+    ///
+    /// assert_eq!(TimestampUnit::Seconds.ticks_per_second(), 1);
+    /// assert_eq!(TimestampUnit::Milliseconds.ticks_per_second(), 1_000);
+    /// ```
+    pub const fn ticks_per_second(&self) -> i64 {
+        match self {
+            TimestampUnit::Seconds => 1,
+            TimestampUnit::Milliseconds => 1_000,
+            TimestampUnit::Microseconds => 1_000_000,
+            TimestampUnit::Nanoseconds => 1_000_000_000,
+        }
+    }
+}
+
+
+impl SessionRangeIter {
+    /// Creates a new SessionRangeIter over the session boundaries in `[start, end)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The Unix timestamp in seconds at which the range starts, inclusive.
+    /// * `end` - The Unix timestamp in seconds at which the range ends, exclusive.
+    ///
+    /// # Returns
+    ///
+    /// A new SessionRangeIter instance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::SessionRangeIter;
+    ///
+    /// // The weekend lasts until the end of time, so no session opens in its last hours
+    /// assert_eq!(SessionRangeIter::new(i64::MAX - 7200, i64::MAX).count(), 0);
+    /// assert_eq!(SessionRangeIter::new(i64::MAX - 10, i64::MAX).count(), 0);
+    /// ```
+    pub fn new(start: i64, end: i64) -> Self {
+        // Sessions only change on the hour, so the first candidate boundary is the first full hour at or after `start`
+        let hour = i64::from(SECONDS_PER_HOUR);
+        let next = start.checked_add((hour - start.rem_euclid(hour)) % hour).unwrap_or(end);
+        Self { next, end }
+    }
+}
+
+
+impl Iterator for SessionRangeIter {
+    type Item = (i64, TradingSession);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next < self.end {
+            let boundary = self.next;
+            // Without a next hour before the extreme of i64, this boundary is the last one
+            self.next = boundary.checked_add(i64::from(SECONDS_PER_HOUR)).unwrap_or(self.end);
+
+            let session = session_at(boundary);
+            if session != session_at(boundary - 1) {
+                return Some((boundary, session));
+            }
+        }
+        None
+    }
+}
+
+
+impl SessionIntervals {
+    /// Renders the remaining intervals as an iCalendar (`.ics`) document, e.g. for a calendar subscription.
+    ///
+    /// Every trading session becomes an event from its open to its close in UTC, so daylight saving
+    /// time shifts and holidays are included as far as the identifier is configured with them.
+    /// Holidays become "Holiday" events, while the Weekend, Closed and Undefined intervals are left out.
+    ///
+    /// # Returns
+    ///
+    /// The iCalendar document, with CRLF line endings as required by RFC 5545.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{HolidayCalendar, IdentifyTradingSession, SessionSchedule};
+    ///
+    /// // Wednesday 3 July 2024 and Independence Day, Thursday 4 July 2024, in UTC
+    /// let ics = IdentifyTradingSession::new(1719964800)
+    ///     .with_schedule(SessionSchedule::nyse_at(1719964800))
+    ///     .with_holidays(HolidayCalendar::new(&[(2024, 7, 4)]))
+    ///     .sessions_until(1720137600)
+    ///     .to_ics();
+    ///
+    /// assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+    /// assert!(ics.contains("DTSTART:20240703T133000Z\r\nDTEND:20240703T200000Z\r\nSUMMARY:Regular\r\n"));
+    /// assert!(ics.contains("DTSTART:20240704T000000Z\r\nDTEND:20240705T000000Z\r\nSUMMARY:Holiday\r\n"));
+    /// assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn to_ics(self) -> String {
+        let mut ics = String::from("BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//trading_sessions//Trading Sessions//EN\r\nCALSCALE:GREGORIAN\r\n");
+        for (session, start, end) in self {
+            if matches!(session, TradingSession::Weekend | TradingSession::Closed | TradingSession::Undefined) {
+                continue;
+            }
+
+            ics.push_str("BEGIN:VEVENT\r\n");
+            ics.push_str(&["UID:", &start.to_string(), "-", session.as_str(), "@trading_sessions\r\n"].concat());
+            push_ics_timestamp(&mut ics, "DTSTAMP", start);
+            push_ics_timestamp(&mut ics, "DTSTART", start);
+            push_ics_timestamp(&mut ics, "DTEND", end);
+            ics.push_str(&["SUMMARY:", session.as_str(), "\r\n"].concat());
+            ics.push_str("END:VEVENT\r\n");
+        }
+        ics.push_str("END:VCALENDAR\r\n");
+        ics
+    }
+}
+
+
+/// Appends an iCalendar property with a Unix timestamp in seconds as a UTC date-time, e.g. `DTSTART:20240222T090000Z`.
+#[cfg(feature = "alloc")]
+fn push_ics_timestamp(ics: &mut String, property: &str, unix_timestamp: i64) {
+    use core::fmt::Write;
+
+    let (year, month, day) = civil_from_days(unix_timestamp.div_euclid(i64::from(SECONDS_PER_DAY)));
+    let seconds_of_day = seconds_of_day(unix_timestamp);
+    let (hour, minute, second) = (seconds_of_day / SECONDS_PER_HOUR, seconds_of_day % SECONDS_PER_HOUR / 60, seconds_of_day % 60);
+    // Writing to a String never fails
+    let _ = write!(ics, "{property}:{year:04}{month:02}{day:02}T{hour:02}{minute:02}{second:02}Z\r\n");
+}
+
+
+impl Iterator for SessionIntervals {
+    type Item = (TradingSession, i64, i64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= self.end {
+            return None;
+        }
+
+        let start = self.next;
+        let end = self.identifier.next_session_at(start).map_or(self.end, |(_, boundary)| boundary.min(self.end));
+        self.next = end;
+        Some((self.identifier.trading_session_at(start), start, end))
+    }
+}
+
+
+impl SessionResult {
+    /// Creates a new SessionResult by identifying the trading session of the given Unix timestamp.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestamp` - A 64-bit signed integer representing the Unix timestamp in seconds, which may be negative.
+    ///
+    /// # Returns
+    ///
+    /// A new SessionResult instance.
+    ///
+    /// # Examples
+    ///
+    /// With the `serde` feature enabled, a result round-trips through JSON:
+    ///
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use trading_sessions::{SessionResult, TradingSession, SESSION_BOUNDARIES};
+    ///
+    /// let result = SessionResult::new(1708596000);
+    /// let json = serde_json::to_string(&result).unwrap();
+    /// assert_eq!(json, r#"{"timestamp":1708596000,"session":"London"}"#);
+    /// assert_eq!(serde_json::from_str::<SessionResult>(&json).unwrap(), result);
+    ///
+    /// let session: TradingSession = serde_json::from_str(r#""London""#).unwrap();
+    /// assert_eq!(session, TradingSession::London);
+    /// # }
+    /// ```
+    pub fn new(timestamp: i64) -> Self {
+        Self {
+            timestamp,
+            session: IdentifyTradingSession::new(timestamp).trading_session(),
+        }
+    }
+}
+
+
+
+impl TradingSession {
+    /// All trading sessions, in their declaration order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::TradingSession;
+    ///
+    /// assert_eq!(TradingSession::ALL.first(), Some(&TradingSession::Tokyo));
+    /// assert!(TradingSession::ALL.windows(2).all(|pair| pair[0] < pair[1]));
+    /// ```
+    pub const ALL: &'static [TradingSession] = &[
+        TradingSession::Tokyo,
+        TradingSession::TokyoLondon,
+        TradingSession::London,
+        TradingSession::LondonNewYork,
+        TradingSession::NewYork,
+        TradingSession::Sydney,
+        TradingSession::SydneyTokyo,
+        TradingSession::PreMarket,
+        TradingSession::Regular,
+        TradingSession::AfterHours,
+        TradingSession::Globex,
+        TradingSession::Weekend,
+        TradingSession::Closed,
+        TradingSession::Holiday,
+        TradingSession::Undefined,
+    ];
+
+    /// Returns the sessions of the daily schedule, in their chronological order.
+    ///
+    /// The sessions outside of the daily schedule, i.e. Weekend, Closed, Holiday and Undefined,
+    /// are left out, see [`TradingSession::all_including_meta`] for the full set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{SessionSchedule, TradingSession};
+    ///
+    /// assert_eq!(TradingSession::all().len(), 5);
+    /// assert!(!TradingSession::all().contains(&TradingSession::Weekend));
+    ///
+    /// // The default schedule covers every session of the daily schedule
+    /// let schedule = SessionSchedule::forex();
+    /// assert!(TradingSession::all().iter().all(|session| schedule.duration_secs(*session) > 0));
+    /// ```
+    pub const fn all() -> &'static [TradingSession] {
+        &[
+            TradingSession::Tokyo,
+            TradingSession::TokyoLondon,
+            TradingSession::London,
+            TradingSession::LondonNewYork,
+            TradingSession::NewYork,
+        ]
+    }
+
+    /// Returns all trading sessions, including the ones outside of the daily schedule.
+    ///
+    /// This is the same as [`TradingSession::ALL`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::TradingSession;
+    ///
+    /// assert!(TradingSession::all_including_meta().starts_with(TradingSession::all()));
+    /// assert_eq!(TradingSession::all_including_meta().last(), Some(&TradingSession::Undefined));
+    /// ```
+    pub const fn all_including_meta() -> &'static [TradingSession] {
+        Self::ALL
+    }
+
+    /// Returns the label of the trading session as used in the "Session" column.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::TradingSession;
+    ///
+    /// assert_eq!(TradingSession::Tokyo.as_str(), "Tokyo");
+    /// assert_eq!(TradingSession::TokyoLondon.as_str(), "Tokyo_London");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TradingSession::Tokyo => "Tokyo",
+            TradingSession::TokyoLondon => "Tokyo_London",
+            TradingSession::London => "London",
+            TradingSession::LondonNewYork => "London_NewYork",
+            TradingSession::NewYork => "NewYork",
+            TradingSession::Sydney => "Sydney",
+            TradingSession::SydneyTokyo => "Sydney_Tokyo",
+            TradingSession::PreMarket => "PreMarket",
+            TradingSession::Regular => "Regular",
+            TradingSession::AfterHours => "AfterHours",
+            TradingSession::Globex => "Globex",
+            TradingSession::Weekend => "Weekend",
+            TradingSession::Closed => "Closed",
+            TradingSession::Holiday => "Holiday",
+            TradingSession::Undefined => "Undefined",
+        }
+    }
+
+    /// Returns the numeric ID of the trading session, as used in the session column with [`SessionColumn::with_session_ids`](crate::SessionColumn::with_session_ids).
+    ///
+    /// The ID is the position of the session in [`TradingSession::ALL`], e.g. 0 for Tokyo and 1 for Tokyo_London.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::TradingSession;
+    ///
+    /// assert_eq!(TradingSession::Tokyo.id(), 0);
+    /// assert_eq!(TradingSession::Undefined.id(), 14);
+    /// assert!(TradingSession::ALL.iter().all(|session| TradingSession::ALL[usize::from(session.id())] == *session));
+    /// ```
+    pub const fn id(&self) -> u8 {
+        // The variants are declared in the order of `TradingSession::ALL`
+        *self as u8
+    }
+
+    /// Returns the trading session of a numeric ID, as returned by [`TradingSession::id`].
+    ///
+    /// # Returns
+    ///
+    /// The trading session, or `None` if the ID matches no trading session.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::TradingSession;
+    ///
+    /// assert_eq!(TradingSession::from_id(2), Some(TradingSession::London));
+    /// assert_eq!(TradingSession::from_id(200), None);
+    /// ```
+    pub fn from_id(id: u8) -> Option<TradingSession> {
+        Self::ALL.get(usize::from(id)).copied()
+    }
+
+    /// Parses a trading session from its label, as returned by [`TradingSession::as_str`].
+    ///
+    /// Unlike parsing with `str::parse`, the error carries the label, e.g. to report it with the
+    /// other errors of a [`SessionColumn`](crate::SessionColumn) pipeline.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TradingSessionsError::UnknownSession` if the label matches no trading session.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::TradingSession;
+    ///
+    /// assert_eq!(TradingSession::from_label("Tokyo_London").unwrap(), TradingSession::TokyoLondon);
+    /// assert_eq!(TradingSession::from_label("Lnodon").unwrap_err().to_string(), "'Lnodon' matches no trading session");
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn from_label(label: &str) -> Result<Self, TradingSessionsError> {
+        label.parse().map_err(|_: ParseSessionError| TradingSessionsError::UnknownSession(label.to_string()))
+    }
+
+    /// Returns the first hour of the day in UTC covered by the trading session.
+    ///
+    /// The Weekend covers the whole day, so it starts at hour 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::TradingSession;
+    ///
+    /// assert_eq!(TradingSession::London.start_hour(), 9);
+    /// assert_eq!(TradingSession::Undefined.start_hour(), 22);
+    /// ```
+    pub fn start_hour(&self) -> u8 {
+        self.hours().0
+    }
+
+    /// Returns the last hour of the day in UTC covered by the trading session, inclusive.
+    ///
+    /// The Weekend covers the whole day, so it ends at hour 23. For a session wrapping around midnight,
+    /// e.g. AfterHours from 21:00 to 01:00 UTC, the last hour comes before the first one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::TradingSession;
+    ///
+    /// assert_eq!(TradingSession::London.end_hour(), 12);
+    /// assert_eq!(TradingSession::NewYork.end_hour(), 21);
+    /// assert_eq!((TradingSession::AfterHours.start_hour(), TradingSession::AfterHours.end_hour()), (21, 0));
+    /// ```
+    pub fn end_hour(&self) -> u8 {
+        self.hours().1
+    }
+
+    /// Returns the hour of the day in UTC at which the trading session opens, i.e. its first hour.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::TradingSession;
+    ///
+    /// assert_eq!(TradingSession::London.open_hour_utc(), 9);
+    /// assert_eq!(TradingSession::NewYork.open_hour_utc(), 16);
+    /// ```
+    pub fn open_hour_utc(&self) -> u8 {
+        self.start_hour()
+    }
+
+    /// Returns the hour of the day in UTC at which the trading session closes, i.e. the hour after its last hour.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::TradingSession;
+    ///
+    /// assert_eq!(TradingSession::London.close_hour_utc(), 13);
+    /// assert_eq!(TradingSession::NewYork.close_hour_utc(), 22);
+    /// ```
+    pub fn close_hour_utc(&self) -> u8 {
+        self.end_hour() + 1
+    }
+
+    /// Returns the scheduled length of the trading session, see [`TradingSession::duration_secs`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::time::Duration;
+    /// use trading_sessions::TradingSession;
+    ///
+    /// assert_eq!(TradingSession::London.duration(), Duration::from_secs(4 * 3600));
+    /// assert_eq!(TradingSession::Weekend.duration(), Duration::ZERO);
+    /// ```
+    pub fn duration(&self) -> core::time::Duration {
+        core::time::Duration::from_secs(u64::from(self.duration_secs()))
+    }
+
+    /// Returns the financial center the trading session is named after, e.g. "New York".
+    ///
+    /// Overlaps name both centers, and the sessions outside of the daily schedule have no center.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::TradingSession;
+    ///
+    /// assert_eq!(TradingSession::NewYork.market_center(), "New York");
+    /// assert_eq!(TradingSession::TokyoLondon.market_center(), "Tokyo / London");
+    /// assert_eq!(TradingSession::Globex.market_center(), "Chicago");
+    /// assert_eq!(TradingSession::Weekend.market_center(), "");
+    /// ```
+    pub fn market_center(&self) -> &'static str {
+        match self {
+            TradingSession::Tokyo => "Tokyo",
+            TradingSession::TokyoLondon => "Tokyo / London",
+            TradingSession::London => "London",
+            TradingSession::LondonNewYork => "London / New York",
+            TradingSession::NewYork | TradingSession::PreMarket | TradingSession::Regular | TradingSession::AfterHours => "New York",
+            TradingSession::Sydney => "Sydney",
+            TradingSession::SydneyTokyo => "Sydney / Tokyo",
+            TradingSession::Globex => "Chicago",
+            TradingSession::Weekend | TradingSession::Closed | TradingSession::Holiday | TradingSession::Undefined => "",
+        }
+    }
+
+    /// Returns the IANA timezone of the financial center of the trading session, e.g. "Europe/London".
+    ///
+    /// # Returns
+    ///
+    /// The timezone identifier, or `None` for overlaps, which span two timezones, and for the
+    /// sessions outside of the daily schedule.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::TradingSession;
+    ///
+    /// assert_eq!(TradingSession::London.timezone(), Some("Europe/London"));
+    /// assert_eq!(TradingSession::Regular.timezone(), Some("America/New_York"));
+    /// assert_eq!(TradingSession::LondonNewYork.timezone(), None);
+    /// ```
+    pub fn timezone(&self) -> Option<&'static str> {
+        match self {
+            TradingSession::Tokyo => Some("Asia/Tokyo"),
+            TradingSession::London => Some("Europe/London"),
+            TradingSession::NewYork | TradingSession::PreMarket | TradingSession::Regular | TradingSession::AfterHours => {
+                Some("America/New_York")
+            }
+            TradingSession::Sydney => Some("Australia/Sydney"),
+            TradingSession::Globex => Some("America/Chicago"),
+            TradingSession::TokyoLondon | TradingSession::LondonNewYork | TradingSession::SydneyTokyo
+            | TradingSession::Weekend | TradingSession::Closed | TradingSession::Holiday | TradingSession::Undefined => None,
+        }
+    }
+
+    /// Returns whether the trading session is an overlap of two major trading centers, during which
+    /// liquidity is typically highest.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::TradingSession;
+    ///
+    /// assert!(TradingSession::LondonNewYork.is_overlap());
+    /// assert!(!TradingSession::NewYork.is_overlap());
+    /// ```
+    pub fn is_overlap(&self) -> bool {
+        matches!(self, TradingSession::SydneyTokyo | TradingSession::TokyoLondon | TradingSession::LondonNewYork)
+    }
+
+    /// Returns the trading sessions that are active during this one, splitting an overlap into the
+    /// two concurrent sessions it consists of.
+    ///
+    /// The sessions outside of the daily schedule, i.e. Weekend, Closed, Holiday and Undefined, have no active sessions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::TradingSession;
+    ///
+    /// assert_eq!(TradingSession::TokyoLondon.constituents(), [TradingSession::Tokyo, TradingSession::London]);
+    /// assert_eq!(TradingSession::NewYork.constituents(), [TradingSession::NewYork]);
+    /// assert!(TradingSession::Weekend.constituents().is_empty());
+    /// ```
+    pub const fn constituents(&self) -> &'static [TradingSession] {
+        match self {
+            TradingSession::Tokyo => &[TradingSession::Tokyo],
+            TradingSession::TokyoLondon => &[TradingSession::Tokyo, TradingSession::London],
+            TradingSession::London => &[TradingSession::London],
+            TradingSession::LondonNewYork => &[TradingSession::London, TradingSession::NewYork],
+            TradingSession::NewYork => &[TradingSession::NewYork],
+            TradingSession::Sydney => &[TradingSession::Sydney],
+            TradingSession::SydneyTokyo => &[TradingSession::Sydney, TradingSession::Tokyo],
+            TradingSession::PreMarket => &[TradingSession::PreMarket],
+            TradingSession::Regular => &[TradingSession::Regular],
+            TradingSession::AfterHours => &[TradingSession::AfterHours],
+            TradingSession::Globex => &[TradingSession::Globex],
+            TradingSession::Weekend | TradingSession::Closed | TradingSession::Holiday | TradingSession::Undefined => &[],
+        }
+    }
+
+    /// Returns the length of the trading session in seconds under the default schedule, under
+    /// [`FOREX_FOUR_BOUNDARIES`] for the Sydney sessions, under [`SessionSchedule::nyse`] for the NYSE sessions,
+    /// or under [`SessionSchedule::cme_globex`] for the Globex session.
+    ///
+    /// The sessions outside of the daily schedule, i.e. Weekend, Closed, Holiday and Undefined, last 0 seconds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::TradingSession;
+    ///
+    /// assert_eq!(TradingSession::Tokyo.duration_secs(), 25_200);
+    /// assert_eq!(TradingSession::LondonNewYork.duration_secs(), 10_800);
+    /// assert_eq!(TradingSession::Undefined.duration_secs(), 0);
+    /// assert_eq!(TradingSession::SydneyTokyo.duration_secs(), 21_600);
+    /// assert_eq!(TradingSession::Regular.duration_secs(), 23_400);
+    /// assert_eq!(TradingSession::Globex.duration_secs(), 58_500);
+    /// ```
+    pub fn duration_secs(&self) -> u32 {
+        match self {
+            TradingSession::Weekend | TradingSession::Closed | TradingSession::Holiday | TradingSession::Undefined => 0,
+            TradingSession::PreMarket | TradingSession::Regular | TradingSession::AfterHours => NYSE_WINDOWS
+                .iter()
+                .find(|window| window.session == *self)
+                .map_or(0, SessionWindow::duration_secs),
+            TradingSession::Globex => CME_GLOBEX_WINDOWS
+                .iter()
+                .filter(|window| window.session == *self)
+                .map(SessionWindow::duration_secs)
+                .sum(),
+            session => (u32::from(session.end_hour()) + 1 - u32::from(session.start_hour())) * SECONDS_PER_HOUR,
+        }
+    }
+
+    /// Returns the market context of the trading session, e.g. for display or reports.
+    ///
+    /// All values are static, so no allocation takes place. The sessions outside of the daily
+    /// schedule have no primary exchange and no typical pairs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::TradingSession;
+    ///
+    /// let metadata = TradingSession::Tokyo.metadata();
+    /// assert_eq!(metadata.name, "Tokyo");
+    /// assert_eq!(metadata.primary_exchange, "Tokyo Stock Exchange");
+    /// assert!(metadata.typical_pairs.contains(&"USDJPY"));
+    /// assert_eq!((metadata.open_hour_utc, metadata.close_hour_utc), (0, 7));
+    /// assert_eq!((metadata.market_center, metadata.timezone), ("Tokyo", Some("Asia/Tokyo")));
+    /// ```
+    pub fn metadata(&self) -> SessionMetadata {
+        let (primary_exchange, typical_pairs): (&'static str, &'static [&'static str]) = match self {
+            TradingSession::Tokyo => ("Tokyo Stock Exchange", &["USDJPY", "AUDUSD", "NZDUSD"]),
+            TradingSession::TokyoLondon => ("Tokyo Stock Exchange / London Stock Exchange", &["EURJPY", "GBPJPY"]),
+            TradingSession::London => ("London Stock Exchange", &["EURUSD", "GBPUSD", "EURGBP"]),
+            TradingSession::LondonNewYork => ("London Stock Exchange / New York Stock Exchange", &["EURUSD", "GBPUSD", "USDCHF"]),
+            TradingSession::NewYork => ("New York Stock Exchange", &["EURUSD", "USDCAD", "USDJPY"]),
+            TradingSession::Sydney => ("Australian Securities Exchange", &["AUDUSD", "NZDUSD", "AUDNZD"]),
+            TradingSession::SydneyTokyo => ("Australian Securities Exchange / Tokyo Stock Exchange", &["AUDJPY", "NZDJPY"]),
+            TradingSession::PreMarket | TradingSession::Regular | TradingSession::AfterHours => {
+                ("New York Stock Exchange", &["SPY", "QQQ", "DIA"])
+            }
+            TradingSession::Globex => ("CME Globex", &["ES", "NQ", "CL"]),
+            TradingSession::Weekend | TradingSession::Closed | TradingSession::Holiday | TradingSession::Undefined => ("", &[]),
+        };
+
+        SessionMetadata {
+            name: self.as_str(),
+            primary_exchange,
+            typical_pairs,
+            open_hour_utc: self.open_hour_utc(),
+            close_hour_utc: self.close_hour_utc(),
+            market_center: self.market_center(),
+            timezone: self.timezone(),
+        }
+    }
+
+    /// Returns the typical relative liquidity of the trading session, e.g. to weight signals by session.
+    ///
+    /// # Returns
+    ///
+    /// The weight, from 0.0 when the market is closed to 1.0 for the London/New York overlap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::TradingSession;
+    ///
+    /// assert_eq!(TradingSession::LondonNewYork.liquidity_weight(), 1.0);
+    /// assert!(TradingSession::London.liquidity_weight() > TradingSession::Tokyo.liquidity_weight());
+    /// assert_eq!(TradingSession::Weekend.liquidity_weight(), 0.0);
+    /// ```
+    pub fn liquidity_weight(&self) -> f64 {
+        match self {
+            TradingSession::Tokyo => 0.5,
+            TradingSession::TokyoLondon => 0.7,
+            TradingSession::London => 0.9,
+            TradingSession::LondonNewYork => 1.0,
+            TradingSession::NewYork => 0.8,
+            TradingSession::Sydney => 0.3,
+            TradingSession::SydneyTokyo => 0.5,
+            TradingSession::PreMarket => 0.2,
+            TradingSession::Regular => 0.8,
+            TradingSession::AfterHours => 0.2,
+            TradingSession::Globex => 0.5,
+            TradingSession::Weekend | TradingSession::Closed | TradingSession::Holiday => 0.0,
+            TradingSession::Undefined => 0.2,
+        }
+    }
+
+    /// Lists the hours of the day in UTC mapped to the trading session under the default schedule.
+    ///
+    /// The hours are taken from [`SESSION_BOUNDARIES`], which the identification is based on as
+    /// well. Sessions outside of the daily schedule, e.g. Weekend, cover no hours.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::TradingSession;
+    ///
+    /// assert_eq!(TradingSession::London.utc_hours(), [9, 10, 11, 12]);
+    /// assert!(TradingSession::Weekend.utc_hours().is_empty());
+    ///
+    /// // Every hour of the day belongs to exactly one session
+    /// let mut hours: Vec<u32> = TradingSession::ALL.iter().flat_map(TradingSession::utc_hours).collect();
+    /// hours.sort();
+    /// assert_eq!(hours, (0..=23).collect::<Vec<u32>>());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn utc_hours(&self) -> Vec<u32> {
+        SESSION_BOUNDARIES
+            .iter()
+            .filter(|(session, _)| session == self)
+            .flat_map(|(_, hours)| hours.clone().map(u32::from))
+            .collect()
+    }
+
+    /// Returns the first and the last hour of the day in UTC covered by the trading session, where
+    /// the last hour comes before the first one for a session wrapping around midnight.
+    fn hours(&self) -> (u8, u8) {
+        // The NYSE and Globex sessions open and close on the half or quarter hour, so the hours they
+        // touch are listed, starting from the first window of the session
+        if let Some(window) = NYSE_WINDOWS.iter().chain(&CME_GLOBEX_WINDOWS).find(|window| window.session == *self) {
+            let last_second = (window.end + SECONDS_PER_DAY - 1) % SECONDS_PER_DAY;
+            return ((window.start / SECONDS_PER_HOUR) as u8, (last_second / SECONDS_PER_HOUR) as u8);
+        }
+
+        // The Sydney sessions are only part of the four-center model
+        SESSION_BOUNDARIES
+            .iter()
+            .chain(FOREX_FOUR_BOUNDARIES)
+            .find(|(session, _)| session == self)
+            .map_or((0, 23), |(_, hours)| (*hours.start(), *hours.end()))
+    }
+}
+
+
+
+impl core::str::FromStr for TradingSession {
+    type Err = ParseSessionError;
+
+    /// Parses a trading session from its label, as returned by `TradingSession::as_str`.
+    ///
+    /// Alternative names are resolved explicitly with [`SessionAliases::parse`] instead.
+    fn from_str(label: &str) -> Result<Self, Self::Err> {
+        TradingSession::ALL
+            .iter()
+            .find(|session| session.as_str() == label)
+            .copied()
+            .ok_or(ParseSessionError)
+    }
+}
+
+
+/// Sessions convert from their label, as returned by `TradingSession::as_str`, just like `FromStr`.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{ParseSessionError, TradingSession};
+///
+/// assert_eq!(TradingSession::try_from("Tokyo_London"), Ok(TradingSession::TokyoLondon));
+/// assert_eq!(TradingSession::try_from("tokyo"), Err(ParseSessionError));
+/// assert!(ParseSessionError.to_string().starts_with("unknown trading session label, expected one of Tokyo, "));
+/// ```
+impl TryFrom<&str> for TradingSession {
+    type Error = ParseSessionError;
+
+    fn try_from(label: &str) -> Result<Self, Self::Error> {
+        label.parse()
+    }
+}
+
+
+/// Sessions display as their label, as returned by `TradingSession::as_str`.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::TradingSession;
+///
+/// assert_eq!(TradingSession::LondonNewYork.to_string(), "London_NewYork");
+/// ```
+impl core::fmt::Display for TradingSession {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+
+/// Sessions compare equal to their label, as returned by `TradingSession::as_str`.
+///
+/// # Examples
+///
+/// ```
+/// use trading_sessions::{IdentifyTradingSession, TradingSession};
+///
+/// assert_eq!(TradingSession::TokyoLondon, "Tokyo_London");
+/// assert_ne!(IdentifyTradingSession::new(1708574400).identify_trading_session(), "London");
+/// ```
+impl PartialEq<&str> for TradingSession {
+    fn eq(&self, label: &&str) -> bool {
+        self.as_str() == *label
+    }
+}
+
+
+impl core::fmt::Display for ParseSessionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("unknown trading session label, expected one of ")?;
+        for (index, session) in TradingSession::ALL.iter().enumerate() {
+            match index {
+                0 => f.write_str(session.as_str())?,
+                _ => write!(f, ", {session}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+
+impl core::error::Error for ParseSessionError {}
+
+
+impl core::fmt::Display for ScheduleIssue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ScheduleIssue::OutOfRange(window) => write!(
+                f, "the {} window from {} to {} seconds does not lie within a day", window.session, window.start, window.end),
+            ScheduleIssue::ZeroLength(window) => write!(f, "the {} window from {} to {} seconds is empty", window.session, window.start, window.end),
+            ScheduleIssue::Overlap { first, second, start, end } => {
+                write!(f, "the {} and {} windows overlap from {start} to {end} seconds", first.session, second.session)
+            }
+            ScheduleIssue::Gap { start, end } => write!(f, "no window covers {start} to {end} seconds"),
+        }
+    }
+}
+
+
+#[cfg(feature = "alloc")]
+impl core::fmt::Display for TradingSessionsError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            TradingSessionsError::MissingColumn(column) => write!(f, "SessionColumn requires a '{column}' column"),
+            TradingSessionsError::WrongDtype { column, expected } => {
+                write!(f, "SessionColumn requires a '{column}' column of {expected} type")
+            }
+            TradingSessionsError::InvalidSchedule(window) => write!(
+                f, "the {} window from {} to {} seconds does not lie within a day", window.session, window.start, window.end),
+            TradingSessionsError::UnknownSession(label) => write!(f, "'{label}' matches no trading session"),
+            TradingSessionsError::UnknownPreset(name) => write!(f, "'{name}' matches no schedule preset"),
+            #[cfg(feature = "polars")]
+            TradingSessionsError::DstWithSchedule => {
+                write!(f, "daylight saving time only shifts the default schedule, not a custom one")
+            }
+            #[cfg(feature = "polars")]
+            TradingSessionsError::Polars(error) => write!(f, "{error}"),
+            #[cfg(feature = "json")]
+            TradingSessionsError::Json(error) => write!(f, "{error}"),
+            #[cfg(feature = "json")]
+            TradingSessionsError::UnsupportedVersion(Some(version)) => {
+                write!(f, "JSON schema version {version} is not supported, expected version {JSON_SCHEMA_VERSION}")
+            }
+            #[cfg(feature = "json")]
+            TradingSessionsError::UnsupportedVersion(None) => write!(f, "the JSON document has no schema version"),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::error::Error for TradingSessionsError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "polars")]
+            TradingSessionsError::Polars(error) => Some(error),
+            #[cfg(feature = "json")]
+            TradingSessionsError::Json(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "polars")]
+impl From<PolarsError> for TradingSessionsError {
+    fn from(error: PolarsError) -> Self {
+        TradingSessionsError::Polars(error)
+    }
+}
+
+#[cfg(feature = "json")]
+impl From<serde_json::Error> for TradingSessionsError {
+    fn from(error: serde_json::Error) -> Self {
+        TradingSessionsError::Json(error)
+    }
+}
+
+
+impl core::fmt::Display for InvalidTimestamp {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid Unix timestamp")
+    }
+}
+
+
+impl core::error::Error for InvalidTimestamp {}
+
+
+impl SessionVerification {
+    /// Creates a new SessionVerification instance with the given Unix timestamp and trading session.
+    ///
+    /// # Arguments
+    ///
+    /// * `unix_timestamp` - A 64-bit signed integer representing the Unix timestamp in seconds, which may be negative.
+    /// * `session` - The trading session to verify against.
+    ///
+    /// # Returns
+    ///
+    /// A new SessionVerification instance.
+    pub fn new(unix_timestamp: i64, session: TradingSession) -> Self {
+        Self { unix_timestamp, session }
+    }
+
+    /// Creates a new SessionVerification instance from a `chrono` UTC datetime and trading session.
+    ///
+    /// Requires the `chrono` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `dt` - A UTC datetime, stored as its Unix timestamp in seconds.
+    /// * `session` - The trading session to verify against.
+    ///
+    /// # Returns
+    ///
+    /// A new SessionVerification instance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use trading_sessions::{SessionVerification, TradingSession};
+    ///
+    /// let dt = Utc.with_ymd_and_hms(2024, 2, 22, 10, 0, 0).unwrap();
+    /// assert!(SessionVerification::from_datetime(dt, TradingSession::London).verify());
+    /// assert!(!SessionVerification::from_datetime(dt, TradingSession::Tokyo).verify());
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn from_datetime(dt: chrono::DateTime<chrono::Utc>, session: TradingSession) -> Self {
+        Self::new(dt.timestamp(), session)
+    }
+
+    /// Creates a new SessionVerification instance with the given Unix timestamp and session label.
+    ///
+    /// # Arguments
+    ///
+    /// * `unix_timestamp` - A 64-bit signed integer representing the Unix timestamp in seconds, which may be negative.
+    /// * `session` - The label of the trading session, as returned by `TradingSession::as_str`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParseSessionError` if the label matches no trading session.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{SessionVerification, TradingSession};
+    ///
+    /// let verifier = SessionVerification::from_str(1708574400, "Tokyo").unwrap();
+    /// assert_eq!(verifier.session, TradingSession::Tokyo);
+    /// ```
+    pub fn from_str(unix_timestamp: i64, session: &str) -> Result<Self, ParseSessionError> {
+        Ok(Self::new(unix_timestamp, session.parse()?))
+    }
+
+    /// Creates a new SessionVerification instance from a session label that may be an alias, e.g. "Asia".
+    ///
+    /// # Arguments
+    ///
+    /// * `unix_timestamp` - A 64-bit signed integer representing the Unix timestamp in seconds, which may be negative.
+    /// * `session` - The label or alias of the trading session, resolved with [`SessionAliases::resolve`].
+    /// * `aliases` - The table of aliases.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParseSessionError` if the label matches no trading session and no alias.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{SessionAliases, SessionVerification, TradingSession};
+    ///
+    /// let verifier = SessionVerification::from_alias(1708574400, "asia", &SessionAliases::common()).unwrap();
+    /// assert_eq!(verifier.session, TradingSession::Tokyo);
+    /// assert!(verifier.verify());
+    /// assert!(SessionVerification::from_alias(1708574400, "asia", &SessionAliases::new()).is_err());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn from_alias(unix_timestamp: i64, session: &str, aliases: &SessionAliases) -> Result<Self, ParseSessionError> {
+        aliases
+            .resolve(session)
+            .map(|session| Self::new(unix_timestamp, session))
+            .ok_or(ParseSessionError)
+    }
+
+    /// Verifies a session label that may be an alias against the trading session identified by the Unix timestamp.
+    ///
+    /// # Arguments
+    ///
+    /// * `unix_timestamp` - A 64-bit signed integer representing the Unix timestamp in seconds, which may be negative.
+    /// * `session` - The label or alias of the trading session, resolved with [`SessionAliases::resolve`].
+    /// * `aliases` - The table of aliases.
+    ///
+    /// # Returns
+    ///
+    /// The canonical trading session the label resolved to if it matches the identified session, or
+    /// `None` if it does not match or resolves to no session at all.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{SessionAliases, SessionVerification, TradingSession};
+    ///
+    /// let aliases = SessionAliases::common();
+    ///
+    /// // Thursday 22 February 2024, 17:00 UTC
+    /// assert_eq!(SessionVerification::verify_alias(1708621200, "NY", &aliases), Some(TradingSession::NewYork));
+    /// assert_eq!(SessionVerification::verify_alias(1708621200, "new_york", &aliases), Some(TradingSession::NewYork));
+    /// assert_eq!(SessionVerification::verify_alias(1708621200, "Asia", &aliases), None);
+    /// assert_eq!(SessionVerification::verify_alias(1708621200, "Frankfurt", &aliases), None);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn verify_alias(unix_timestamp: i64, session: &str, aliases: &SessionAliases) -> Option<TradingSession> {
+        Self::from_alias(unix_timestamp, session, aliases)
+            .ok()
+            .filter(Self::verify)
+            .map(|verifier| verifier.session)
+    }
+
+    /// Verifies if the given trading session matches the trading session identified by the Unix timestamp.
+    ///
+    /// # Returns
+    ///
+    /// Returns true if the identified session matches the input session; otherwise, returns false.
+    pub fn verify(&self) -> bool {
+        IdentifyTradingSession::new(self.unix_timestamp).matches(self.session)
+    }
+
+    /// Verifies the trading session against a custom schedule of session windows instead of the default schedule.
+    ///
+    /// # Arguments
+    ///
+    /// * `schedule` - The schedule of session windows to identify the session with.
+    ///
+    /// # Returns
+    ///
+    /// Returns true if the session identified under the schedule matches the input session; otherwise, returns false.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{SessionSchedule, SessionVerification, TradingSession};
+    ///
+    /// // A custom Asian block from 23:00 to 08:00 UTC
+    /// let schedule = SessionSchedule::new().add(TradingSession::Tokyo, 23 * 3600, 8 * 3600);
+    ///
+    /// // Thursday 22 February 2024, 07:30 UTC
+    /// let verifier = SessionVerification::new(1708587000, TradingSession::Tokyo);
+    /// assert!(!verifier.verify());
+    /// assert!(verifier.verify_with_schedule(&schedule));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn verify_with_schedule(&self, schedule: &SessionSchedule) -> bool {
+        IdentifyTradingSession::new(self.unix_timestamp)
+            .with_schedule(schedule.clone())
+            .matches(self.session)
+    }
+
+    /// Verifies if the trading session identified by the Unix timestamp matches any of the given sessions.
+    ///
+    /// # Arguments
+    ///
+    /// * `unix_timestamp` - A 64-bit signed integer representing the Unix timestamp in seconds, which may be negative.
+    /// * `sessions` - The acceptable trading sessions.
+    ///
+    /// # Returns
+    ///
+    /// Returns true if the identified session matches any of the given sessions; otherwise, returns false.
+    /// An empty slice of sessions never matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trading_sessions::{SessionVerification, TradingSession};
+    ///
+    /// // The timestamp corresponds to a time within the London session
+    /// let sessions = [TradingSession::Tokyo, TradingSession::London, TradingSession::LondonNewYork];
+    /// assert!(SessionVerification::verify_any(1708596000, &sessions));
+    ///
+    /// let sessions = [TradingSession::Tokyo, TradingSession::NewYork];
+    /// assert!(!SessionVerification::verify_any(1708596000, &sessions));
+    ///
+    /// assert!(!SessionVerification::verify_any(1708596000, &[]));
+    /// ```
+    pub fn verify_any(unix_timestamp: i64, sessions: &[TradingSession]) -> bool {
+        let identified_session = IdentifyTradingSession::new(unix_timestamp).trading_session();
+        sessions.contains(&identified_session)
+    }
+
+    /// Verifies the claimed session of every row of a LazyFrame against the session identified from its timestamp.
+    ///
+    /// The claimed sessions are compared with their canonical labels, as returned by `TradingSession::as_str`.
+    /// A row whose claimed session is null, or whose timestamp is null, only matches when both are null.
+    ///
+    /// # Arguments
+    ///
+    /// * `lazyframe` - The labeled data, e.g. a third-party dataset.
+    /// * `time_col` - The column of Unix timestamps in seconds, or of `Datetime` or `Date` values.
+    /// * `session_col` - The column of claimed session labels.
+    ///
+    /// # Returns
+    ///
+    /// A [`VerificationReport`] with the number of rows and mismatches, and a LazyFrame of the mismatching rows.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TradingSessionsError::MissingColumn` if either column is missing, a
+    /// `TradingSessionsError::WrongDtype` if the timestamps are of an unsupported dtype, or a
+    /// `TradingSessionsError::Polars` for any error raised while collecting the counts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionVerification;
+    ///
+    /// // Thursday 22 February 2024, 04:00, 10:00 and 17:00 UTC
+    /// let df = df! {
+    ///     "time" => [1708574400, 1708596000, 1708621200],
+    ///     "session" => ["Tokyo", "Tokyo", "NewYork"]
+    /// }.unwrap();
+    ///
+    /// let report = SessionVerification::verify_frame(df.lazy(), "time", "session").unwrap();
+    /// assert_eq!((report.rows, report.mismatches), (3, 1));
+    ///
+    /// let offending_df = report.offending_rows.collect().unwrap();
+    /// assert_eq!(offending_df.column("time").unwrap().i32().unwrap().get(0), Some(1708596000));
+    /// assert_eq!(offending_df.column("ComputedSession").unwrap().str_value(0).unwrap(), "London");
+    /// ```
+    #[cfg(feature = "polars")]
+    pub fn verify_frame(lazyframe: LazyFrame, time_col: &str, session_col: &str) -> Result<VerificationReport, TradingSessionsError> {
+        if lazyframe.schema()?.get(session_col).is_none() {
+            return Err(TradingSessionsError::MissingColumn(session_col.to_string()));
+        }
+
+        let lazyframe = SessionColumn::new(lazyframe)
+            .with_columns(time_col, "ComputedSession")
+            .try_apply_session_column()?
+            .into_lazyframe();
+        let mismatch = col(session_col).cast(DataType::Utf8).neq_missing(col("ComputedSession"));
+
+        let counts = lazyframe
+            .clone()
+            .select([count().cast(DataType::UInt64).alias("rows"), mismatch.clone().sum().cast(DataType::UInt64).alias("mismatches")])
+            .collect()?;
+        let count_of = |name: &str| -> Result<usize, TradingSessionsError> {
+            Ok(counts.column(name)?.u64()?.get(0).unwrap_or(0) as usize)
+        };
+
+        Ok(VerificationReport {
+            rows: count_of("rows")?,
+            mismatches: count_of("mismatches")?,
+            offending_rows: lazyframe.filter(mismatch),
+        })
+    }
+}
+
+
+
+#[cfg(feature = "polars")]
+impl SessionColumn {
+    pub fn new(lazyframe: LazyFrame) -> Self {
+        Self {
+            lazyframe,
+            input_column: "time".to_string(),
+            output_column: "Session".to_string(),
+            datetime_column: None,
+            parse_strings: false,
+            time_format: None,
+            schedule: None,
+            categorical: false,
+            session_ids: false,
+            output_aliases: None,
+            dst: false,
+            unit: TimestampUnit::Seconds,
+            holidays: None,
+            fallback_label: TradingSession::Undefined.as_str().to_string(),
+            null_label: None,
+            market: Market::Forex,
+        }
+    }
+
+    /// Sets the names of the column holding the Unix timestamps and of the session column to add.
+    ///
+    /// By default, the timestamps are read from the "time" column and the sessions are written to
+    /// the "Session" column.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_col` - The name of the column holding the Unix timestamps in seconds.
+    /// * `output_col` - The name of the session column to add.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionColumn;
+    ///
+    /// let df = df! {
+    ///     "open_time" => [1708574400, 1708596000]
+    /// }.unwrap();
+    ///
+    /// let result_df = SessionColumn::new(df.lazy())
+    ///     .with_columns("open_time", "trading_session")
+    ///     .apply_session_column()
+    ///     .into_lazyframe()
+    ///     .collect()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(result_df.column("trading_session").unwrap().str_value(0).unwrap(), "Tokyo");
+    /// assert_eq!(result_df.column("trading_session").unwrap().str_value(1).unwrap(), "London");
+    /// assert!(result_df.column("Session").is_err());
+    /// ```
+    pub fn with_columns(mut self, input_col: &str, output_col: &str) -> Self {
+        self.input_column = input_col.to_string();
+        self.output_column = output_col.to_string();
+        self
+    }
+
+    /// Emits the "Session" column as a Polars `Categorical` instead of a `Utf8` column.
+    ///
+    /// The column only holds a handful of distinct session names, so encoding it as a categorical
+    /// greatly reduces the memory footprint of large frames. Disabled by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to emit the "Session" column as a categorical.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionColumn;
+    ///
+    /// let df = df! {
+    ///     "time" => [1708574400, 1708596000, 1708696800]
+    /// }.unwrap();
+    ///
+    /// let session_column = SessionColumn::new(df.lazy()).with_categorical(true).apply_session_column();
+    /// let result_df = session_column.into_lazyframe().collect().unwrap();
+    ///
+    /// assert!(matches!(result_df.column("Session").unwrap().dtype(), DataType::Categorical(_)));
+    /// assert_eq!(result_df.column("Session").unwrap().str_value(1).unwrap(), "London");
+    /// ```
+    pub fn with_categorical(mut self, enabled: bool) -> Self {
+        self.categorical = enabled;
+        self
+    }
+
+    /// Emits the "Session" column as UInt8 session IDs instead of session names.
+    ///
+    /// Each session is encoded with its stable [`TradingSession::id`], e.g. 0 for Tokyo and 2 for London,
+    /// which feature stores and machine learning pipelines handle more readily than strings. The IDs
+    /// map back to the sessions with [`TradingSession::from_id`]. Fallback and null labels which match
+    /// no trading session are encoded as null. The IDs take precedence over [`SessionColumn::with_categorical`].
+    ///
+    /// The methods reading the session column back, e.g. [`SessionColumn::session_counts`], expect
+    /// session names, so they should be used without the IDs.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to emit the "Session" column as session IDs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::{SessionColumn, TradingSession};
+    ///
+    /// // Thursday 22 February 2024, 04:00, 10:00 and 23:00 UTC
+    /// let df = df! {
+    ///     "time" => [1708574400, 1708596000, 1708642800]
+    /// }.unwrap();
+    ///
+    /// let session_column = SessionColumn::new(df.lazy()).with_session_ids(true).apply_session_column();
+    /// let result_df = session_column.into_lazyframe().collect().unwrap();
+    ///
+    /// let ids: Vec<Option<u8>> = result_df.column("Session").unwrap().u8().unwrap().into_iter().collect();
+    /// assert_eq!(ids, [Some(0), Some(2), Some(14)]);
+    /// assert_eq!(TradingSession::from_id(2), Some(TradingSession::London));
+    /// ```
+    pub fn with_session_ids(mut self, enabled: bool) -> Self {
+        self.session_ids = enabled;
+        self
+    }
+
+    /// Renames the sessions in the session column to the naming convention of an alias table, e.g.
+    /// to match third-party labeled data.
+    ///
+    /// Each session is written as its first alias in the table, see [`SessionAliases::alias_of`],
+    /// and sessions without an alias keep their canonical label. The fallback and null labels are
+    /// written as configured. Session IDs are unaffected, see [`SessionColumn::with_session_ids`].
+    ///
+    /// # Arguments
+    ///
+    /// * `aliases` - The alias table to rename the sessions with.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::{SessionAliases, SessionColumn, TradingSession};
+    ///
+    /// // Thursday 22 February 2024, 04:00, 10:00 and 14:00 UTC
+    /// let df = df! {
+    ///     "time" => [1708574400, 1708596000, 1708610400]
+    /// }.unwrap();
+    ///
+    /// let aliases = SessionAliases::new().with_alias("Asia", TradingSession::Tokyo).with_alias("EU", TradingSession::London);
+    /// let result_df = SessionColumn::new(df.lazy())
+    ///     .with_output_aliases(&aliases)
+    ///     .apply_session_column()
+    ///     .into_lazyframe()
+    ///     .collect()
+    ///     .unwrap();
+    ///
+    /// let sessions: Vec<Option<&str>> = result_df.column("Session").unwrap().utf8().unwrap().into_iter().collect();
+    /// assert_eq!(sessions, [Some("Asia"), Some("EU"), Some("London_NewYork")]);
+    /// ```
+    pub fn with_output_aliases(mut self, aliases: &SessionAliases) -> Self {
+        self.output_aliases = Some(aliases.clone());
+        self
+    }
+
+    /// Reads the timestamps from a Polars `Datetime` column instead of the "time" column of Unix timestamps.
+    ///
+    /// The session is then determined with the `.dt().hour()` and `.dt().weekday()` accessors,
+    /// which take the time unit of the column into account. This is the typical dtype of a parsed
+    /// datetime column, e.g. `Datetime(TimeUnit::Milliseconds, None)`. `Date` columns are read as
+    /// midnight UTC of each day.
+    ///
+    /// A `Datetime` or `Date` input column, e.g. set with [`SessionColumn::with_columns`], is detected
+    /// from the schema of the LazyFrame, so this is only needed to read the timestamps from another column.
+    ///
+    /// # Arguments
+    ///
+    /// * `col_name` - The name of the `Datetime` or `Date` column.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionColumn;
+    ///
+    /// let timestamps = Series::new("timestamp", [1708574400000i64, 1708596000000, 1708696800000])
+    ///     .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))
+    ///     .unwrap();
+    /// let df = DataFrame::new(vec![timestamps]).unwrap();
+    ///
+    /// let session_column = SessionColumn::new(df.lazy()).with_datetime_column("timestamp").apply_session_column();
+    /// let result_df = session_column.into_lazyframe().collect().unwrap();
+    ///
+    /// assert_eq!(result_df.column("Session").unwrap().str_value(0).unwrap(), "Tokyo");
+    /// assert_eq!(result_df.column("Session").unwrap().str_value(1).unwrap(), "London");
+    /// assert_eq!(result_df.column("Session").unwrap().str_value(2).unwrap(), "London_NewYork");
+    ///
+    /// // Without the cast to Int64 seconds, the dtypes are detected on the input column
+    /// let df = df! {
+    ///     "time" => [1708574400000i64, 1708596000000, 1708740000000]
+    /// }.unwrap();
+    /// let df = df.lazy().with_columns([
+    ///     col("time").cast(DataType::Datetime(TimeUnit::Milliseconds, None)).alias("time_ms"),
+    /// ]).with_columns([
+    ///     col("time_ms").cast(DataType::Datetime(TimeUnit::Nanoseconds, None)).alias("time_ns"),
+    ///     col("time_ms").cast(DataType::Date).alias("date"),
+    /// ]);
+    /// let result_df = SessionColumn::new(df)
+    ///     .with_columns("time_ns", "Session")
+    ///     .try_apply_session_column()
+    ///     .unwrap()
+    ///     .with_columns("date", "DateSession")
+    ///     .apply_session_column()
+    ///     .into_lazyframe()
+    ///     .collect()
+    ///     .unwrap();
+    ///
+    /// let sessions: Vec<Option<&str>> = result_df.column("Session").unwrap().utf8().unwrap().into_iter().collect();
+    /// assert_eq!(sessions, [Some("Tokyo"), Some("London"), Some("Weekend")]);
+    /// let sessions: Vec<Option<&str>> = result_df.column("DateSession").unwrap().utf8().unwrap().into_iter().collect();
+    /// assert_eq!(sessions, [Some("Tokyo"), Some("Tokyo"), Some("Weekend")]);
+    /// ```
+    pub fn with_datetime_column(mut self, col_name: &str) -> Self {
+        self.datetime_column = Some(col_name.to_string());
+        self
+    }
+
+    /// Reads the timestamps from a Utf8 column of datetime strings, e.g. straight from a CSV feed,
+    /// parsing and classifying them in one lazy expression.
+    ///
+    /// Without a format, ISO-8601 strings such as "2024-02-22T10:00:00" or "2024-02-22 10:00:00.250"
+    /// are recognized. A `chrono`-style format, e.g. "%d/%m/%Y %H:%M", parses any other layout. The
+    /// strings are read as UTC. Strings that do not parse become null timestamps rather than failing
+    /// the query, so they are classified with the null label, see [`SessionColumn::with_null_label`].
+    ///
+    /// # Arguments
+    ///
+    /// * `col_name` - The name of the Utf8 column.
+    /// * `format` - The format of the strings, or `None` for ISO-8601.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionColumn;
+    ///
+    /// let df = df! {
+    ///     "timestamp" => ["2024-02-22T04:00:00", "2024-02-22T10:30:00", "not a timestamp"]
+    /// }.unwrap();
+    ///
+    /// let result_df = SessionColumn::new(df.lazy())
+    ///     .with_string_column("timestamp", None)
+    ///     .with_null_label("Unparseable")
+    ///     .try_apply_session_column()
+    ///     .unwrap()
+    ///     .into_lazyframe()
+    ///     .collect()
+    ///     .unwrap();
+    ///
+    /// let sessions: Vec<Option<&str>> = result_df.column("Session").unwrap().utf8().unwrap().into_iter().collect();
+    /// assert_eq!(sessions, [Some("Tokyo"), Some("London"), Some("Unparseable")]);
+    ///
+    /// // Saturday 24 February 2024, 02:00 UTC in a custom format
+    /// let df = df! { "timestamp" => ["24/02/2024 02:00"] }.unwrap();
+    /// let result_df = SessionColumn::new(df.lazy())
+    ///     .with_string_column("timestamp", Some("%d/%m/%Y %H:%M"))
+    ///     .apply_session_column()
+    ///     .into_lazyframe()
+    ///     .collect()
+    ///     .unwrap();
+    /// assert_eq!(result_df.column("Session").unwrap().str_value(0).unwrap(), "Weekend");
+    /// ```
+    pub fn with_string_column(mut self, col_name: &str, format: Option<&str>) -> Self {
+        self.datetime_column = Some(col_name.to_string());
+        self.parse_strings = true;
+        self.time_format = format.map(str::to_string);
+        self
+    }
+
+    /// Sets the unit of the integer Unix timestamps in the input column, seconds by default.
+    ///
+    /// The timestamps are floored to whole seconds before they are classified, e.g. for market data
+    /// in epoch milliseconds. `Datetime` and `Date` columns carry their own time unit, so this has no effect on them.
+    ///
+    /// # Arguments
+    ///
+    /// * `unit` - The unit of the timestamps.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::{SessionColumn, TimestampUnit};
+    ///
+    /// let df = df! {
+    ///     "time" => [1708574400000i64, 1708596000000, 1708740000000]
+    /// }.unwrap();
+    ///
+    /// let result_df = SessionColumn::new(df.lazy())
+    ///     .with_timestamp_unit(TimestampUnit::Milliseconds)
+    ///     .apply_session_column()
+    ///     .into_lazyframe()
+    ///     .collect()
+    ///     .unwrap();
+    ///
+    /// let sessions: Vec<Option<&str>> = result_df.column("Session").unwrap().utf8().unwrap().into_iter().collect();
+    /// assert_eq!(sessions, [Some("Tokyo"), Some("London"), Some("Weekend")]);
+    /// ```
+    pub fn with_timestamp_unit(mut self, unit: TimestampUnit) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    /// Sets the label of the timestamps outside of every session window, "Undefined" by default like
+    /// `TradingSession::Undefined` in the scalar API.
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - The label to emit instead of "Undefined".
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionColumn;
+    ///
+    /// // Thursday 22 February 2024, 10:00 and 23:00 UTC
+    /// let df = df! {
+    ///     "time" => [1708596000, 1708642800]
+    /// }.unwrap();
+    ///
+    /// let result_df = SessionColumn::new(df.clone().lazy())
+    ///     .with_fallback_label("Off_Hours")
+    ///     .apply_session_column()
+    ///     .into_lazyframe()
+    ///     .collect()
+    ///     .unwrap();
+    ///
+    /// let sessions: Vec<Option<&str>> = result_df.column("Session").unwrap().utf8().unwrap().into_iter().collect();
+    /// assert_eq!(sessions, [Some("London"), Some("Off_Hours")]);
+    ///
+    /// // The parallel classification emits the same label
+    /// let mut session_column = SessionColumn::new(df.lazy()).with_fallback_label("Off_Hours");
+    /// session_column.apply_session_column_parallel();
+    /// let parallel_df = session_column.into_lazyframe().collect().unwrap();
+    /// assert!(result_df.frame_equal(&parallel_df));
+    /// ```
+    pub fn with_fallback_label(mut self, label: &str) -> Self {
+        self.fallback_label = label.to_string();
+        self
+    }
+
+    /// Sets the label of the rows with a null timestamp, which are null by default.
+    ///
+    /// Null timestamps have no trading session, so they are kept apart from both the sessions and
+    /// the fallback label of the timestamps outside of every session.
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - The label of the rows with a null timestamp, e.g. "Unknown".
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionColumn;
+    ///
+    /// // Thursday 22 February 2024, 10:00 UTC, a missing timestamp and 23:00 UTC
+    /// let df = df! {
+    ///     "time" => [Some(1708596000), None, Some(1708642800)]
+    /// }.unwrap();
+    ///
+    /// let result_df = SessionColumn::new(df.clone().lazy()).apply_session_column().into_lazyframe().collect().unwrap();
+    /// let sessions: Vec<Option<&str>> = result_df.column("Session").unwrap().utf8().unwrap().into_iter().collect();
+    /// assert_eq!(sessions, [Some("London"), None, Some("Undefined")]);
+    ///
+    /// let result_df = SessionColumn::new(df.clone().lazy())
+    ///     .with_null_label("Unknown")
+    ///     .apply_session_column()
+    ///     .into_lazyframe()
+    ///     .collect()
+    ///     .unwrap();
+    /// let sessions: Vec<Option<&str>> = result_df.column("Session").unwrap().utf8().unwrap().into_iter().collect();
+    /// assert_eq!(sessions, [Some("London"), Some("Unknown"), Some("Undefined")]);
+    ///
+    /// // The parallel classification handles null timestamps alike
+    /// for null_label in [None, Some("Unknown")] {
+    ///     let mut serial = SessionColumn::new(df.clone().lazy());
+    ///     let mut parallel = SessionColumn::new(df.clone().lazy());
+    ///     if let Some(label) = null_label {
+    ///         (serial, parallel) = (serial.with_null_label(label), parallel.with_null_label(label));
+    ///     }
+    ///     parallel.apply_session_column_parallel();
+    ///     let serial_df = serial.apply_session_column().into_lazyframe().collect().unwrap();
+    ///     assert!(serial_df.frame_equal_missing(&parallel.into_lazyframe().collect().unwrap()));
+    /// }
+    /// ```
+    pub fn with_null_label(mut self, label: &str) -> Self {
+        self.null_label = Some(label.to_string());
+        self
+    }
+
+    /// Sets a calendar of holidays, on which the timestamps are labeled as "Holiday", like [`IdentifyTradingSession::with_holidays`].
+    ///
+    /// The early closes of the calendar label the closed rest of the session as "Undefined".
+    ///
+    /// # Arguments
+    ///
+    /// * `calendar` - The holidays and early closes of the market.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::{HolidayCalendar, SessionColumn};
+    ///
+    /// // Tuesday 24 December 2024, 10:00 UTC and Wednesday 25 December 2024, 10:00 UTC
+    /// let df = df! {
+    ///     "time" => [1735034400, 1735120800]
+    /// }.unwrap();
+    ///
+    /// let result_df = SessionColumn::new(df.lazy())
+    ///     .with_holidays(HolidayCalendar::new(&[(2024, 12, 25)]))
+    ///     .apply_session_column()
+    ///     .into_lazyframe()
+    ///     .collect()
+    ///     .unwrap();
+    ///
+    /// let sessions: Vec<Option<&str>> = result_df.column("Session").unwrap().utf8().unwrap().into_iter().collect();
+    /// assert_eq!(sessions, [Some("London"), Some("Holiday")]);
+    /// ```
+    pub fn with_holidays(mut self, calendar: HolidayCalendar) -> Self {
+        self.holidays = Some(calendar);
+        self
+    }
+
+    /// Classifies the timestamps into the sessions of one of the session sets, see [`SessionSet`].
+    ///
+    /// This is a shorthand for `with_schedule(set.schedule())`.
+    ///
+    /// # Arguments
+    ///
+    /// * `set` - The set of trading centers to divide the day into.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::{SessionColumn, SessionSet};
+    ///
+    /// // Thursday 22 February 2024, 03:00, 06:00 and 23:00 UTC
+    /// let df = df! {
+    ///     "time" => [1708570800, 1708581600, 1708642800]
+    /// }.unwrap();
+    ///
+    /// let result_df = SessionColumn::new(df.lazy())
+    ///     .with_session_set(SessionSet::ForexFour)
+    ///     .apply_session_column()
+    ///     .into_lazyframe()
+    ///     .collect()
+    ///     .unwrap();
+    ///
+    /// let sessions: Vec<Option<&str>> = result_df.column("Session").unwrap().utf8().unwrap().into_iter().collect();
+    /// assert_eq!(sessions, [Some("Sydney_Tokyo"), Some("Tokyo"), Some("Sydney")]);
+    /// ```
+    pub fn with_session_set(self, set: SessionSet) -> Self {
+        self.with_schedule(set.schedule())
+    }
+
+    /// Sets the market whose trading hours are used, as for [`IdentifyTradingSession::with_market`].
+    ///
+    /// For Crypto markets, no row falls on the weekend, and with the default schedule the hours after
+    /// the New York session are folded into the Tokyo session.
+    ///
+    /// # Arguments
+    ///
+    /// * `market` - The market to identify the trading sessions of.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::{Market, SessionColumn, SessionSchedule};
+    ///
+    /// // Thursday 22 February 2024, 23:00 UTC and Saturday 24 February 2024, 10:00 UTC
+    /// let df = df! {
+    ///     "time" => [1708642800, 1708768800]
+    /// }.unwrap();
+    ///
+    /// let result_df = SessionColumn::new(df.clone().lazy())
+    ///     .with_market(Market::Crypto)
+    ///     .apply_session_column()
+    ///     .into_lazyframe()
+    ///     .collect()
+    ///     .unwrap();
+    /// let sessions: Vec<Option<&str>> = result_df.column("Session").unwrap().utf8().unwrap().into_iter().collect();
+    /// assert_eq!(sessions, [Some("Tokyo"), Some("London")]);
+    ///
+    /// let result_df = SessionColumn::new(df.lazy())
+    ///     .with_market(Market::Crypto)
+    ///     .with_schedule(SessionSchedule::crypto())
+    ///     .apply_session_column()
+    ///     .into_lazyframe()
+    ///     .collect()
+    ///     .unwrap();
+    /// let sessions: Vec<Option<&str>> = result_df.column("Session").unwrap().utf8().unwrap().into_iter().collect();
+    /// assert_eq!(sessions, [Some("Tokyo"), Some("London")]);
+    /// ```
+    pub fn with_market(mut self, market: Market) -> Self {
+        self.market = market;
+        self
+    }
+
+    /// Enables daylight saving time, shifting the London and New York boundaries of the default schedule
+    /// as observed at each row, like [`IdentifyTradingSession::with_dst`].
+    ///
+    /// While the UK observes British Summer Time, the London open at 07:00 and close at 16:00 UTC are
+    /// an hour earlier, and while the US observes daylight saving time, the New York open at 13:00 and
+    /// close at 22:00 UTC are an hour earlier. The transitions are evaluated per row, so historical
+    /// data spanning many years shifts at the right dates, both by [`SessionColumn::apply_session_column`]
+    /// and by [`SessionColumn::apply_session_column_parallel`]. A custom schedule is not shifted, so
+    /// [`SessionColumn::try_apply_session_column`] rejects the combination.
+    ///
+    /// # Returns
+    ///
+    /// The SessionColumn instance with daylight saving time enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::{IdentifyTradingSession, SessionColumn};
+    ///
+    /// // Thursday 13 June 2024, 06:30 and 21:30 UTC, Wednesday 20 March 2024, 06:30 and 21:30 UTC
+    /// let timestamps = [1718260200i64, 1718314200, 1710916200, 1710970200];
+    /// let df = df! { "time" => timestamps }.unwrap();
+    ///
+    /// let result_df = SessionColumn::new(df.lazy()).with_dst().apply_session_column().into_lazyframe().collect().unwrap();
+    ///
+    /// let sessions: Vec<Option<&str>> = result_df.column("Session").unwrap().utf8().unwrap().into_iter().collect();
+    /// assert_eq!(sessions, [Some("Tokyo_London"), Some("Undefined"), Some("Tokyo"), Some("Undefined")]);
+    /// for (unix_timestamp, session) in timestamps.into_iter().zip(sessions) {
+    ///     assert_eq!(session, Some(IdentifyTradingSession::new(unix_timestamp).with_dst().identify_trading_session_label()));
+    /// }
+    /// ```
+    pub fn with_dst(mut self) -> Self {
+        self.dst = true;
+        self
+    }
+
+    /// Sets a custom schedule of session windows to classify the timestamps with, instead of the default schedule.
+    ///
+    /// Windows wrapping midnight are supported, as for [`IdentifyTradingSession::with_schedule`].
+    ///
+    /// # Arguments
+    ///
+    /// * `schedule` - The schedule of session windows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::{SessionColumn, SessionSchedule, TradingSession};
+    ///
+    /// // An Asian futures session from 23:00 to 03:00 UTC
+    /// let schedule = SessionSchedule::new().add(TradingSession::Tokyo, 23 * 3600, 3 * 3600);
+    ///
+    /// // Wednesday 23:30, Thursday 01:00 and Thursday 04:00 UTC
+    /// let df = df! {
+    ///     "time" => [1708558200, 1708563600, 1708574400]
+    /// }.unwrap();
+    ///
+    /// let result_df = SessionColumn::new(df.lazy())
+    ///     .with_schedule(schedule)
+    ///     .apply_session_column()
+    ///     .into_lazyframe()
+    ///     .collect()
+    ///     .unwrap();
+    ///
+    /// let sessions: Vec<Option<&str>> = result_df.column("Session").unwrap().utf8().unwrap().into_iter().collect();
+    /// assert_eq!(sessions, [Some("Tokyo"), Some("Tokyo"), Some("Undefined")]);
+    ///
+    /// // The weekend of the schedule takes the place of the Forex weekend, here on Friday 23:00 and Sunday 23:30 UTC
+    /// let df = df! {
+    ///     "time" => [1708729200, 1708907400]
+    /// }.unwrap();
+    /// let result_df = SessionColumn::new(df.lazy())
+    ///     .with_schedule(SessionSchedule::nyse())
+    ///     .apply_session_column()
+    ///     .into_lazyframe()
+    ///     .collect()
+    ///     .unwrap();
+    ///
+    /// let sessions: Vec<Option<&str>> = result_df.column("Session").unwrap().utf8().unwrap().into_iter().collect();
+    /// assert_eq!(sessions, [Some("AfterHours"), Some("Weekend")]);
+    /// ```
+    pub fn with_schedule(mut self, schedule: SessionSchedule) -> Self {
+        self.schedule = Some(schedule);
+        self
+    }
+
+    /// Applies the trading session column transformation to the LazyFrame.
+    ///
+    /// # Returns
+    ///
+    /// The SessionColumn instance with the "Session" column applied, for chaining. A missing timestamp
+    /// column only surfaces once the LazyFrame is collected, see [`SessionColumn::try_apply_session_column`]
+    /// to check it eagerly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionColumn;
+    ///
+    /// // Thursday 22 February 2024, 04:00 UTC and Saturday 24 February 2024, 02:00 UTC
+    /// let df = df! {
+    ///     "time" => [1708574400, 1708740000]
+    /// }.unwrap();
+    ///
+    /// let session_column = SessionColumn::new(df.lazy()).apply_session_column();
+    /// let result_df = session_column.into_lazyframe().collect().unwrap();
+    ///
+    /// assert_eq!(result_df.column("Session").unwrap().str_value(0).unwrap(), "Tokyo");
+    /// assert_eq!(result_df.column("Session").unwrap().str_value(1).unwrap(), "Weekend");
+    /// ```
+    ///
+    /// Large frames collect just as well, and agree with the scalar classification row by row:
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::{IdentifyTradingSession, SessionColumn};
+    ///
+    /// // One row per minute starting on Thursday 22 February 2024, 00:00 UTC
+    /// let times: Vec<i64> = (0..1_000_000).map(|i| 1708560000 + i * 60).collect();
+    /// let df = df! { "time" => times.clone() }.unwrap();
+    ///
+    /// let result_df = SessionColumn::new(df.lazy()).apply_session_column().into_lazyframe().collect().unwrap();
+    /// assert_eq!(result_df.height(), 1_000_000);
+    ///
+    /// let sessions = result_df.column("Session").unwrap().utf8().unwrap();
+    /// for (time, session) in times.iter().zip(sessions.into_iter()) {
+    ///     assert_eq!(session, Some(IdentifyTradingSession::new(*time).identify_trading_session().as_str()));
+    /// }
+    /// ```
+    ///
+    /// Timestamps before 1970 and after 2038 are classified as in the scalar classification as well:
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::{IdentifyTradingSession, SessionColumn};
+    ///
+    /// let times = [-616843800i64, -396000, -72000, -1, 2213604000];
+    /// let df = df! { "time" => times }.unwrap();
+    /// let result_df = SessionColumn::new(df.lazy()).apply_session_column().into_lazyframe().collect().unwrap();
+    ///
+    /// let sessions = result_df.column("Session").unwrap().utf8().unwrap();
+    /// for (time, session) in times.iter().zip(sessions.into_iter()) {
+    ///     assert_eq!(session, Some(IdentifyTradingSession::new(*time).identify_trading_session().as_str()));
+    /// }
+    /// ```
+    ///
+    /// Rows exactly on a session boundary open the new session, as in the scalar classification,
+    /// even for timestamps parsed as floats:
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::{IdentifyTradingSession, SessionColumn};
+    ///
+    /// // The last second before and the first second of each session on Thursday 22 February 2024,
+    /// // followed by Saturday 24 February 2024, 02:00 UTC
+    /// let times: Vec<i64> = [25200, 32400, 46800, 57600, 79200]
+    ///     .iter()
+    ///     .flat_map(|boundary| [1708560000 + boundary - 1, 1708560000 + boundary])
+    ///     .chain([1708740000])
+    ///     .collect();
+    /// let floats: Vec<f64> = times.iter().map(|&time| time as f64 + 0.5).collect();
+    /// let df = df! { "time" => times.clone(), "float_time" => floats }.unwrap();
+    ///
+    /// let result_df = SessionColumn::new(df.lazy())
+    ///     .apply_session_column()
+    ///     .with_columns("float_time", "float_session")
+    ///     .apply_session_column()
+    ///     .into_lazyframe()
+    ///     .collect()
+    ///     .unwrap();
+    ///
+    /// let sessions = result_df.column("Session").unwrap().utf8().unwrap();
+    /// let float_sessions = result_df.column("float_session").unwrap().utf8().unwrap();
+    /// for ((time, session), float_session) in times.iter().zip(sessions.into_iter()).zip(float_sessions.into_iter()) {
+    ///     let scalar = IdentifyTradingSession::new(*time).identify_trading_session();
+    ///     assert_eq!(session, Some(scalar.as_str()));
+    ///     assert_eq!(float_session, Some(scalar.as_str()));
+    /// }
+    /// ```
+    #[must_use]
+    pub fn apply_session_column(mut self) -> Self {
+        // The seconds since midnight are computed once in `session_expr` and shared by every
+        // session window, and the LazyFrame is moved rather than cloned into the new plan
+        let session = self.output_expr(self.session_expr());
+        self.lazyframe = self.lazyframe.with_column(session);
+        self
+    }
+
+    /// Applies the trading session column transformation after checking the timestamp column eagerly.
+    ///
+    /// Unlike [`SessionColumn::apply_session_column`], a missing timestamp column is reported right
+    /// away rather than only once the LazyFrame is collected.
+    ///
+    /// # Returns
+    ///
+    /// The SessionColumn instance with the "Session" column applied, for chaining.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TradingSessionsError::MissingColumn` if the timestamp column is missing, a
+    /// `TradingSessionsError::WrongDtype` if it is neither an integer nor a `Datetime` column, a
+    /// `TradingSessionsError::InvalidSchedule` if the schedule fails [`SessionSchedule::validate`], a
+    /// `TradingSessionsError::DstWithSchedule` if daylight saving time is enabled along with a custom
+    /// schedule, or a `TradingSessionsError::Polars` for any error raised while resolving the LazyFrame's schema.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::{SessionColumn, TradingSessionsError};
+    ///
+    /// let df = df! {
+    ///     "timestamp" => [1708574400, 1708596000],
+    ///     "label" => ["a", "b"]
+    /// }.unwrap();
+    ///
+    /// let error = SessionColumn::new(df.clone().lazy()).try_apply_session_column().err().unwrap();
+    /// assert_eq!(error.to_string(), "SessionColumn requires a 'time' column");
+    ///
+    /// let error = SessionColumn::new(df.clone().lazy()).with_columns("label", "Session").try_apply_session_column().err().unwrap();
+    /// assert!(matches!(error, TradingSessionsError::WrongDtype { expected: "integer, Datetime or Date", .. }));
+    ///
+    /// let result_df = SessionColumn::new(df.lazy())
+    ///     .with_columns("timestamp", "Session")
+    ///     .try_apply_session_column()
+    ///     .unwrap()
+    ///     .into_lazyframe()
+    ///     .collect()
+    ///     .unwrap();
+    /// assert_eq!(result_df.column("Session").unwrap().str_value(1).unwrap(), "London");
+    /// ```
+    ///
+    /// The NYSE and CME Globex schedules are fixed to standard time, so their snapshots in effect at a
+    /// timestamp, e.g. [`SessionSchedule::nyse_at`], take the place of daylight saving time:
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::{SessionColumn, SessionSchedule, TradingSessionsError};
+    ///
+    /// let df = df! { "time" => [1719840600] }.unwrap();
+    ///
+    /// let error = SessionColumn::new(df.lazy()).with_schedule(SessionSchedule::nyse()).with_dst().try_apply_session_column();
+    /// assert!(matches!(error, Err(TradingSessionsError::DstWithSchedule)));
+    /// ```
+    pub fn try_apply_session_column(self) -> Result<Self, TradingSessionsError> {
+        self.check_time_column()?;
+        self.check_schedule()?;
+        Ok(self.apply_session_column())
+    }
+
+    /// Applies the trading session column transformation in place.
+    ///
+    /// This is the mutating form of [`SessionColumn::apply_session_column`], kept for compatibility
+    /// with code written against the in-place API.
+    #[deprecated(note = "use `apply_session_column` or `with_session_column(...).build()` instead")]
+    pub fn apply_session_column_mut(&mut self) {
+        let session = self.output_expr(self.session_expr());
+        self.lazyframe = std::mem::take(&mut self.lazyframe).with_column(session);
+    }
+
+    /// Adds the trading session column as a step of the builder, see [`SessionColumn::build`].
+    ///
+    /// # Returns
+    ///
+    /// The SessionColumn instance with the session column applied, for chaining.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionColumn;
+    ///
+    /// let df = df! {
+    ///     "time" => [1708574400, 1708596000, 1708696800],
+    ///     "close" => [1.0, 2.0, 3.0]
+    /// }.unwrap();
+    ///
+    /// let result_df = SessionColumn::new(df.lazy())
+    ///     .with_session_column()
+    ///     .build()
+    ///     .filter(col("close").gt(lit(1.5)))
+    ///     .collect()
+    ///     .unwrap();
+    ///
+    /// let sessions: Vec<Option<&str>> = result_df.column("Session").unwrap().utf8().unwrap().into_iter().collect();
+    /// assert_eq!(sessions, [Some("London"), Some("London_NewYork")]);
+    /// ```
+    #[must_use]
+    pub fn with_session_column(self) -> Self {
+        self.apply_session_column()
+    }
+
+    /// Finishes the builder, returning the transformed LazyFrame.
+    ///
+    /// This is equivalent to [`SessionColumn::into_lazyframe`].
+    ///
+    /// # Returns
+    ///
+    /// The LazyFrame with all transformations applied so far.
+    pub fn build(self) -> LazyFrame {
+        self.into_lazyframe()
+    }
+
+    /// Applies a session column for each of several timestamp columns at once.
+    ///
+    /// All session columns are added in a single `with_columns` call, so Polars evaluates them
+    /// together. Each input column is either an integer column of Unix timestamps in seconds or a
+    /// `Datetime` column, as detected from the schema. The schedule and dtype settings apply to all of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `mappings` - The `(input_col, output_col)` pairs, one for each session column to add.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TradingSessionsError::MissingColumn` if an input column is missing, a
+    /// `TradingSessionsError::WrongDtype` if it is neither an integer nor a `Datetime` column, a
+    /// `TradingSessionsError::DstWithSchedule` or invalid schedule error as with
+    /// [`SessionColumn::try_apply_session_column`], or a `TradingSessionsError::Polars` for any
+    /// error raised while resolving the LazyFrame's schema.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::{SessionColumn, SessionSchedule, TradingSessionsError};
+    ///
+    /// let df = df! {
+    ///     "entry_time" => [1708574400, 1708596000],
+    ///     "exit_time" => [1708596000, 1708696800]
+    /// }.unwrap();
+    ///
+    /// let mut session_column = SessionColumn::new(df.clone().lazy());
+    /// session_column.apply_for_columns(&[("entry_time", "entry_session"), ("exit_time", "exit_session")]).unwrap();
+    /// let result_df = session_column.into_lazyframe().collect().unwrap();
+    ///
+    /// assert_eq!(result_df.column("entry_session").unwrap().str_value(0).unwrap(), "Tokyo");
+    /// assert_eq!(result_df.column("exit_session").unwrap().str_value(0).unwrap(), "London");
+    /// assert_eq!(result_df.column("exit_session").unwrap().str_value(1).unwrap(), "London_NewYork");
+    ///
+    /// let mut session_column = SessionColumn::new(df.clone().lazy());
+    /// assert!(session_column.apply_for_columns(&[("open_time", "open_session")]).is_err());
+    ///
+    /// let mut session_column = SessionColumn::new(df.lazy()).with_schedule(SessionSchedule::nyse()).with_dst();
+    /// let error = session_column.apply_for_columns(&[("entry_time", "entry_session")]);
+    /// assert!(matches!(error, Err(TradingSessionsError::DstWithSchedule)));
+    /// ```
+    pub fn apply_for_columns(&mut self, mappings: &[(&str, &str)]) -> Result<(), TradingSessionsError> {
+        self.check_schedule()?;
+        let schema = self.lazyframe.schema()?;
+        let sessions = mappings
+            .iter()
+            .map(|&(input_col, output_col)| {
+                let dtype = schema.get(input_col).ok_or_else(|| TradingSessionsError::MissingColumn(input_col.to_string()))?;
+                let parse_strings = self.parse_strings && dtype == &DataType::Utf8;
+                let temporal = matches!(dtype, DataType::Datetime(_, _) | DataType::Date) || parse_strings;
+                if !dtype.is_integer() && !temporal {
+                    let expected = match self.parse_strings {
+                        true => "integer, Datetime, Date or Utf8",
+                        false => "integer, Datetime or Date",
+                    };
+                    return Err(TradingSessionsError::WrongDtype { column: input_col.to_string(), expected });
+                }
+                let column = SessionColumn {
+                    lazyframe: LazyFrame::default(),
+                    input_column: input_col.to_string(),
+                    output_column: output_col.to_string(),
+                    datetime_column: temporal.then(|| input_col.to_string()),
+                    parse_strings,
+                    time_format: self.time_format.clone(),
+                    schedule: self.schedule.clone(),
+                    categorical: self.categorical,
+                    session_ids: self.session_ids,
+                    output_aliases: self.output_aliases.clone(),
+                    dst: self.dst,
+                    unit: self.unit,
+                    holidays: self.holidays.clone(),
+                    fallback_label: self.fallback_label.clone(),
+                    null_label: self.null_label.clone(),
+                    market: self.market,
+                };
+                Ok(column.output_expr(column.session_expr()))
+            })
+            .collect::<Result<Vec<_>, TradingSessionsError>>()?;
+
+        self.lazyframe = std::mem::take(&mut self.lazyframe).with_columns(sessions);
+        Ok(())
+    }
+
+    /// Applies the trading session column transformation, forward-filling the session over null timestamps.
+    ///
+    /// Rows with a timestamp are classified as usual, while rows with a null timestamp carry forward
+    /// the session of the last row with a valid timestamp. Leading nulls, which have no previous
+    /// session to carry forward, remain null.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionColumn;
+    ///
+    /// // A null timestamp between two timestamps within the Tokyo session
+    /// let df = df! {
+    ///     "time" => [Some(1708574400i64), None, Some(1708578000i64)]
+    /// }.unwrap();
+    ///
+    /// let mut session_column = SessionColumn::new(df.lazy());
+    /// session_column.apply_session_column_ffill();
+    /// let result_df = session_column.into_lazyframe().collect().unwrap();
+    ///
+    /// assert_eq!(result_df.column("Session").unwrap().str_value(0).unwrap(), "Tokyo");
+    /// assert_eq!(result_df.column("Session").unwrap().str_value(1).unwrap(), "Tokyo");
+    /// assert_eq!(result_df.column("Session").unwrap().str_value(2).unwrap(), "Tokyo");
+    /// ```
+    pub fn apply_session_column_ffill(&mut self) {
+        let session = self.output_expr(
+            when(self.time_expr().is_null())
+                .then(lit(NULL).cast(DataType::Utf8))
+                .otherwise(self.session_expr())
+                .forward_fill(None));
+        self.lazyframe = std::mem::take(&mut self.lazyframe).with_column(session);
+    }
+
+    /// Appends a boolean "SessionChange" column marking the first row of each new session.
+    ///
+    /// A row is marked `true` when its session differs from the session of the previous row. The
+    /// first row is always `true`, since there is no previous row. This method expects the
+    /// "Session" column to be present, so it is meant to be called after `apply_session_column`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TradingSessionsError::MissingColumn` if the LazyFrame has no "Session" column, or a
+    /// `TradingSessionsError::Polars` for any error raised while resolving the LazyFrame's schema.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionColumn;
+    ///
+    /// let df = df! {
+    ///     "time" => [1708574400, 1708578000, 1708596000, 1708599600]
+    /// }.unwrap();
+    ///
+    /// let mut session_column = SessionColumn::new(df.lazy()).apply_session_column();
+    /// session_column.apply_session_change_column().unwrap();
+    /// let result_df = session_column.into_lazyframe().collect().unwrap();
+    ///
+    /// let changes: Vec<Option<bool>> = result_df.column("SessionChange").unwrap().bool().unwrap().into_iter().collect();
+    /// assert_eq!(changes, [Some(true), Some(false), Some(true), Some(false)]);
+    /// ```
+    pub fn apply_session_change_column(&mut self) -> Result<(), TradingSessionsError> {
+        if self.lazyframe.schema()?.get(&self.output_column).is_none() {
+            return Err(TradingSessionsError::MissingColumn(self.output_column.clone()));
+        }
+
+        let session_change = session_change_expr(col(&self.output_column)).alias("SessionChange");
+        self.lazyframe = std::mem::take(&mut self.lazyframe).with_column(session_change);
+        Ok(())
+    }
+
+    /// Appends a boolean "SessionChange" column marking the rows on which the trading session changes.
+    ///
+    /// Unlike `apply_session_change_column`, the session of each row is computed from the "time"
+    /// column directly, so no "Session" column is required. A row is marked `true` when its session
+    /// differs from the session of the previous row, with the first row being `true` by convention.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionColumn;
+    ///
+    /// // Tokyo, Tokyo, London, London
+    /// let df = df! {
+    ///     "time" => [1708574400, 1708578000, 1708596000, 1708599600]
+    /// }.unwrap();
+    ///
+    /// let mut session_column = SessionColumn::new(df.lazy());
+    /// session_column.apply_session_transition_column();
+    /// let result_df = session_column.into_lazyframe().collect().unwrap();
+    ///
+    /// assert!(result_df.column("Session").is_err());
+    /// let changes: Vec<Option<bool>> = result_df.column("SessionChange").unwrap().bool().unwrap().into_iter().collect();
+    /// assert_eq!(changes, [Some(true), Some(false), Some(true), Some(false)]);
+    /// ```
+    pub fn apply_session_transition_column(&mut self) {
+        let session_change = session_change_expr(self.session_expr()).alias("SessionChange");
+        self.lazyframe = std::mem::take(&mut self.lazyframe).with_column(session_change);
+    }
+
+    /// Appends a boolean "Overlap" column marking the rows within an overlap of two major trading centers.
+    ///
+    /// The overlap sessions are Tokyo_London and London_NewYork, see [`TradingSession::is_overlap`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionColumn;
+    ///
+    /// // Tokyo_London, London and Undefined
+    /// let df = df! {
+    ///     "time" => [1708588800, 1708596000, 1708642800]
+    /// }.unwrap();
+    ///
+    /// let mut session_column = SessionColumn::new(df.lazy());
+    /// session_column.apply_overlap_column();
+    /// let result_df = session_column.into_lazyframe().collect().unwrap();
+    ///
+    /// let overlaps: Vec<Option<bool>> = result_df.column("Overlap").unwrap().bool().unwrap().into_iter().collect();
+    /// assert_eq!(overlaps, [Some(true), Some(false), Some(false)]);
+    /// ```
+    pub fn apply_overlap_column(&mut self) {
+        let overlap = TradingSession::ALL
+            .iter()
+            .filter(|session| session.is_overlap())
+            .fold(lit(false), |overlap, session| overlap.or(self.session_expr().eq(lit(session.as_str()))))
+            .alias("Overlap");
+        self.lazyframe = std::mem::take(&mut self.lazyframe).with_column(overlap);
+    }
+
+    /// Appends an "ActiveSessions" List column with all trading sessions active at each row.
+    ///
+    /// Unlike the "Session" column, an overlap is reported as the two concurrent sessions it consists
+    /// of, see [`TradingSession::constituents`]. Rows without an active session get an empty list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionColumn;
+    ///
+    /// // Thursday 22 February 2024, 08:00, 10:00 and 23:00 UTC
+    /// let df = df! {
+    ///     "time" => [1708588800, 1708596000, 1708642800]
+    /// }.unwrap();
+    ///
+    /// let mut session_column = SessionColumn::new(df.lazy());
+    /// session_column.apply_active_sessions_column();
+    /// let result_df = session_column.into_lazyframe().collect().unwrap();
+    ///
+    /// let active = result_df.column("ActiveSessions").unwrap().list().unwrap();
+    /// let first = active.get_as_series(0).unwrap();
+    /// let first: Vec<Option<&str>> = first.utf8().unwrap().into_iter().collect();
+    /// assert_eq!(first, [Some("Tokyo"), Some("London")]);
+    /// assert_eq!(active.get_as_series(1).unwrap().len(), 1);
+    /// assert_eq!(active.get_as_series(2).unwrap().len(), 0);
+    /// ```
+    pub fn apply_active_sessions_column(&mut self) {
+        let active_sessions = self.session_expr()
+            .map(|sessions| {
+                let active: ListChunked = sessions
+                    .utf8()?
+                    .into_iter()
+                    .map(|label| {
+                        let constituents = label
+                            .and_then(|label| label.parse::<TradingSession>().ok())
+                            .map_or(&[][..], |session| session.constituents());
+                        label.map(|_| Series::new("", constituents.iter().map(TradingSession::as_str).collect::<Vec<_>>()))
+                    })
+                    .collect();
+                Ok(Some(active.into_series()))
+            }, GetOutput::from_type(DataType::List(Box::new(DataType::Utf8))))
+            .alias("ActiveSessions");
+        self.lazyframe = std::mem::take(&mut self.lazyframe).with_column(active_sessions);
+    }
+
+    /// Appends a "Killzone" column with the ICT-style killzone of each row, see [`Killzone`].
+    ///
+    /// Rows outside of every killzone, during the weekend or with a null timestamp are null.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionColumn;
+    ///
+    /// // Thursday 22 February 2024, 08:00, 11:00 and 13:00 UTC
+    /// let df = df! {
+    ///     "time" => [1708588800, 1708599600, 1708606800]
+    /// }.unwrap();
+    ///
+    /// let mut session_column = SessionColumn::new(df.lazy());
+    /// session_column.apply_killzone_column();
+    /// let result_df = session_column.into_lazyframe().collect().unwrap();
+    ///
+    /// let killzones: Vec<Option<&str>> = result_df.column("Killzone").unwrap().utf8().unwrap().into_iter().collect();
+    /// assert_eq!(killzones, [Some("London_Open"), None, Some("NewYork_Open")]);
+    /// ```
+    pub fn apply_killzone_column(&mut self) {
+        let seconds_of_day = self.seconds_of_day_expr();
+        let killzone = KILLZONE_BOUNDARIES
+            .iter()
+            .rev()
+            .fold(lit(NULL).cast(DataType::Utf8), |otherwise, (killzone, hours)| {
+                let within = seconds_of_day.clone().gt_eq(lit(u32::from(*hours.start()) * SECONDS_PER_HOUR))
+                    .and(seconds_of_day.clone().lt(lit((u32::from(*hours.end()) + 1) * SECONDS_PER_HOUR)));
+                when(within)
+                    .then(lit(killzone.as_str()))
+                    .otherwise(otherwise)
+            });
+        let killzone = when(self.is_weekend_expr())
+            .then(lit(NULL).cast(DataType::Utf8))
+            .otherwise(killzone)
+            .alias("Killzone");
+        self.lazyframe = std::mem::take(&mut self.lazyframe).with_column(killzone);
+    }
+
+    /// Appends a "session_progress" Float64 column with how far through its trading session each row is.
+    ///
+    /// The progress follows [`IdentifyTradingSession::session_progress`], measured against the
+    /// session windows of the schedule. Rows outside of every scheduled session, e.g. during the
+    /// weekend, on holidays or with a null timestamp, are null.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionColumn;
+    ///
+    /// // Thursday 22 February 2024, 09:00, 11:00 and 23:00 UTC
+    /// let df = df! {
+    ///     "time" => [1708592400, 1708599600, 1708642800]
+    /// }.unwrap();
+    ///
+    /// let mut session_column = SessionColumn::new(df.lazy());
+    /// session_column.apply_session_progress_column();
+    /// let result_df = session_column.into_lazyframe().collect().unwrap();
+    ///
+    /// let progress: Vec<Option<f64>> = result_df.column("session_progress").unwrap().f64().unwrap().into_iter().collect();
+    /// assert_eq!(progress, [Some(0.0), Some(0.5), None]);
+    /// ```
+    pub fn apply_session_progress_column(&mut self) {
+        let progress = self
+            .session_window_expr(DataType::Float64, |window, elapsed| {
+                elapsed.cast(DataType::Float64) / lit(f64::from(window.duration_secs()))
+            })
+            .alias("session_progress");
+        self.lazyframe = std::mem::take(&mut self.lazyframe).with_column(progress);
+    }
+
+    /// Appends "session_open" and "session_close" Int64 columns with the UTC Unix timestamps in seconds
+    /// at which the trading session of each row opens and closes.
+    ///
+    /// The bounds follow [`IdentifyTradingSession::session_bounds`], measured against the session
+    /// windows of the schedule, so sessions that wrap around midnight UTC open on the previous day or
+    /// close on the next day as needed. The close is exclusive. Rows outside of every scheduled
+    /// session, e.g. during the weekend, on holidays or with a null timestamp, are null.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionColumn;
+    ///
+    /// // Thursday 22 February 2024, 10:00 and 23:00 UTC
+    /// let df = df! {
+    ///     "time" => [1708596000i64, 1708642800]
+    /// }.unwrap();
+    ///
+    /// let mut session_column = SessionColumn::new(df.lazy());
+    /// session_column.apply_session_bounds_columns();
+    /// let result_df = session_column.into_lazyframe().collect().unwrap();
+    ///
+    /// // London session from 09:00 to 13:00 UTC
+    /// let opens: Vec<Option<i64>> = result_df.column("session_open").unwrap().i64().unwrap().into_iter().collect();
+    /// let closes: Vec<Option<i64>> = result_df.column("session_close").unwrap().i64().unwrap().into_iter().collect();
+    /// assert_eq!(opens, [Some(1708592400), None]);
+    /// assert_eq!(closes, [Some(1708606800), None]);
+    /// ```
+    pub fn apply_session_bounds_columns(&mut self) {
+        let unix_seconds = self.unix_seconds_expr();
+        let session_open = self
+            .session_window_expr(DataType::Int64, |_, elapsed| unix_seconds.clone() - elapsed)
+            .cast(DataType::Int64)
+            .alias("session_open");
+        let session_close = self
+            .session_window_expr(DataType::Int64, |window, elapsed| {
+                unix_seconds.clone() - elapsed + lit(i64::from(window.duration_secs()))
+            })
+            .cast(DataType::Int64)
+            .alias("session_close");
+        self.lazyframe = std::mem::take(&mut self.lazyframe).with_columns([session_open, session_close]);
+    }
+
+    /// Adds one boolean indicator column per trading session of the schedule, e.g. as one-hot features.
+    ///
+    /// The columns are named after the lowercase session labels, i.e. "is_tokyo", "is_tokyo_london",
+    /// "is_london", "is_london_newyork" and "is_newyork" under the default schedule, in the order of
+    /// the schedule. The Undefined hours and the weekend leave all indicators false.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionColumn;
+    ///
+    /// // Tokyo, London and the weekend
+    /// let df = df! {
+    ///     "time" => [1708574400, 1708596000, 1708740000]
+    /// }.unwrap();
+    ///
+    /// let mut session_column = SessionColumn::new(df.lazy());
+    /// session_column.apply_session_indicator_columns();
+    /// let result_df = session_column.into_lazyframe().collect().unwrap();
+    ///
+    /// assert_eq!(result_df.get_column_names(), ["time", "is_tokyo", "is_tokyo_london", "is_london", "is_london_newyork", "is_newyork"]);
+    /// let london: Vec<Option<bool>> = result_df.column("is_london").unwrap().bool().unwrap().into_iter().collect();
+    /// assert_eq!(london, [Some(false), Some(true), Some(false)]);
+    /// ```
+    pub fn apply_session_indicator_columns(&mut self) {
+        let schedule = self.effective_schedule();
+        let mut sessions: Vec<TradingSession> = Vec::new();
+        for window in &schedule.windows {
+            if window.session != TradingSession::Undefined && !sessions.contains(&window.session) {
+                sessions.push(window.session);
+            }
+        }
+
+        let session = self.session_expr();
+        let indicators: Vec<Expr> = sessions
+            .iter()
+            .map(|indicator| {
+                let name = format!("is_{}", indicator.as_str().to_lowercase());
+                session.clone().eq(lit(indicator.as_str())).alias(&name)
+            })
+            .collect();
+        self.lazyframe = std::mem::take(&mut self.lazyframe).with_columns(indicators);
+    }
+
+    /// Adds a boolean "MarketOpen" column that is true unless the timestamp falls on the weekend.
+    ///
+    /// The Forex market is closed from Friday 22:00 to Sunday 22:00 UTC, like [`is_market_open`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionColumn;
+    ///
+    /// // Friday 21:00 UTC, Friday 22:00 UTC, Sunday 21:00 UTC and Sunday 22:00 UTC
+    /// let df = df! {
+    ///     "time" => [1708722000, 1708725600, 1708894800, 1708898400]
+    /// }.unwrap();
+    ///
+    /// let mut session_column = SessionColumn::new(df.lazy());
+    /// session_column.apply_market_open_column();
+    /// let result_df = session_column.into_lazyframe().collect().unwrap();
+    ///
+    /// let open: Vec<Option<bool>> = result_df.column("MarketOpen").unwrap().bool().unwrap().into_iter().collect();
+    /// assert_eq!(open, [Some(true), Some(false), Some(false), Some(true)]);
+    ///
+    /// // The same instants in a Datetime column
+    /// let timestamps = Series::new("timestamp", [1708722000000i64, 1708725600000, 1708894800000, 1708898400000])
+    ///     .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))
+    ///     .unwrap();
+    /// let mut session_column = SessionColumn::new(DataFrame::new(vec![timestamps]).unwrap().lazy()).with_datetime_column("timestamp");
+    /// session_column.apply_market_open_column();
+    /// let result_df = session_column.into_lazyframe().collect().unwrap();
+    ///
+    /// let open: Vec<Option<bool>> = result_df.column("MarketOpen").unwrap().bool().unwrap().into_iter().collect();
+    /// assert_eq!(open, [Some(true), Some(false), Some(false), Some(true)]);
+    /// ```
+    pub fn apply_market_open_column(&mut self) {
+        let market_open = self.is_weekend_expr().not().alias("MarketOpen");
+        self.lazyframe = std::mem::take(&mut self.lazyframe).with_column(market_open);
+    }
+
+    /// Appends a "utc_day" Date column with the UTC calendar day of each row, e.g. to bucket
+    /// around-the-clock Crypto data by day alongside the session.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionColumn;
+    ///
+    /// // Thursday 22 February 2024, 23:00 UTC and Friday 23 February 2024, 01:00 UTC
+    /// let df = df! {
+    ///     "time" => [1708642800, 1708650000]
+    /// }.unwrap();
+    ///
+    /// let mut session_column = SessionColumn::new(df.lazy());
+    /// session_column.apply_utc_day_column();
+    /// let result_df = session_column.into_lazyframe().collect().unwrap();
+    ///
+    /// let days = result_df.column("utc_day").unwrap();
+    /// assert_eq!(days.dtype(), &DataType::Date);
+    /// assert_eq!(days.cast(&DataType::Int32).unwrap().i32().unwrap().get(1), Some(19776));
+    /// assert_eq!(days.str_value(0).unwrap(), "2024-02-22");
+    /// ```
+    pub fn apply_utc_day_column(&mut self) {
+        let utc_day = self.day_expr().cast(DataType::Int32).cast(DataType::Date).alias("utc_day");
+        self.lazyframe = std::mem::take(&mut self.lazyframe).with_column(utc_day);
+    }
+
+    /// Appends a "trading_date" Date column with the Forex trading day of each row, which begins at
+    /// 17:00 New York time, see [`IdentifyTradingSession::trading_day`].
+    ///
+    /// Unlike the "utc_day" column, a trading day holds a complete Sydney, Tokyo, London and New York
+    /// cycle, so it is the key to aggregate daily bars by. Rows with a null timestamp are null.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionColumn;
+    ///
+    /// // Thursday 22 February 2024, 21:59 and 22:00 UTC, and Wednesday 10 July 2024, 21:00 UTC
+    /// let df = df! {
+    ///     "time" => [1708639140, 1708639200, 1720645200]
+    /// }.unwrap();
+    ///
+    /// let mut session_column = SessionColumn::new(df.lazy());
+    /// session_column.apply_trading_date_column();
+    /// let result_df = session_column.into_lazyframe().collect().unwrap();
+    ///
+    /// let dates = result_df.column("trading_date").unwrap();
+    /// assert_eq!(dates.dtype(), &DataType::Date);
+    /// assert_eq!(dates.str_value(0).unwrap(), "2024-02-22");
+    /// assert_eq!(dates.str_value(1).unwrap(), "2024-02-23");
+    /// assert_eq!(dates.str_value(2).unwrap(), "2024-07-11");
+    /// ```
+    pub fn apply_trading_date_column(&mut self) {
+        let unix_seconds = self.unix_seconds_expr();
+        let rollover_offset = when(summer_time_expr(unix_seconds.clone(), is_new_york_summer_time))
+            .then(lit(i64::from(3 * SECONDS_PER_HOUR)))
+            .otherwise(lit(i64::from(2 * SECONDS_PER_HOUR)));
+        let trading_date = div_euclid_expr(unix_seconds + rollover_offset, SECONDS_PER_DAY)
+            .cast(DataType::Int32)
+            .cast(DataType::Date)
+            .alias("trading_date");
+        self.lazyframe = std::mem::take(&mut self.lazyframe).with_column(trading_date);
+    }
+
+    /// Appends a "session_id" column identifying the occurrence of the trading session of each row,
+    /// e.g. "2024-02-22_London", to group bars by a specific session rather than by session name.
+    ///
+    /// The date is the UTC trading date on which the session opened, see [`SessionColumn::apply_session_bounds_columns`],
+    /// so a session that wraps around midnight UTC keeps a single ID for the whole occurrence. Rows
+    /// outside of every scheduled session, e.g. during the weekend, are keyed by their UTC calendar
+    /// day instead. Rows with a null timestamp are null.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::{Market, SessionColumn, SessionSchedule};
+    ///
+    /// // Thursday 22 February 2024, 23:00 UTC and Friday 23 February 2024, 01:00 and 10:00 UTC
+    /// let df = df! {
+    ///     "time" => [1708642800, 1708650000, 1708682400]
+    /// }.unwrap();
+    ///
+    /// // The Crypto Tokyo bucket runs from 22:00 to 08:00 UTC
+    /// let mut session_column = SessionColumn::new(df.lazy()).with_market(Market::Crypto).with_schedule(SessionSchedule::crypto());
+    /// session_column.apply_session_id_column();
+    /// let result_df = session_column.into_lazyframe().collect().unwrap();
+    ///
+    /// let ids: Vec<Option<&str>> = result_df.column("session_id").unwrap().utf8().unwrap().into_iter().collect();
+    /// assert_eq!(ids, [Some("2024-02-22_Tokyo"), Some("2024-02-22_Tokyo"), Some("2024-02-23_London")]);
+    /// ```
+    pub fn apply_session_id_column(&mut self) {
+        let open_day = self.session_window_expr(DataType::Int64, |_, elapsed| {
+            div_euclid_expr(self.unix_seconds_expr() - elapsed, SECONDS_PER_DAY)
+        });
+        let trading_date = open_day.fill_null(self.day_expr()).cast(DataType::Int32).cast(DataType::Date).cast(DataType::Utf8);
+        let session_id = (trading_date + lit("_") + self.session_expr()).alias("session_id");
+        self.lazyframe = std::mem::take(&mut self.lazyframe).with_column(session_id);
+    }
+
+    /// Appends the "market_center", "timezone" and "liquidity_weight" columns with the metadata of the
+    /// trading session of each row, see [`TradingSession::metadata`] and [`TradingSession::liquidity_weight`].
+    ///
+    /// Rows whose session is the fallback label get the metadata of `TradingSession::Undefined`, and
+    /// rows with a null timestamp are null. A session without a single timezone, e.g. an overlap,
+    /// has a null timezone.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionColumn;
+    ///
+    /// // Thursday 22 February 2024, 10:00 and 14:00 UTC
+    /// let df = df! {
+    ///     "time" => [1708596000, 1708610400]
+    /// }.unwrap();
+    ///
+    /// let mut session_column = SessionColumn::new(df.lazy());
+    /// session_column.apply_session_metadata_columns();
+    /// let result_df = session_column.into_lazyframe().collect().unwrap();
+    ///
+    /// let timezones: Vec<Option<&str>> = result_df.column("timezone").unwrap().utf8().unwrap().into_iter().collect();
+    /// assert_eq!(timezones, [Some("Europe/London"), None]);
+    /// assert_eq!(result_df.column("market_center").unwrap().str_value(1).unwrap(), "London / New York");
+    /// assert_eq!(result_df.column("liquidity_weight").unwrap().f64().unwrap().get(1), Some(1.0));
+    /// ```
+    pub fn apply_session_metadata_columns(&mut self) {
+        let session = self.session_expr();
+        let metadata_expr = |name: &str, dtype: DataType, value: &dyn Fn(TradingSession) -> Expr| {
+            let otherwise = when(session.clone().is_null()).then(lit(NULL).cast(dtype.clone())).otherwise(value(TradingSession::Undefined));
+            TradingSession::ALL
+                .iter()
+                .rev()
+                .fold(otherwise, |otherwise, trading_session| {
+                    when(session.clone().eq(lit(trading_session.as_str()))).then(value(*trading_session)).otherwise(otherwise)
+                })
+                .cast(dtype)
+                .alias(name)
+        };
+
+        let columns = [
+            metadata_expr("market_center", DataType::Utf8, &|session| lit(session.market_center())),
+            metadata_expr("timezone", DataType::Utf8, &|session| match session.timezone() {
+                Some(timezone) => lit(timezone),
+                None => lit(NULL).cast(DataType::Utf8),
+            }),
+            metadata_expr("liquidity_weight", DataType::Float64, &|session| lit(session.liquidity_weight())),
+        ];
+        self.lazyframe = std::mem::take(&mut self.lazyframe).with_columns(columns);
+    }
+
+    /// Appends the "dominant_session" and "touched_sessions" columns for OHLC bars spanning a session
+    /// boundary, with the bar opening at the time column and closing at the given expression.
+    ///
+    /// "dominant_session" holds the session covering most of each bar, see
+    /// [`IdentifyTradingSession::dominant_session_until`], and "touched_sessions" the list of all
+    /// sessions the bar touches, see [`IdentifyTradingSession::sessions_touched_until`]. The close is
+    /// exclusive and read in the dtype and unit of the time column, so it is either a close time
+    /// column, e.g. `col("close_time")`, or the open time plus the bar duration. The schedule, market,
+    /// holidays, daylight saving time and fallback label of the column apply. Bars with a null open
+    /// or close are null.
+    ///
+    /// # Arguments
+    ///
+    /// * `close` - The expression of the time at which each bar closes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionColumn;
+    ///
+    /// // 4h bars from 08:00 and 12:00 UTC on Thursday 22 February 2024
+    /// let df = df! {
+    ///     "time" => [1708588800i64, 1708603200]
+    /// }.unwrap();
+    ///
+    /// let mut session_column = SessionColumn::new(df.lazy());
+    /// session_column.apply_bar_session_columns(col("time") + lit(4 * 3600));
+    /// let result_df = session_column.into_lazyframe().collect().unwrap();
+    ///
+    /// let dominant: Vec<Option<&str>> = result_df.column("dominant_session").unwrap().utf8().unwrap().into_iter().collect();
+    /// assert_eq!(dominant, [Some("London"), Some("London_NewYork")]);
+    ///
+    /// let touched = result_df.column("touched_sessions").unwrap().list().unwrap().get_as_series(1).unwrap();
+    /// let touched: Vec<Option<&str>> = touched.utf8().unwrap().into_iter().collect();
+    /// assert_eq!(touched, [Some("London"), Some("London_NewYork")]);
+    /// ```
+    pub fn apply_bar_session_columns(&mut self, close: Expr) {
+        let mut identifier = IdentifyTradingSession::new(0).with_market(self.market);
+        if let Some(schedule) = &self.schedule {
+            identifier = identifier.with_schedule(schedule.clone());
+        }
+        if let Some(calendar) = &self.holidays {
+            identifier = identifier.with_holidays(calendar.clone());
+        }
+        if self.dst {
+            identifier = identifier.with_dst();
+        }
+
+        let bar = [self.unix_seconds_expr(), self.unix_seconds_of(close)];
+        let (template, fallback_label) = (identifier.clone(), self.fallback_label.clone());
+        let dominant_session = map_multiple(move |bars| {
+            let dominant: Utf8Chunked = bars[0]
+                .i64()?
+                .into_iter()
+                .zip(bars[1].i64()?)
+                .map(|bar| match bar {
+                    (Some(open), Some(close)) => match (IdentifyTradingSession { unix_timestamp: open, ..template.clone() }).dominant_session_until(close) {
+                        TradingSession::Undefined => Some(fallback_label.clone()),
+                        session => Some(session.as_str().to_string()),
+                    },
+                    _ => None,
+                })
+                .collect();
+            Ok(Some(dominant.into_series()))
+        }, bar.clone(), GetOutput::from_type(DataType::Utf8))
+        .alias("dominant_session");
+
+        let fallback_label = self.fallback_label.clone();
+        let touched_sessions = map_multiple(move |bars| {
+            let touched: ListChunked = bars[0]
+                .i64()?
+                .into_iter()
+                .zip(bars[1].i64()?)
+                .map(|bar| match bar {
+                    (Some(open), Some(close)) => {
+                        let sessions = (IdentifyTradingSession { unix_timestamp: open, ..identifier.clone() }).sessions_touched_until(close);
+                        let labels: Vec<&str> = sessions
+                            .iter()
+                            .map(|session| match session {
+                                TradingSession::Undefined => fallback_label.as_str(),
+                                session => session.as_str(),
+                            })
+                            .collect();
+                        Some(Series::new("", labels))
+                    }
+                    _ => None,
+                })
+                .collect();
+            Ok(Some(touched.into_series()))
+        }, bar, GetOutput::from_type(DataType::List(Box::new(DataType::Utf8))))
+        .alias("touched_sessions");
+
+        self.lazyframe = std::mem::take(&mut self.lazyframe).with_columns([dominant_session, touched_sessions]);
+    }
+
+    /// Computes the persistence of the trading session over the rows of the LazyFrame.
+    ///
+    /// The persistence is the fraction of rows whose session equals the session `lag` rows
+    /// earlier, taken over the rows that have such an earlier row. Values close to 1.0 indicate
+    /// long runs within the same session.
+    ///
+    /// # Arguments
+    ///
+    /// * `lag` - The number of rows to look back.
+    ///
+    /// # Returns
+    ///
+    /// The persistence as a value in `[0.0, 1.0]`, or 0.0 if the LazyFrame has no more than `lag` rows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionColumn;
+    ///
+    /// // Tokyo, Tokyo, London, London, London
+    /// let df = df! {
+    ///     "time" => [1708574400, 1708578000, 1708596000, 1708599600, 1708603200]
+    /// }.unwrap();
+    ///
+    /// let session_column = SessionColumn::new(df.lazy());
+    /// assert_eq!(session_column.session_persistence(1).unwrap(), 0.75);
+    /// ```
+    pub fn session_persistence(&self, lag: usize) -> PolarsResult<f64> {
+        let persistence_df = self.lazyframe.clone()
+            .select([self.session_expr().eq(self.session_expr().shift(lit(lag as i64)))
+                .cast(DataType::Float64)
+                .mean()
+                .alias("persistence")])
+            .collect()?;
+
+        Ok(persistence_df.column("persistence")?.f64()?.get(0).unwrap_or(0.0))
+    }
+
+    /// Resamples the LazyFrame into one OHLCV bar per trading session and UTC day.
+    ///
+    /// The session column is applied internally and the rows are grouped by the "date" (the number
+    /// of days since 1 January 1970, derived from the "time" column) and the "Session". Rows are
+    /// expected to be sorted by time, so that the first and last row of each group provide the
+    /// open and close prices.
+    ///
+    /// # Arguments
+    ///
+    /// * `price_col` - The name of the price column to aggregate into open, high, low and close.
+    /// * `volume_col` - The name of an optional volume column to sum into the volume.
+    ///
+    /// # Returns
+    ///
+    /// A LazyFrame with the columns `[date, Session, open, high, low, close]`, followed by
+    /// `volume` if a volume column is given, with the groups in order of first appearance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionColumn;
+    ///
+    /// // Tokyo, Tokyo, London, London
+    /// let df = df! {
+    ///     "time" => [1708574400, 1708578000, 1708596000, 1708599600],
+    ///     "price" => [1.0, 3.0, 2.0, 4.0],
+    ///     "volume" => [10, 20, 30, 40]
+    /// }.unwrap();
+    ///
+    /// let bars = SessionColumn::new(df.lazy())
+    ///     .session_ohlcv("price", Some("volume"))
+    ///     .collect()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(bars.get_column_names(), ["date", "Session", "open", "high", "low", "close", "volume"]);
+    /// assert_eq!(bars.column("Session").unwrap().str_value(1).unwrap(), "London");
+    /// assert_eq!(bars.column("open").unwrap().f64().unwrap().get(1), Some(2.0));
+    /// assert_eq!(bars.column("high").unwrap().f64().unwrap().get(0), Some(3.0));
+    /// assert_eq!(bars.column("close").unwrap().f64().unwrap().get(1), Some(4.0));
+    /// assert_eq!(bars.column("volume").unwrap().i32().unwrap().get(1), Some(70));
+    /// ```
+    pub fn session_ohlcv(self, price_col: &str, volume_col: Option<&str>) -> LazyFrame {
+        let mut aggregations = vec![
+            col(price_col).first().alias("open"),
+            col(price_col).max().alias("high"),
+            col(price_col).min().alias("low"),
+            col(price_col).last().alias("close"),
+        ];
+        if let Some(volume_col) = volume_col {
+            aggregations.push(col(volume_col).sum().alias("volume"));
+        }
+
+        let columns = [self.day_expr().alias("date"), self.session_expr().alias(&self.output_column)];
+        self.lazyframe
+            .with_columns(columns)
+            .group_by_stable([col("date"), col(&self.output_column)])
+            .agg(aggregations)
+    }
+
+    /// Appends a column counting the distinct trading sessions within a trailing time window.
+    ///
+    /// For each row, the window covers the rows whose timestamp lies within `(time - window, time]`,
+    /// i.e. up to and including the row's own timestamp. The "time" column must be sorted in ascending order.
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - The length of the trailing window, truncated to whole seconds.
+    /// * `col_name` - The name of the column to add.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use std::time::Duration;
+    /// use trading_sessions::SessionColumn;
+    ///
+    /// // 06:00 (Tokyo), 07:00 (Tokyo_London), 08:00 (Tokyo_London) and 12:00 (London)
+    /// let df = df! {
+    ///     "time" => [1708581600, 1708585200, 1708588800, 1708603200]
+    /// }.unwrap();
+    ///
+    /// let mut session_column = SessionColumn::new(df.lazy());
+    /// session_column.rolling_distinct_sessions(Duration::from_secs(3 * 3600), "DistinctSessions");
+    /// let result_df = session_column.into_lazyframe().collect().unwrap();
+    ///
+    /// let counts: Vec<Option<u32>> = result_df.column("DistinctSessions").unwrap().u32().unwrap().into_iter().collect();
+    /// assert_eq!(counts, [Some(1), Some(2), Some(2), Some(1)]);
+    /// ```
+    pub fn rolling_distinct_sessions(&mut self, window: std::time::Duration, col_name: &str) {
+        // Integer columns are windowed by index value, which are seconds for Unix timestamps
+        let unit = if self.is_temporal() { "s" } else { "i" };
+        let options = RollingGroupOptions {
+            index_column: self.time_column().into(),
+            period: Duration::parse(&format!("{}{unit}", window.as_secs())),
+            offset: Duration::parse(&format!("-{}{unit}", window.as_secs())),
+            closed_window: ClosedWindow::Right,
+            check_sorted: false,
+        };
+
+        // The rolling window relies on the "time" column being sorted, which is a documented precondition
+        let sorted_time = self.time_expr().set_sorted_flag(IsSorted::Ascending);
+        let distinct_sessions = self.session_expr().n_unique().rolling(options).alias(col_name);
+        self.lazyframe = std::mem::take(&mut self.lazyframe)
+            .with_column(sorted_time)
+            .with_column(distinct_sessions);
+    }
+
+    /// Groups the LazyFrame by trading session and aggregates the given value columns.
+    ///
+    /// The session column is applied internally, after which the rows are grouped by "Session" and
+    /// each value column is aggregated with `agg`, keeping its name.
+    ///
+    /// # Arguments
+    ///
+    /// * `value_cols` - The names of the columns to aggregate.
+    /// * `agg` - The aggregation to apply to each value column.
+    ///
+    /// # Returns
+    ///
+    /// A LazyFrame with a "Session" column followed by the aggregated value columns, with the
+    /// sessions in order of first appearance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::{SessionAgg, SessionColumn};
+    ///
+    /// // Tokyo, Tokyo, London, London
+    /// let df = df! {
+    ///     "time" => [1708574400, 1708578000, 1708596000, 1708599600],
+    ///     "price" => [1.0, 3.0, 2.0, 4.0],
+    ///     "volume" => [10.0, 20.0, 30.0, 50.0]
+    /// }.unwrap();
+    ///
+    /// let result_df = SessionColumn::new(df.lazy())
+    ///     .group_by_session(&["price", "volume"], SessionAgg::Mean)
+    ///     .collect()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(result_df.column("Session").unwrap().str_value(0).unwrap(), "Tokyo");
+    /// assert_eq!(result_df.column("price").unwrap().f64().unwrap().get(0), Some(2.0));
+    /// assert_eq!(result_df.column("Session").unwrap().str_value(1).unwrap(), "London");
+    /// assert_eq!(result_df.column("volume").unwrap().f64().unwrap().get(1), Some(40.0));
+    /// ```
+    pub fn group_by_session(self, value_cols: &[&str], agg: SessionAgg) -> LazyFrame {
+        let session = self.session_expr().alias(&self.output_column);
+        self.lazyframe
+            .with_column(session)
+            .group_by_stable([col(&self.output_column)])
+            .agg(session_aggregations(value_cols, agg))
+    }
+
+    /// Groups the LazyFrame by UTC day and trading session and aggregates the given value columns.
+    ///
+    /// Like [`SessionColumn::group_by_session`], but each day's occurrence of a session forms its own
+    /// group, keyed by the "date" (the number of days since 1 January 1970) as in [`SessionColumn::session_ohlcv`].
+    ///
+    /// # Arguments
+    ///
+    /// * `value_cols` - The names of the columns to aggregate.
+    /// * `agg` - The aggregation to apply to each value column.
+    ///
+    /// # Returns
+    ///
+    /// A LazyFrame with the "date" and "Session" columns followed by the aggregated value columns,
+    /// with the groups in order of first appearance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::{SessionAgg, SessionColumn};
+    ///
+    /// // London on Thursday 22 February 2024 (twice) and on Friday 23 February 2024
+    /// let df = df! {
+    ///     "time" => [1708596000, 1708599600, 1708682400],
+    ///     "price" => [1.0, 3.0, 2.0]
+    /// }.unwrap();
+    ///
+    /// let result_df = SessionColumn::new(df.lazy())
+    ///     .group_by_session_and_date(&["price"], SessionAgg::Count)
+    ///     .collect()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(result_df.get_column_names(), ["date", "Session", "price"]);
+    /// let counts: Vec<Option<u32>> = result_df.column("price").unwrap().u32().unwrap().into_iter().collect();
+    /// assert_eq!(counts, [Some(2), Some(1)]);
+    /// ```
+    pub fn group_by_session_and_date(self, value_cols: &[&str], agg: SessionAgg) -> LazyFrame {
+        let columns = [self.day_expr().alias("date"), self.session_expr().alias(&self.output_column)];
+        self.lazyframe
+            .with_columns(columns)
+            .group_by_stable([col("date"), col(&self.output_column)])
+            .agg(session_aggregations(value_cols, agg))
+    }
+
+    /// Applies the trading session column transformation, classifying the rows in parallel.
+    ///
+    /// The timestamps are split into one contiguous block per available core, and each block is
+    /// classified on its own thread with the same logic as `IdentifyTradingSession`, including
+    /// daylight saving time. The resulting "Session" column is identical to the one produced by
+    /// `apply_session_column`, which is the better choice for small frames, where spawning threads
+    /// outweighs the gain.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionColumn;
+    ///
+    /// let times: Vec<i64> = (0..1_000).map(|i| 1708560000 + i * 900).collect();
+    /// let df = df! { "time" => times }.unwrap();
+    ///
+    /// let serial = SessionColumn::new(df.clone().lazy()).apply_session_column();
+    /// let mut parallel = SessionColumn::new(df.lazy());
+    /// parallel.apply_session_column_parallel();
+    ///
+    /// assert!(serial.into_lazyframe().collect().unwrap().frame_equal(&parallel.into_lazyframe().collect().unwrap()));
+    ///
+    /// // Thursday 13 June 2024, around the clock, with the boundaries of London and New York summer time
+    /// let times: Vec<i64> = (0..96).map(|i| 1718236800 + i * 900).collect();
+    /// let df = df! { "time" => times }.unwrap();
+    ///
+    /// let serial = SessionColumn::new(df.clone().lazy()).with_dst().apply_session_column();
+    /// let mut parallel = SessionColumn::new(df.lazy()).with_dst();
+    /// parallel.apply_session_column_parallel();
+    ///
+    /// assert!(serial.into_lazyframe().collect().unwrap().frame_equal(&parallel.into_lazyframe().collect().unwrap()));
+    /// ```
+    pub fn apply_session_column_parallel(&mut self) {
+        let (ticks, ticks_per_second) = match self.is_temporal() {
+            true => (self.time_expr().dt().timestamp(TimeUnit::Milliseconds), 1_000),
+            false => (col(self.time_column()).cast(DataType::Int64), self.unit.ticks_per_second()),
+        };
+
+        // Like `schedule_expr`, daylight saving time shifts the default schedule only, indexed here by
+        // whether London and New York observe summer time
+        let dst = self.dst && self.schedule.is_none();
+        let schedules = match dst {
+            true => vec![
+                self.default_schedule(false, false),
+                self.default_schedule(false, true),
+                self.default_schedule(true, false),
+                self.default_schedule(true, true),
+            ],
+            false => vec![self.effective_schedule()],
+        };
+        let holidays = self.holidays.clone();
+        let (fallback_label, null_label, weekend) = (self.fallback_label.clone(), self.null_label.clone(), self.market == Market::Forex);
+        let session = self.output_expr(
+            ticks.map(move |ticks| {
+                          let schedule_at = |unix_timestamp: i64| match dst {
+                              true => &schedules[usize::from(is_london_summer_time(unix_timestamp)) * 2 + usize::from(is_new_york_summer_time(unix_timestamp))],
+                              false => &schedules[0],
+                          };
+                          classify_parallel(&ticks, ticks_per_second, schedule_at, holidays.as_ref(), &fallback_label, null_label.as_deref(), weekend).map(Some)
+                      },
+                      GetOutput::from_type(DataType::Utf8)));
+        self.lazyframe = std::mem::take(&mut self.lazyframe).with_column(session);
+    }
+
+    /// Counts the rows of the LazyFrame per UTC hour of the day and trading session.
+    ///
+    /// Under the default schedule each hour maps to a single session on weekdays, but the counts
+    /// also reveal the weekend and any other deviation in how the hours are classified, which makes
+    /// the result a natural input for a heatmap of hour versus session.
+    ///
+    /// # Returns
+    ///
+    /// A DataFrame with the columns "hour", "Session" and "count", sorted by hour and session name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionColumn;
+    ///
+    /// // Thursday 04:00, Friday 04:00, Saturday 02:00 and Thursday 10:00 UTC
+    /// let df = df! {
+    ///     "time" => [1708574400, 1708660800, 1708740000, 1708596000]
+    /// }.unwrap();
+    ///
+    /// let heatmap = SessionColumn::new(df.lazy()).hour_session_heatmap().unwrap();
+    ///
+    /// let hours: Vec<Option<u32>> = heatmap.column("hour").unwrap().u32().unwrap().into_iter().collect();
+    /// let counts: Vec<Option<u32>> = heatmap.column("count").unwrap().u32().unwrap().into_iter().collect();
+    /// assert_eq!(hours, [Some(2), Some(4), Some(10)]);
+    /// assert_eq!(heatmap.column("Session").unwrap().str_value(0).unwrap(), "Weekend");
+    /// assert_eq!(heatmap.column("Session").unwrap().str_value(1).unwrap(), "Tokyo");
+    /// assert_eq!(heatmap.column("Session").unwrap().str_value(2).unwrap(), "London");
+    /// assert_eq!(counts, [Some(1), Some(2), Some(1)]);
+    /// ```
+    pub fn hour_session_heatmap(&self) -> PolarsResult<DataFrame> {
+        self.lazyframe.clone()
+            .select([
+                self.utc_hour_expr().cast(DataType::UInt32).alias("hour"),
+                self.session_expr().alias(&self.output_column),
+            ])
+            .group_by([col("hour"), col(&self.output_column)])
+            .agg([count().alias("count")])
+            .sort_by_exprs([col("hour"), col(&self.output_column)], [false, false], false, false)
+            .collect()
+    }
+
+    /// Counts the rows of the LazyFrame per trading session.
+    ///
+    /// This is a quick way to sanity-check the coverage of a dataset. Sessions without any rows are
+    /// left out, and an empty LazyFrame results in an empty DataFrame.
+    ///
+    /// # Returns
+    ///
+    /// A DataFrame with the columns "Session" and "count", sorted in the declaration order of
+    /// [`TradingSession::ALL`], followed by a custom fallback label.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionColumn;
+    ///
+    /// // Saturday 02:00, Thursday 10:00, Thursday 04:00, Thursday 11:00 and Friday 04:00 UTC
+    /// let df = df! {
+    ///     "time" => [1708740000, 1708596000, 1708574400, 1708599600, 1708660800]
+    /// }.unwrap();
+    ///
+    /// let counts = SessionColumn::new(df.lazy()).session_counts().unwrap();
+    ///
+    /// let sessions: Vec<Option<&str>> = counts.column("Session").unwrap().utf8().unwrap().into_iter().collect();
+    /// let rows: Vec<Option<u32>> = counts.column("count").unwrap().u32().unwrap().into_iter().collect();
+    /// assert_eq!(sessions, [Some("Tokyo"), Some("London"), Some("Weekend")]);
+    /// assert_eq!(rows, [Some(2), Some(2), Some(1)]);
+    ///
+    /// // Thursday 22:00 UTC is outside of every session, and a custom label for it sorts last
+    /// let df = df! { "time" => [1708639200, 1708596000] }.unwrap();
+    /// let counts = SessionColumn::new(df.lazy()).with_fallback_label("Off_Hours").session_counts().unwrap();
+    /// let sessions: Vec<Option<&str>> = counts.column("Session").unwrap().utf8().unwrap().into_iter().collect();
+    /// assert_eq!(sessions, [Some("London"), Some("Off_Hours")]);
+    ///
+    /// let empty = df! { "time" => Vec::<i64>::new() }.unwrap();
+    /// assert_eq!(SessionColumn::new(empty.lazy()).session_counts().unwrap().height(), 0);
+    /// ```
+    pub fn session_counts(self) -> PolarsResult<DataFrame> {
+        let (session, order) = (self.session_expr().alias(&self.output_column), self.session_order_expr());
+        self.lazyframe
+            .select([session])
+            .group_by([col(&self.output_column)])
+            .agg([count().alias("count")])
+            .sort_by_exprs([order], [false], false, false)
+            .collect()
+    }
+
+    /// Summarizes the rows of the LazyFrame per trading session, see [`SessionStats::summarize`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`SessionColumn::try_apply_session_column`], or a `TradingSessionsError::Polars`
+    /// for any error raised while collecting the summary.
+    pub fn session_stats(self) -> Result<DataFrame, TradingSessionsError> {
+        let (time_column, order) = (self.time_column().to_string(), self.session_order_expr());
+        let output_column = self.output_column.clone();
+        let rows = count().alias("rows");
+        let coverage = (col("rows").cast(DataType::Float64) / col("rows").sum().cast(DataType::Float64)).alias("coverage");
+
+        Ok(self
+            .try_apply_session_column()?
+            .into_lazyframe()
+            .group_by([col(&output_column)])
+            .agg([rows, col(&time_column).min().alias("first"), col(&time_column).max().alias("last")])
+            .with_column(coverage)
+            .sort_by_exprs([order], [false], false, false)
+            .collect()?)
+    }
+
+    /// Reports the data coverage of each session occurrence against an expected bar interval, see [`SessionStats::coverage_report`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`SessionColumn::try_apply_session_column`], or a `TradingSessionsError::Polars`
+    /// for any error raised while collecting the report.
+    pub fn coverage_report(self, expected_interval: u32) -> Result<DataFrame, TradingSessionsError> {
+        let interval = i64::from(expected_interval.max(1));
+        let (unix_seconds, output_column) = (self.unix_seconds_expr(), self.output_column.clone());
+        let mut column = self.try_apply_session_column()?;
+        column.apply_session_id_column();
+        column.apply_session_bounds_columns();
+
+        let occurrences = column
+            .into_lazyframe()
+            .filter(col("session_open").is_not_null())
+            .group_by([col("session_id")])
+            .agg([
+                col(&output_column).first(),
+                col("session_open").first(),
+                col("session_close").first(),
+                unix_seconds.sort(false).alias("times"),
+            ])
+            .sort("session_open", SortOptions::default())
+            .collect()?;
+
+        let (mut expected, mut actual) = (Vec::with_capacity(occurrences.height()), Vec::with_capacity(occurrences.height()));
+        let (mut gap_starts, mut gap_ends) = (Vec::with_capacity(occurrences.height()), Vec::with_capacity(occurrences.height()));
+        let bounds = occurrences.column("session_open")?.i64()?.into_iter().zip(occurrences.column("session_close")?.i64()?);
+        for ((open, close), times) in bounds.zip(occurrences.column("times")?.list()?) {
+            let (open, close) = (open.unwrap_or_default(), close.unwrap_or_default());
+            let times: Vec<i64> = match times {
+                Some(times) => times.i64()?.into_iter().flatten().collect(),
+                None => Vec::new(),
+            };
+
+            // A gap is a span of at least one missing bar, between the session bounds and the rows
+            let (mut starts, mut ends, mut previous) = (Vec::new(), Vec::new(), open - interval);
+            for time in times.iter().copied().chain(core::iter::once(close)) {
+                if time - previous > interval {
+                    starts.push(previous + interval);
+                    ends.push(time);
+                }
+                previous = time;
+            }
+
+            expected.push(((close - open + interval - 1) / interval) as u32);
+            actual.push(times.len() as u32);
+            gap_starts.push(Series::new("", starts));
+            gap_ends.push(Series::new("", ends));
+        }
+
+        let expected = Series::new("expected", expected);
+        let actual = Series::new("actual", actual);
+        let coverage = (actual.cast(&DataType::Float64)? / expected.cast(&DataType::Float64)?).with_name("coverage");
+        Ok(DataFrame::new(vec![
+            occurrences.column("session_id")?.clone(),
+            occurrences.column(&output_column)?.clone(),
+            occurrences.column("session_open")?.clone(),
+            occurrences.column("session_close")?.clone(),
+            expected,
+            actual,
+            coverage,
+            Series::new("gap_starts", gap_starts),
+            Series::new("gap_ends", gap_ends),
+        ])?)
+    }
+
+    /// Filters the LazyFrame down to the rows falling within the given trading session.
+    ///
+    /// The session expression is fused into the filter predicate, so no "Session" column is
+    /// materialized and Polars can push the predicate down as a single expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - The trading session to keep.
+    ///
+    /// # Returns
+    ///
+    /// The SessionColumn instance with the filtered LazyFrame, for chaining.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::{SessionColumn, TradingSession};
+    ///
+    /// let df = df! {
+    ///     "time" => [1708574400, 1708596000, 1708696800, 1708599600]
+    /// }.unwrap();
+    ///
+    /// let result_df = SessionColumn::new(df.clone().lazy())
+    ///     .filter_by_session(TradingSession::London)
+    ///     .into_lazyframe()
+    ///     .collect()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(result_df.height(), 2);
+    /// assert!(result_df.column("Session").is_err());
+    ///
+    /// // Chained after the session column transformation
+    /// let result_df = SessionColumn::new(df.lazy())
+    ///     .apply_session_column()
+    ///     .filter_by_session(TradingSession::London)
+    ///     .into_lazyframe()
+    ///     .collect()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(result_df.height(), 2);
+    /// assert_eq!(result_df.column("Session").unwrap().str_value(0).unwrap(), "London");
+    /// ```
+    #[must_use]
+    pub fn filter_by_session(mut self, session: TradingSession) -> Self {
+        let predicate = self.session_expr().eq(lit(self.session_label(session)));
+        self.lazyframe = self.lazyframe.filter(predicate);
+        self
+    }
+
+    /// Filters the rows of the LazyFrame to the timestamps falling within any of the given trading sessions.
+    ///
+    /// Like [`SessionColumn::filter_by_session`], the session expression is fused into the filter
+    /// predicate, so no "Session" column needs to be materialized first.
+    ///
+    /// # Arguments
+    ///
+    /// * `sessions` - The trading sessions to keep. An empty slice keeps no rows.
+    ///
+    /// # Returns
+    ///
+    /// The SessionColumn instance with the filtered LazyFrame, for chaining.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::{SessionColumn, TradingSession};
+    ///
+    /// // Tokyo, London, London_NewYork and the weekend
+    /// let df = df! {
+    ///     "time" => [1708574400, 1708596000, 1708696800, 1708740000]
+    /// }.unwrap();
+    ///
+    /// let result_df = SessionColumn::new(df.lazy())
+    ///     .filter_sessions(&[TradingSession::London, TradingSession::LondonNewYork])
+    ///     .into_lazyframe()
+    ///     .collect()
+    ///     .unwrap();
+    ///
+    /// let times: Vec<Option<i32>> = result_df.column("time").unwrap().i32().unwrap().into_iter().collect();
+    /// assert_eq!(times, [Some(1708596000), Some(1708696800)]);
+    /// ```
+    #[must_use]
+    pub fn filter_sessions(mut self, sessions: &[TradingSession]) -> Self {
+        let session = self.session_expr();
+        let predicate = sessions
+            .iter()
+            .fold(lit(false), |predicate, &kept| predicate.or(session.clone().eq(lit(self.session_label(kept)))));
+        self.lazyframe = self.lazyframe.filter(predicate);
+        self
+    }
+
+    /// Consumes the SessionColumn, returning the transformed LazyFrame.
+    ///
+    /// # Returns
+    ///
+    /// The LazyFrame with all transformations applied so far.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionColumn;
+    ///
+    /// let df = df! {
+    ///     "time" => [1708574400, 1708596000]
+    /// }.unwrap();
+    ///
+    /// let lazy_frame = SessionColumn::new(df.lazy()).apply_session_column().into_lazyframe();
+    /// assert_eq!(lazy_frame.collect().unwrap().width(), 2);
+    /// ```
+    pub fn into_lazyframe(self) -> LazyFrame {
+        self.lazyframe
+    }
+
+    /// Collects the transformed LazyFrame with the Polars streaming engine, processing a scan in
+    /// batches rather than materializing it whole, e.g. for backfills larger than memory.
+    ///
+    /// The session expressions are elementwise, so the session column and the columns derived from
+    /// a single row, e.g. the indicator or bounds columns, run within the streaming engine. Columns
+    /// comparing neighbouring rows, e.g. [`SessionColumn::apply_session_change_column`], fall back
+    /// to the default engine for their part of the plan.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors raised while collecting the LazyFrame, e.g. a missing timestamp column.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionColumn;
+    ///
+    /// // A week of minute timestamps from Monday 19 February 2024, 00:00 UTC
+    /// let times: Vec<i64> = (0..7 * 1440).map(|minute| 1708300800 + minute * 60).collect();
+    /// let df = df! { "time" => times }.unwrap();
+    ///
+    /// let session_column = || {
+    ///     let mut session_column = SessionColumn::new(df.clone().lazy()).with_dst().apply_session_column();
+    ///     session_column.apply_session_bounds_columns();
+    ///     session_column
+    /// };
+    ///
+    /// let plan = session_column().into_lazyframe().with_streaming(true).describe_optimized_plan().unwrap();
+    /// assert!(plan.contains("STREAMING"));
+    ///
+    /// let streamed = session_column().collect_streaming().unwrap();
+    /// assert!(streamed.frame_equal_missing(&session_column().into_lazyframe().collect().unwrap()));
+    /// ```
+    #[cfg(feature = "streaming")]
+    pub fn collect_streaming(self) -> PolarsResult<DataFrame> {
+        self.lazyframe.with_streaming(true).collect()
+    }
+
+    /// Returns the name of the column holding the timestamps.
+    fn time_column(&self) -> &str {
+        self.datetime_column.as_deref().unwrap_or(&self.input_column)
+    }
+
+    /// Checks that the column holding the timestamps exists and is of a dtype sessions can be identified from.
+    fn check_time_column(&self) -> Result<(), TradingSessionsError> {
+        let schema = self.lazyframe.schema()?;
+        let dtype = schema
+            .get(self.time_column())
+            .ok_or_else(|| TradingSessionsError::MissingColumn(self.time_column().to_string()))?;
+
+        let (valid, expected) = match &self.datetime_column {
+            Some(_) if self.parse_strings => (dtype == &DataType::Utf8, "Utf8"),
+            Some(_) => (matches!(dtype, DataType::Datetime(_, _) | DataType::Date), "Datetime or Date"),
+            None => (dtype.is_integer() || matches!(dtype, DataType::Datetime(_, _) | DataType::Date), "integer, Datetime or Date"),
+        };
+        match valid {
+            true => Ok(()),
+            false => Err(TradingSessionsError::WrongDtype { column: self.time_column().to_string(), expected }),
+        }
+    }
+
+    /// Checks that the configured schedule is valid and not combined with daylight saving time.
+    fn check_schedule(&self) -> Result<(), TradingSessionsError> {
+        if let Some(schedule) = &self.schedule {
+            schedule.validate()?;
+            if self.dst {
+                return Err(TradingSessionsError::DstWithSchedule);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns whether the timestamps are held in a temporal column, either configured with
+    /// [`SessionColumn::with_datetime_column`] or detected from the dtype of the input column.
+    fn is_temporal(&self) -> bool {
+        self.datetime_column.is_some()
+            || self.lazyframe.schema().is_ok_and(|schema| matches!(schema.get(&self.input_column), Some(DataType::Datetime(_, _) | DataType::Date)))
+    }
+
+    /// Converts a temporal expression to a Datetime in milliseconds, parsing it first if the timestamps are strings.
+    fn datetime_of(&self, time: Expr) -> Expr {
+        let time = match self.parse_strings {
+            true => {
+                let options = StrptimeOptions { format: self.time_format.clone(), strict: false, exact: true, cache: true };
+                time.str().to_datetime(Some(TimeUnit::Milliseconds), None, options, lit("raise"))
+            }
+            false => time,
+        };
+        time.cast(DataType::Datetime(TimeUnit::Milliseconds, None))
+    }
+
+    /// Builds the expression selecting the column holding the timestamps.
+    fn time_expr(&self) -> Expr {
+        match self.is_temporal() {
+            // Dates are cast to midnight UTC, and every Datetime to one unit, so the time zone and
+            // unit of the input never affect the classification
+            true => self.datetime_of(col(self.time_column())),
+            // Unix timestamps are cast to whole seconds, so the expressions use integer division at
+            // every session boundary, exactly like the scalar classification
+            false => match self.unit {
+                TimestampUnit::Seconds => col(self.time_column()).cast(DataType::Int64),
+                unit => div_euclid_expr(col(self.time_column()).cast(DataType::Int64), unit.ticks_per_second() as u32),
+            },
+        }
+    }
+
+    /// Builds the expression computing the hour of the day in UTC.
+    fn utc_hour_expr(&self) -> Expr {
+        match self.is_temporal() {
+            true => self.time_expr().dt().hour(),
+            false => rem_euclid_expr(self.time_expr(), SECONDS_PER_DAY) / lit(SECONDS_PER_HOUR),
+        }
+    }
+
+    /// Builds the expression computing the number of seconds elapsed since midnight UTC.
+    fn seconds_of_day_expr(&self) -> Expr {
+        match self.is_temporal() {
+            true => {
+                self.time_expr().dt().hour().cast(DataType::Int32) * lit(SECONDS_PER_HOUR)
+                    + self.time_expr().dt().minute().cast(DataType::Int32) * lit(60)
+                    + self.time_expr().dt().second().cast(DataType::Int32)
+            }
+            false => rem_euclid_expr(self.time_expr(), SECONDS_PER_DAY),
+        }
+    }
+
+    /// Builds the expression computing whether the timestamp falls on the weekend of the schedule, by
+    /// default between Friday 22:00 and Sunday 22:00 UTC.
+    fn is_weekend_expr(&self) -> Expr {
+        if self.market == Market::Crypto {
+            return lit(false);
+        }
+
+        if let Some((start, end)) = self.schedule.as_ref().and_then(|schedule| schedule.weekend) {
+            // 1 January 1970 was a Thursday, so shifting by 4 days counts the seconds since Sunday 00:00 UTC
+            let shifted = self.unix_seconds_expr() + lit(4 * i64::from(SECONDS_PER_DAY));
+            let seconds_of_week = rem_euclid_expr(shifted, SECONDS_PER_DAY * DAYS_PER_WEEK);
+            let (after_start, before_end) = (seconds_of_week.clone().gt_eq(lit(start)), seconds_of_week.lt(lit(end)));
+            return match start <= end {
+                true => after_start.and(before_end),
+                false => after_start.or(before_end),
+            };
+        }
+
+        match self.is_temporal() {
+            // ISO weekdays run from Monday = 1 to Sunday = 7
+            true => {
+                let (weekday, hour) = (self.time_expr().dt().weekday(), self.time_expr().dt().hour());
+                (weekday.clone().eq(lit(5)).and(hour.clone().gt_eq(lit(22))))
+                    .or(weekday.clone().eq(lit(6)))
+                    .or(weekday.eq(lit(7)).and(hour.lt(lit(22))))
+            }
+            false => {
+                // 1 January 1970 was a Thursday, so shifting by 4 days maps Sunday to 0 and Saturday to 6
+                let shifted = self.time_expr() + lit(WEEKEND_SHIFT);
+                let day_of_week = rem_euclid_expr(div_euclid_expr(shifted, SECONDS_PER_DAY) + lit(4), DAYS_PER_WEEK);
+                day_of_week.clone().eq(lit(SUNDAY)).or(day_of_week.eq(lit(SATURDAY)))
+            }
+        }
+    }
+
+    /// Returns the configured schedule, or the default one for the market if none is set.
+    fn effective_schedule(&self) -> SessionSchedule {
+        match &self.schedule {
+            Some(schedule) => schedule.clone(),
+            None => self.default_schedule(false, false),
+        }
+    }
+
+    /// Returns the default schedule of the market, with the boundaries set by London and New York shifted for summer time as given.
+    fn default_schedule(&self, london: bool, new_york: bool) -> SessionSchedule {
+        let windows = shifted_forex_windows(london, new_york);
+        match self.market {
+            Market::Forex => SessionSchedule { windows: windows.to_vec(), weekend: None },
+            // Crypto markets trade around the clock, so the hours after New York are folded into Tokyo
+            Market::Crypto => SessionSchedule {
+                windows: windows
+                    .map(|window| match window.session {
+                        TradingSession::Undefined => SessionWindow { session: TradingSession::Tokyo, ..window },
+                        _ => window,
+                    })
+                    .to_vec(),
+                weekend: None,
+            },
+        }
+    }
+
+    /// Builds an expression from the effective schedule, or with daylight saving time enabled from the
+    /// default schedule as shifted for London and New York summer time at each row.
+    fn schedule_expr(&self, f: impl Fn(&SessionSchedule) -> Expr) -> Expr {
+        if !self.dst || self.schedule.is_some() {
+            return f(&self.effective_schedule());
+        }
+
+        let london = summer_time_expr(self.unix_seconds_expr(), is_london_summer_time);
+        let new_york = summer_time_expr(self.unix_seconds_expr(), is_new_york_summer_time);
+        when(london.clone().and(new_york.clone()))
+            .then(f(&self.default_schedule(true, true)))
+            .when(london)
+            .then(f(&self.default_schedule(true, false)))
+            .when(new_york)
+            .then(f(&self.default_schedule(false, true)))
+            .otherwise(f(&self.default_schedule(false, false)))
+    }
+
+    /// Builds the expression computing the Unix timestamp in whole seconds.
+    fn unix_seconds_expr(&self) -> Expr {
+        self.unix_seconds_of(col(self.time_column()))
+    }
+
+    /// Builds the expression computing the Unix timestamps in whole seconds of a time expression
+    /// in the dtype and unit of the time column.
+    fn unix_seconds_of(&self, time: Expr) -> Expr {
+        match self.is_temporal() {
+            true => div_euclid_expr(self.datetime_of(time).dt().timestamp(TimeUnit::Milliseconds), 1_000),
+            false => match self.unit {
+                TimestampUnit::Seconds => time.cast(DataType::Int64),
+                unit => div_euclid_expr(time.cast(DataType::Int64), unit.ticks_per_second() as u32),
+            },
+        }
+    }
+
+    /// Builds the expression computing the number of days since 1 January 1970.
+    fn day_expr(&self) -> Expr {
+        match self.is_temporal() {
+            true => div_euclid_expr(self.time_expr().dt().timestamp(TimeUnit::Milliseconds), SECONDS_PER_DAY * 1_000),
+            false => div_euclid_expr(self.time_expr(), SECONDS_PER_DAY),
+        }
+    }
+
+    /// Builds the expression mapping the timestamps to the trading session names.
+    fn session_expr(&self) -> Expr {
+        let session = self.schedule_expr(|schedule| {
+            schedule_session_expr(self.seconds_of_day_expr(), self.is_weekend_expr(), schedule, &self.fallback_label)
+        });
+        let session = match &self.holidays {
+            Some(calendar) => self.holiday_expr(session, calendar),
+            None => session,
+        };
+
+        let null_label = match &self.null_label {
+            Some(label) => lit(label.as_str()),
+            None => lit(NULL).cast(DataType::Utf8),
+        };
+        when(self.time_expr().is_null()).then(null_label).otherwise(session)
+    }
+
+    /// Wraps an expression of session names with the holidays, early closes and late opens of a calendar.
+    fn holiday_expr(&self, session: Expr, calendar: &HolidayCalendar) -> Expr {
+        // Early closes and late opens only apply to the session they override, so they are checked against the session names
+        let overridden = |date: (i32, u32, u32), overridden_session: TradingSession| {
+            let (year, month, day) = date;
+            self.day_expr().eq(lit(days_from_civil(year, month, day))).and(session.clone().eq(lit(overridden_session.as_str())))
+        };
+        let closed_early = calendar.early_closes.iter().map(|early_close| {
+            overridden(early_close.date, early_close.session)
+                .and(self.seconds_of_day_expr().gt_eq(lit(u32::from(early_close.close_hour) * SECONDS_PER_HOUR)))
+        });
+        let opened_late = calendar.late_opens.iter().map(|late_open| {
+            overridden(late_open.date, late_open.session)
+                .and(self.seconds_of_day_expr().lt(lit(u32::from(late_open.open_hour) * SECONDS_PER_HOUR)))
+        });
+        let session = closed_early.chain(opened_late).fold(session.clone(), |otherwise, closed| {
+            when(closed).then(lit(self.fallback_label.as_str())).otherwise(otherwise)
+        });
+
+        let holiday = calendar.days.iter().fold(lit(false), |holiday, &day| holiday.or(self.day_expr().eq(lit(day))));
+        when(holiday).then(lit(TradingSession::Holiday.as_str())).otherwise(session)
+    }
+
+    /// Returns the label a trading session is emitted with, honoring the configured fallback label.
+    fn session_label(&self, session: TradingSession) -> &str {
+        match session {
+            TradingSession::Undefined => &self.fallback_label,
+            session => session.as_str(),
+        }
+    }
+
+    /// Builds an expression from the session window of each row and the seconds elapsed since the window opened.
+    ///
+    /// Rows outside of every scheduled session, e.g. during the weekend or on holidays, are null of the given dtype.
+    fn session_window_expr(&self, dtype: DataType, f: impl Fn(&SessionWindow, Expr) -> Expr) -> Expr {
+        let (session, seconds_of_day) = (self.session_expr(), self.seconds_of_day_expr());
+        self.schedule_expr(|schedule| {
+            schedule
+                .windows
+                .iter()
+                .rev()
+                .filter(|window| !matches!(window.session, TradingSession::Undefined | TradingSession::Closed))
+                .fold(lit(NULL).cast(dtype.clone()), |otherwise, window| {
+                    let after_start = seconds_of_day.clone().gt_eq(lit(window.start));
+                    let before_end = seconds_of_day.clone().lt(lit(window.end));
+                    let within = match window.start <= window.end {
+                        true => after_start.and(before_end),
+                        false => after_start.or(before_end),
+                    };
+                    let elapsed = rem_euclid_expr(seconds_of_day.clone() - lit(window.start), SECONDS_PER_DAY);
+                    when(within.and(session.clone().eq(lit(window.session.as_str()))))
+                        .then(f(window, elapsed))
+                        .otherwise(otherwise)
+                })
+        })
+    }
+
+    /// Builds the expression ranking the session names of the output column in the declaration order of
+    /// `TradingSession::ALL`, with a custom fallback label ranked after Undefined.
+    fn session_order_expr(&self) -> Expr {
+        let fallback = when(col(&self.output_column).eq(lit(self.fallback_label.as_str())))
+            .then(lit(TradingSession::ALL.len() as u32))
+            .otherwise(lit(NULL).cast(DataType::UInt32));
+        TradingSession::ALL
+            .iter()
+            .enumerate()
+            .rev()
+            .fold(fallback, |otherwise, (index, session)| {
+                when(col(&self.output_column).eq(lit(session.as_str())))
+                    .then(lit(index as u32))
+                    .otherwise(otherwise)
+            })
+    }
+
+    /// Turns an expression of session names into the output column, honoring the configured dtype.
+    fn output_expr(&self, session: Expr) -> Expr {
+        let session = match (self.session_ids, self.categorical) {
+            (true, _) => TradingSession::ALL.iter().rev().fold(lit(NULL).cast(DataType::UInt32), |otherwise, trading_session| {
+                when(session.clone().eq(lit(trading_session.as_str())))
+                    .then(lit(u32::from(trading_session.id())))
+                    .otherwise(otherwise)
+            })
+            .cast(DataType::UInt8),
+            (false, true) => self.renamed_expr(session).cast(DataType::Categorical(None)),
+            (false, false) => self.renamed_expr(session),
+        };
+        session.alias(&self.output_column)
+    }
+
+    /// Renames the session names of an expression to their output aliases, if any.
+    fn renamed_expr(&self, session: Expr) -> Expr {
+        let Some(aliases) = &self.output_aliases else {
+            return session;
+        };
+        TradingSession::ALL.iter().fold(session.clone(), |otherwise, trading_session| match aliases.alias_of(*trading_session) {
+            Some(alias) => when(session.clone().eq(lit(trading_session.as_str()))).then(lit(alias)).otherwise(otherwise),
+            None => otherwise,
+        })
+    }
+}
+
+
+#[cfg(feature = "polars")]
+impl SessionColumnBuilder {
+    /// Creates a builder with the defaults of [`SessionColumn::new`], reading the "time" column and writing the "Session" column.
+    pub fn new() -> Self {
+        Self { column: SessionColumn::new(LazyFrame::default()), column_pairs: Vec::new(), extra_columns: Vec::new() }
+    }
+
+    /// Sets the names of the timestamp column and of the session column to add, see [`SessionColumn::with_columns`].
+    pub fn with_columns(mut self, input_col: &str, output_col: &str) -> Self {
+        self.column = self.column.with_columns(input_col, output_col);
+        self
+    }
+
+    /// Adds a session column for each `(input_col, output_col)` pair instead of the single session
+    /// column, e.g. for the entry and exit times of trades, see [`SessionColumn::apply_for_columns`].
+    ///
+    /// The additional columns, e.g. [`SessionColumnBuilder::with_indicator_columns`], still read
+    /// the timestamp column set with [`SessionColumnBuilder::with_columns`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionColumnBuilder;
+    ///
+    /// let builder = SessionColumnBuilder::new().with_column_pairs(&[("entry_time", "entry_session"), ("exit_time", "exit_session")]);
+    ///
+    /// let df = df! {
+    ///     "entry_time" => [1708574400],
+    ///     "exit_time" => [1708596000]
+    /// }.unwrap();
+    /// let result_df = builder.apply(df.lazy()).unwrap().collect().unwrap();
+    ///
+    /// assert_eq!(result_df.column("entry_session").unwrap().str_value(0).unwrap(), "Tokyo");
+    /// assert_eq!(result_df.column("exit_session").unwrap().str_value(0).unwrap(), "London");
+    /// ```
+    pub fn with_column_pairs(mut self, mappings: &[(&str, &str)]) -> Self {
+        self.column_pairs = mappings.iter().map(|(input_col, output_col)| (input_col.to_string(), output_col.to_string())).collect();
+        self
+    }
+
+    /// Emits the session column as a Categorical instead of a Utf8 column, see [`SessionColumn::with_categorical`].
+    pub fn with_categorical(mut self, enabled: bool) -> Self {
+        self.column = self.column.with_categorical(enabled);
+        self
+    }
+
+    /// Shifts the London and New York boundaries of the default schedule for daylight saving time, see [`SessionColumn::with_dst`].
+    pub fn with_dst(mut self) -> Self {
+        self.column = self.column.with_dst();
+        self
+    }
+
+    /// Emits the session column as UInt8 session IDs instead of session names, see [`SessionColumn::with_session_ids`].
+    pub fn with_session_ids(mut self, enabled: bool) -> Self {
+        self.column = self.column.with_session_ids(enabled);
+        self
+    }
+
+    /// Renames the sessions in the session column to their aliases, see [`SessionColumn::with_output_aliases`].
+    pub fn with_output_aliases(mut self, aliases: &SessionAliases) -> Self {
+        self.column = self.column.with_output_aliases(aliases);
+        self
+    }
+
+    /// Reads the timestamps from a `Datetime` or `Date` column, see [`SessionColumn::with_datetime_column`].
+    pub fn with_datetime_column(mut self, col_name: &str) -> Self {
+        self.column = self.column.with_datetime_column(col_name);
+        self
+    }
+
+    /// Reads the timestamps from a Utf8 column of datetime strings, see [`SessionColumn::with_string_column`].
+    pub fn with_string_column(mut self, col_name: &str, format: Option<&str>) -> Self {
+        self.column = self.column.with_string_column(col_name, format);
+        self
+    }
+
+    /// Sets the unit of the integer Unix timestamps, see [`SessionColumn::with_timestamp_unit`].
+    pub fn with_timestamp_unit(mut self, unit: TimestampUnit) -> Self {
+        self.column = self.column.with_timestamp_unit(unit);
+        self
+    }
+
+    /// Sets the label of the timestamps outside of every session, see [`SessionColumn::with_fallback_label`].
+    pub fn with_fallback_label(mut self, label: &str) -> Self {
+        self.column = self.column.with_fallback_label(label);
+        self
+    }
+
+    /// Sets the label of the rows with a null timestamp, see [`SessionColumn::with_null_label`].
+    pub fn with_null_label(mut self, label: &str) -> Self {
+        self.column = self.column.with_null_label(label);
+        self
+    }
+
+    /// Sets a calendar of holidays, see [`SessionColumn::with_holidays`].
+    pub fn with_holidays(mut self, calendar: HolidayCalendar) -> Self {
+        self.column = self.column.with_holidays(calendar);
+        self
+    }
+
+    /// Selects the sessions of a set of trading centers, see [`SessionColumn::with_session_set`].
+    pub fn with_session_set(mut self, set: SessionSet) -> Self {
+        self.column = self.column.with_session_set(set);
+        self
+    }
+
+    /// Sets the market whose trading hours are used, see [`SessionColumn::with_market`].
+    pub fn with_market(mut self, market: Market) -> Self {
+        self.column = self.column.with_market(market);
+        self
+    }
+
+    /// Sets a custom schedule of session windows, see [`SessionColumn::with_schedule`].
+    pub fn with_schedule(mut self, schedule: SessionSchedule) -> Self {
+        self.column = self.column.with_schedule(schedule);
+        self
+    }
+
+    /// Appends one boolean indicator column per trading session after the session column, see [`SessionColumn::apply_session_indicator_columns`].
+    pub fn with_indicator_columns(mut self) -> Self {
+        self.extra_columns.push(SessionColumn::apply_session_indicator_columns);
+        self
+    }
+
+    /// Appends the "Overlap" column after the session column, see [`SessionColumn::apply_overlap_column`].
+    pub fn with_overlap_column(mut self) -> Self {
+        self.extra_columns.push(SessionColumn::apply_overlap_column);
+        self
+    }
+
+    /// Appends the "ActiveSessions" column after the session column, see [`SessionColumn::apply_active_sessions_column`].
+    pub fn with_active_sessions_column(mut self) -> Self {
+        self.extra_columns.push(SessionColumn::apply_active_sessions_column);
+        self
+    }
+
+    /// Appends the "Killzone" column after the session column, see [`SessionColumn::apply_killzone_column`].
+    pub fn with_killzone_column(mut self) -> Self {
+        self.extra_columns.push(SessionColumn::apply_killzone_column);
+        self
+    }
+
+    /// Appends the "session_progress" column after the session column, see [`SessionColumn::apply_session_progress_column`].
+    pub fn with_session_progress_column(mut self) -> Self {
+        self.extra_columns.push(SessionColumn::apply_session_progress_column);
+        self
+    }
+
+    /// Appends the session metadata columns after the session column, see [`SessionColumn::apply_session_metadata_columns`].
+    pub fn with_session_metadata_columns(mut self) -> Self {
+        self.extra_columns.push(SessionColumn::apply_session_metadata_columns);
+        self
+    }
+
+    /// Appends the "session_id" column after the session column, see [`SessionColumn::apply_session_id_column`].
+    pub fn with_session_id_column(mut self) -> Self {
+        self.extra_columns.push(SessionColumn::apply_session_id_column);
+        self
+    }
+
+    /// Appends the "session_open" and "session_close" columns after the session column, see [`SessionColumn::apply_session_bounds_columns`].
+    pub fn with_session_bounds_columns(mut self) -> Self {
+        self.extra_columns.push(SessionColumn::apply_session_bounds_columns);
+        self
+    }
+
+    /// Appends the "MarketOpen" column after the session column, see [`SessionColumn::apply_market_open_column`].
+    pub fn with_market_open_column(mut self) -> Self {
+        self.extra_columns.push(SessionColumn::apply_market_open_column);
+        self
+    }
+
+    /// Appends the "utc_day" column after the session column, see [`SessionColumn::apply_utc_day_column`].
+    pub fn with_utc_day_column(mut self) -> Self {
+        self.extra_columns.push(SessionColumn::apply_utc_day_column);
+        self
+    }
+
+    /// Appends the "trading_date" column after the session column, see [`SessionColumn::apply_trading_date_column`].
+    pub fn with_trading_date_column(mut self) -> Self {
+        self.extra_columns.push(SessionColumn::apply_trading_date_column);
+        self
+    }
+
+    /// Applies the configured session column, followed by the additional columns in the order they were added.
+    ///
+    /// # Arguments
+    ///
+    /// * `lazyframe` - The LazyFrame holding the timestamp column.
+    ///
+    /// # Returns
+    ///
+    /// The LazyFrame with the columns added.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`SessionColumn::try_apply_session_column`], e.g. a
+    /// `TradingSessionsError::MissingColumn` if the LazyFrame has no timestamp column, or those of
+    /// [`SessionColumn::apply_for_columns`] with column pairs.
+    pub fn apply(&self, lazyframe: LazyFrame) -> Result<LazyFrame, TradingSessionsError> {
+        let mut column = SessionColumn { lazyframe, ..self.column.clone() };
+        match self.column_pairs.is_empty() {
+            true => column = column.try_apply_session_column()?,
+            false => {
+                let mappings: Vec<(&str, &str)> = self.column_pairs.iter().map(|(input_col, output_col)| (input_col.as_str(), output_col.as_str())).collect();
+                column.apply_for_columns(&mappings)?;
+            }
+        }
+        for apply_extra_column in &self.extra_columns {
+            apply_extra_column(&mut column);
+        }
+        Ok(column.into_lazyframe())
+    }
+}
+
+
+#[cfg(feature = "polars")]
+impl SessionStats {
+    /// Summarizes a LazyFrame of timestamps per trading session under the default schedule.
+    ///
+    /// Sessions without any rows are left out. For a custom schedule or other options, use
+    /// [`SessionColumn::session_stats`] on a configured SessionColumn instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `lazyframe` - The LazyFrame, e.g. of an incoming market data feed.
+    /// * `time_col` - The column of Unix timestamps in seconds, or of `Datetime` or `Date` values.
+    ///
+    /// # Returns
+    ///
+    /// A DataFrame with the columns "Session", "rows", "first" and "last", the earliest and latest
+    /// timestamp of the session, and "coverage", the share of all rows falling within the session.
+    /// The sessions are sorted in the declaration order of [`TradingSession::ALL`], followed by a custom fallback label.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TradingSessionsError::MissingColumn` or `TradingSessionsError::WrongDtype` if the
+    /// timestamp column is missing or of an unsupported dtype, or a `TradingSessionsError::Polars`
+    /// for any error raised while collecting the summary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionStats;
+    ///
+    /// // Thursday 22 February 2024, 04:00, 10:00, 11:00 and 12:00 UTC
+    /// let df = df! {
+    ///     "time" => [1708574400i64, 1708596000, 1708599600, 1708603200]
+    /// }.unwrap();
+    ///
+    /// let stats = SessionStats::summarize(df.lazy(), "time").unwrap();
+    ///
+    /// let sessions: Vec<Option<&str>> = stats.column("Session").unwrap().utf8().unwrap().into_iter().collect();
+    /// assert_eq!(sessions, [Some("Tokyo"), Some("London")]);
+    /// assert_eq!(stats.column("rows").unwrap().u32().unwrap().get(1), Some(3));
+    /// assert_eq!(stats.column("first").unwrap().i64().unwrap().get(1), Some(1708596000));
+    /// assert_eq!(stats.column("last").unwrap().i64().unwrap().get(1), Some(1708603200));
+    /// assert_eq!(stats.column("coverage").unwrap().f64().unwrap().get(0), Some(0.25));
+    /// ```
+    pub fn summarize(lazyframe: LazyFrame, time_col: &str) -> Result<DataFrame, TradingSessionsError> {
+        SessionColumn::new(lazyframe).with_columns(time_col, "Session").session_stats()
+    }
+
+    /// Reports the data coverage of each session occurrence against an expected bar interval, for
+    /// monitoring the quality of bar data session by session.
+    ///
+    /// Each occurrence of a session, as keyed by [`SessionColumn::apply_session_id_column`], expects one
+    /// row per interval between its bounds. Spans of at least one missing bar are listed as gaps,
+    /// including those at the start and end of the occurrence. Rows outside of every scheduled
+    /// session, e.g. during the weekend, and session occurrences without any rows are left out. An
+    /// interval of zero counts as one second. For a custom schedule or other options, use
+    /// [`SessionColumn::coverage_report`] on a configured SessionColumn instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `lazyframe` - The LazyFrame of bars.
+    /// * `time_col` - The column of Unix timestamps in seconds, or of `Datetime` or `Date` values.
+    /// * `expected_interval` - The expected interval between two rows in seconds, e.g. `60` for minute bars.
+    ///
+    /// # Returns
+    ///
+    /// A DataFrame with the columns "session_id", "Session", "session_open" and "session_close", the
+    /// "expected" and "actual" number of rows, "coverage", their ratio, and the "gap_starts" and
+    /// "gap_ends" lists holding the bounds of each gap in Unix seconds, end exclusive. The occurrences
+    /// are sorted by their opening time.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TradingSessionsError::MissingColumn` or `TradingSessionsError::WrongDtype` if the
+    /// timestamp column is missing or of an unsupported dtype, or a `TradingSessionsError::Polars`
+    /// for any error raised while collecting the report.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::SessionStats;
+    ///
+    /// // Hourly bars on Thursday 22 February 2024 at 04:00 UTC, and at 09:00, 10:00 and 12:00 UTC in
+    /// // the London session from 09:00 to 13:00 UTC
+    /// let df = df! {
+    ///     "time" => [1708574400i64, 1708592400, 1708596000, 1708603200]
+    /// }.unwrap();
+    ///
+    /// let report = SessionStats::coverage_report(df.lazy(), "time", 3600).unwrap();
+    ///
+    /// let ids: Vec<Option<&str>> = report.column("session_id").unwrap().utf8().unwrap().into_iter().collect();
+    /// assert_eq!(ids, [Some("2024-02-22_Tokyo"), Some("2024-02-22_London")]);
+    /// assert_eq!(report.column("expected").unwrap().u32().unwrap().get(1), Some(4));
+    /// assert_eq!(report.column("actual").unwrap().u32().unwrap().get(1), Some(3));
+    /// assert_eq!(report.column("coverage").unwrap().f64().unwrap().get(1), Some(0.75));
+    ///
+    /// // London misses the 11:00 bar
+    /// let gap_starts = report.column("gap_starts").unwrap().list().unwrap().get_as_series(1).unwrap();
+    /// let gap_ends = report.column("gap_ends").unwrap().list().unwrap().get_as_series(1).unwrap();
+    /// assert_eq!(gap_starts.i64().unwrap().get(0), Some(1708599600));
+    /// assert_eq!(gap_ends.i64().unwrap().get(0), Some(1708603200));
+    ///
+    /// // Tokyo only has the 04:00 bar of its seven, before and after which bars are missing
+    /// assert_eq!(report.column("gap_starts").unwrap().list().unwrap().get_as_series(0).unwrap().len(), 2);
+    /// ```
+    pub fn coverage_report(lazyframe: LazyFrame, time_col: &str, expected_interval: u32) -> Result<DataFrame, TradingSessionsError> {
+        SessionColumn::new(lazyframe).with_columns(time_col, "Session").coverage_report(expected_interval)
+    }
+}
+
+
+#[cfg(feature = "polars")]
+impl Default for SessionColumnBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+#[cfg(feature = "polars")]
+impl SessionResampler {
+    /// Creates a resampler of the given price column, with the default session column configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `price_col` - The name of the price column to aggregate into open, high, low and close.
+    pub fn new(price_col: &str) -> Self {
+        Self { session_column: SessionColumnBuilder::new(), price_column: price_col.to_string(), volume_column: None }
+    }
+
+    /// Sums a volume column into the "volume" column of each bar.
+    pub fn with_volume_column(mut self, volume_col: &str) -> Self {
+        self.volume_column = Some(volume_col.to_string());
+        self
+    }
+
+    /// Configures the session column the bars are grouped by, e.g. the time column, schedule or holidays.
+    ///
+    /// The additional columns of the builder are left out of the bars.
+    pub fn with_session_column(mut self, builder: SessionColumnBuilder) -> Self {
+        self.session_column = builder;
+        self
+    }
+
+    /// Resamples a LazyFrame into one bar per session occurrence.
+    ///
+    /// Rows are expected to be sorted by time, so that the first and last row of each session occurrence
+    /// provide the open and close prices. Rows outside of every scheduled session, e.g. during the
+    /// weekend, are left out.
+    ///
+    /// # Returns
+    ///
+    /// A LazyFrame with the columns `[session_id, Session, session_open, session_close, open, high, low, close]`,
+    /// followed by `volume` if a volume column is set, with the bars in order of first appearance. The
+    /// session bounds are Unix timestamps in seconds, see [`SessionColumn::apply_session_bounds_columns`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`SessionColumn::try_apply_session_column`], e.g. a
+    /// `TradingSessionsError::MissingColumn` if the LazyFrame has no timestamp column.
+    pub fn resample(&self, lazyframe: LazyFrame) -> Result<LazyFrame, TradingSessionsError> {
+        let mut column = SessionColumn { lazyframe, ..self.session_column.column.clone() }.try_apply_session_column()?;
+        column.apply_session_id_column();
+        column.apply_session_bounds_columns();
+
+        let price = col(&self.price_column);
+        let mut aggregations = vec![
+            col(&column.output_column).first(),
+            col("session_open").first(),
+            col("session_close").first(),
+            price.clone().first().alias("open"),
+            price.clone().max().alias("high"),
+            price.clone().min().alias("low"),
+            price.last().alias("close"),
+        ];
+        if let Some(volume_col) = &self.volume_column {
+            aggregations.push(col(volume_col).sum().alias("volume"));
+        }
+
+        Ok(column
+            .into_lazyframe()
+            .filter(col("session_open").is_not_null())
+            .group_by_stable([col("session_id")])
+            .agg(aggregations))
+    }
+}
+
+
+/// The temporary column holding the time each row is split by, see [`SessionSplit::split`].
+#[cfg(feature = "polars")]
+const SPLIT_TIME_COLUMN: &str = "__session_split_time";
+
+
+#[cfg(feature = "polars")]
+impl SessionSplit {
+    /// Creates a split of the given time column and share of test rows, stratified by the "Session" column.
+    ///
+    /// # Arguments
+    ///
+    /// * `time_col` - The column the rows are ordered by, e.g. of Unix timestamps or `Datetime` values.
+    /// * `test_fraction` - The share of the rows of each session to put in the test set, clamped to `[0, 1]`.
+    pub fn new(time_col: &str, test_fraction: f64) -> Self {
+        Self {
+            time_column: time_col.to_string(),
+            session_column: "Session".to_string(),
+            instance_column: None,
+            test_fraction: test_fraction.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Sets the session column to stratify by, e.g. a renamed session column.
+    pub fn with_session_column(mut self, session_col: &str) -> Self {
+        self.session_column = session_col.to_string();
+        self
+    }
+
+    /// Keeps every session occurrence whole, e.g. by the "session_id" column of
+    /// [`SessionColumn::apply_session_id_column`], so that bars of one occurrence don't leak into both sets.
+    ///
+    /// The latest occurrences of each session by their first row go to the test set, so the share of
+    /// test rows follows the test fraction as closely as the size of the occurrences allows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polars::prelude::*;
+    /// use trading_sessions::{SessionColumn, SessionSplit};
+    ///
+    /// // Thursday 22 and Friday 23 February 2024, 10:00 and 11:00 UTC in London
+    /// let df = df! {
+    ///     "time" => [1708596000i64, 1708599600, 1708682400, 1708686000]
+    /// }.unwrap();
+    /// let mut session_column = SessionColumn::new(df.lazy()).apply_session_column();
+    /// session_column.apply_session_id_column();
+    ///
+    /// let (train, test) = SessionSplit::new("time", 0.5).with_instance_column("session_id").split(session_column.into_lazyframe());
+    /// let test = test.collect().unwrap();
+    ///
+    /// // The Friday occurrence goes to the test set as a whole
+    /// assert_eq!(train.collect().unwrap().height(), 2);
+    /// let ids: Vec<Option<&str>> = test.column("session_id").unwrap().utf8().unwrap().into_iter().collect();
+    /// assert_eq!(ids, [Some("2024-02-23_London"), Some("2024-02-23_London")]);
+    /// ```
+    pub fn with_instance_column(mut self, instance_col: &str) -> Self {
+        self.instance_column = Some(instance_col.to_string());
+        self
+    }
+
+    /// Splits a labeled LazyFrame into train and test sets.
+    ///
+    /// Rows with a null session are stratified together, and a session too small to split, e.g. of a
+    /// single row, stays in the train set. The order of the rows is kept within each set.
+    ///
+    /// # Returns
+    ///
+    /// A `(train, test)` tuple of LazyFrames, which fail on collection if a column is missing.
+    pub fn split(&self, lazyframe: LazyFrame) -> (LazyFrame, LazyFrame) {
+        // Occurrences are ordered by their first row, which is materialized as the split time since windows don't nest
+        let split_time = match &self.instance_column {
+            Some(instance_col) => col(&self.time_column).min().over([col(instance_col)]),
+            None => col(&self.time_column),
+        };
+        let lazyframe = lazyframe.with_column(split_time.alias(SPLIT_TIME_COLUMN));
+
+        // The test rows are those after the time up to which the train share of each session falls
+        let is_test = match self.test_fraction {
+            fraction if fraction <= 0.0 => lit(false),
+            fraction if fraction >= 1.0 => lit(true),
+            fraction => {
+                let threshold = col(SPLIT_TIME_COLUMN).quantile(lit(1.0 - fraction), QuantileInterpolOptions::Lower);
+                col(SPLIT_TIME_COLUMN).gt(threshold.over([col(&self.session_column)]))
+            }
+        };
+        let split = |is_test: Expr| lazyframe.clone().filter(is_test).drop_columns([SPLIT_TIME_COLUMN]);
+        (split(is_test.clone().not()), split(is_test))
+    }
+}
+
+
+#[cfg(feature = "polars")]
+impl SessionExprExt for Expr {
+    fn session(self) -> SessionNameSpace {
+        SessionNameSpace(self)
+    }
+}
+
+
+#[cfg(feature = "polars")]
+impl SessionNameSpace {
+    /// Identifies the trading session label of each timestamp, like [`session_series_named`].
+    ///
+    /// # Returns
+    ///
+    /// A Utf8 expression keeping the name of the timestamps. Null timestamps stay null, and the
+    /// query fails if the timestamps are not integers or datetimes.
+    pub fn label(self) -> Expr {
+        self.0.map(|times| session_series_named(&times, times.name()).map(Some), GetOutput::from_type(DataType::Utf8))
+    }
+
+    /// Determines whether the market is open at each timestamp, i.e. outside of the weekend, like [`is_market_open`].
+    ///
+    /// # Returns
+    ///
+    /// A Boolean expression keeping the name of the timestamps. Null timestamps stay null, and the
+    /// query fails if the timestamps are not integers or datetimes.
+    pub fn is_open(self) -> Expr {
+        self.0.map(|times| {
+            let ticks_per_second = ticks_per_second_of(times.dtype())?;
+            let open: BooleanChunked = times
+                .cast(&DataType::Int64)?
+                .i64()?
+                .into_iter()
+                .map(|ticks| ticks.map(|ticks| is_market_open(ticks.div_euclid(ticks_per_second))))
+                .collect();
+            Ok(Some(open.with_name(times.name()).into_series()))
+        }, GetOutput::from_type(DataType::Boolean))
+    }
+}
+
+
+/// Builds the expression mapping the number of seconds since midnight UTC to the trading session names of a schedule,
+/// with the fallback label for the seconds outside of every window.
+#[cfg(feature = "polars")]
+fn schedule_session_expr(seconds_of_day: Expr, is_weekend: Expr, schedule: &SessionSchedule, fallback_label: &str) -> Expr {
+    let session = schedule.windows
+        .iter()
+        .rev()
+        .fold(lit(fallback_label), |otherwise, window| {
+            let after_start = seconds_of_day.clone().gt_eq(lit(window.start));
+            let before_end = seconds_of_day.clone().lt(lit(window.end));
+            let within = match window.start <= window.end {
+                true => after_start.and(before_end),
+                false => after_start.or(before_end),
+            };
+            let label = match window.session {
+                TradingSession::Undefined => fallback_label,
+                session => session.as_str(),
+            };
+            when(within)
+                .then(lit(label))
+                .otherwise(otherwise)
+        });
+
+    when(is_weekend).then(lit(TradingSession::Weekend.as_str())).otherwise(session)
+}
+
+
+/// Classifies a series of `Int64` timestamps into trading session names, splitting the work across threads,
+/// under the schedule `schedule_at` returns for each Unix timestamp in seconds.
+#[cfg(feature = "polars")]
+fn classify_parallel<'a>(ticks: &Series, ticks_per_second: i64, schedule_at: impl Fn(i64) -> &'a SessionSchedule + Sync,
+                         holidays: Option<&HolidayCalendar>, fallback_label: &str, null_label: Option<&str>, weekend: bool) -> PolarsResult<Series> {
+    let ticks: Vec<Option<i64>> = ticks.i64()?.into_iter().collect();
+    let threads = std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get);
+    let block_size = ticks.len().div_ceil(threads).max(1);
+    let schedule_at = &schedule_at;
+
+    let sessions: Vec<Option<&str>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = ticks
+            .chunks(block_size)
+            .map(|block| {
+                scope.spawn(move || {
+                    block
+                        .iter()
+                        .map(|tick| match tick {
+                            Some(tick) => {
+                                let unix_timestamp = tick.div_euclid(ticks_per_second);
+                                let schedule = schedule_at(unix_timestamp);
+                                let session = match weekend && schedule.is_weekend(unix_timestamp) {
+                                    true => TradingSession::Weekend,
+                                    false => schedule.session_for_seconds(seconds_of_day(unix_timestamp)),
+                                };
+                                let session = match holidays {
+                                    Some(calendar) if calendar.is_holiday(unix_timestamp) => TradingSession::Holiday,
+                                    Some(calendar) if calendar.is_overridden(unix_timestamp, session) => TradingSession::Undefined,
+                                    _ => session,
+                                };
+                                match session {
+                                    TradingSession::Undefined => Some(fallback_label),
+                                    session => Some(session.as_str()),
+                                }
+                            }
+                            None => null_label,
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("session classification thread panicked"))
+            .collect()
+    });
+
+    Ok(Series::new("Session", sessions))
+}
+
+
+/// Builds the aggregation of each value column, keeping the column names.
+#[cfg(feature = "polars")]
+fn session_aggregations(value_cols: &[&str], agg: SessionAgg) -> Vec<Expr> {
+    value_cols
+        .iter()
+        .map(|&value_col| match agg {
+            SessionAgg::Sum => col(value_col).sum(),
+            SessionAgg::Mean => col(value_col).mean(),
+            SessionAgg::Min => col(value_col).min(),
+            SessionAgg::Max => col(value_col).max(),
+            SessionAgg::Count => col(value_col).count(),
+        })
+        .collect()
+}
+
+
+/// Builds the expression of the Euclidean remainder of an integer expression, which unlike `%` is
+/// never negative, e.g. for timestamps before 1970.
+#[cfg(feature = "polars")]
+fn rem_euclid_expr(expr: Expr, divisor: u32) -> Expr {
+    ((expr % lit(divisor)) + lit(divisor)) % lit(divisor)
+}
+
+
+/// Builds the expression of the Euclidean quotient of an integer expression, which unlike `/`
+/// rounds towards negative infinity, e.g. for timestamps before 1970.
+#[cfg(feature = "polars")]
+fn div_euclid_expr(expr: Expr, divisor: u32) -> Expr {
+    (expr.clone() - rem_euclid_expr(expr, divisor)) / lit(divisor)
+}
+
+
+/// Builds the expression marking the rows on which the given session expression changes, the first row included.
+#[cfg(feature = "polars")]
+fn session_change_expr(session: Expr) -> Expr {
+    session.clone().shift(lit(1)).neq(session)
+        .fill_null(lit(true))
+}